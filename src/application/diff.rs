@@ -0,0 +1,200 @@
+use crate::domain::entities::{Contact, FieldChange};
+use std::collections::{HashMap, HashSet};
+
+/// A contact present in both phonebooks whose fields differ
+#[derive(Debug, Clone)]
+pub struct ChangedContact {
+    pub a: Contact,
+    pub b: Contact,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Differences between two sets of contacts
+#[derive(Debug, Clone, Default)]
+pub struct ContactsDiff {
+    pub only_in_a: Vec<Contact>,
+    pub only_in_b: Vec<Contact>,
+    pub changed: Vec<ChangedContact>,
+    /// Contacts present in both with identical fields
+    pub unchanged: usize,
+}
+
+/// How the current contacts differ from an earlier snapshot of them
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotChanges {
+    /// Contacts whose ID isn't in the snapshot
+    pub added: Vec<Contact>,
+    /// Contacts in the snapshot whose fields have changed since
+    pub modified: Vec<ChangedContact>,
+    pub unchanged: usize,
+}
+
+impl ContactsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.only_in_a.is_empty() && self.only_in_b.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two sets of contacts
+/// Contacts are matched by ID first; the rest are matched to the same person
+/// (`Contact::same_person`) if there is one, else by a shared normalized
+/// phone number or email address
+pub fn diff_contacts(a: &[Contact], b: &[Contact]) -> ContactsDiff {
+    let mut diff = ContactsDiff::default();
+    let mut matched_b: HashSet<usize> = HashSet::new();
+    let mut unmatched_a = Vec::new();
+
+    for contact in a {
+        match b.iter().position(|other| other.id() == contact.id()) {
+            Some(index) => {
+                matched_b.insert(index);
+                record_pair(&mut diff, contact, &b[index]);
+            }
+            None => unmatched_a.push(contact),
+        }
+    }
+
+    for contact in unmatched_a {
+        let unmatched = || b.iter().enumerate().filter(|(index, _)| !matched_b.contains(index));
+        let fallback = unmatched()
+            .find(|(_, other)| contact.same_person(other))
+            .or_else(|| unmatched().find(|(_, other)| shares_contact_method(contact, other)));
+
+        match fallback {
+            Some((index, other)) => {
+                matched_b.insert(index);
+                record_pair(&mut diff, contact, other);
+            }
+            None => diff.only_in_a.push(contact.clone()),
+        }
+    }
+
+    diff.only_in_b = b
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !matched_b.contains(index))
+        .map(|(_, contact)| contact.clone())
+        .collect();
+
+    diff
+}
+
+/// Compare the current contacts with a snapshot of them
+/// Unlike `diff_contacts`, contacts are matched by ID only, so a contact
+/// that was deleted and re-created counts as added
+pub fn changes_since(snapshot: &[Contact], current: &[Contact]) -> SnapshotChanges {
+    let snapshot_by_id: HashMap<_, _> = snapshot.iter().map(|contact| (contact.id(), contact)).collect();
+    let mut changes = SnapshotChanges::default();
+
+    for contact in current {
+        match snapshot_by_id.get(&contact.id()) {
+            Some(before) => {
+                let fields = before.changes_to(contact);
+                if fields.is_empty() {
+                    changes.unchanged += 1;
+                } else {
+                    changes.modified.push(ChangedContact {
+                        a: (*before).clone(),
+                        b: contact.clone(),
+                        changes: fields,
+                    });
+                }
+            }
+            None => changes.added.push(contact.clone()),
+        }
+    }
+
+    changes
+}
+
+fn record_pair(diff: &mut ContactsDiff, a: &Contact, b: &Contact) {
+    let changes = a.changes_to(b);
+    if changes.is_empty() {
+        diff.unchanged += 1;
+    } else {
+        diff.changed.push(ChangedContact {
+            a: a.clone(),
+            b: b.clone(),
+            changes,
+        });
+    }
+}
+
+/// Whether two contacts share a phone number (in any format) or an email address
+fn shares_contact_method(a: &Contact, b: &Contact) -> bool {
+    a.phone_numbers()
+        .iter()
+        .any(|p| b.phone_numbers().iter().any(|q| p.normalized() == q.normalized()))
+        || a.emails()
+            .iter()
+            .any(|e| b.emails().iter().any(|f| e.canonical() == f.canonical()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::PhoneNumber;
+
+    fn contact(first_name: &str, phone: &str) -> Contact {
+        Contact::new(
+            first_name.to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new(phone.to_string()).unwrap()],
+            vec![],
+        )
+    }
+
+    fn names(contacts: &[Contact]) -> Vec<&str> {
+        contacts.iter().map(|contact| contact.first_name()).collect()
+    }
+
+    #[test]
+    fn contacts_are_matched_by_id_and_compared_field_by_field() {
+        let same = contact("Ann", "5550000001");
+        let before = contact("Bob", "5550000002");
+        let mut after = before.clone();
+        after.set_notes(Some("new job".to_string()));
+        let only_a = contact("Cid", "5550000003");
+        let only_b = contact("Dee", "5550000004");
+
+        let diff = diff_contacts(&[same.clone(), before, only_a], &[only_b, after, same]);
+
+        assert_eq!(diff.unchanged, 1);
+        assert_eq!(names(&diff.only_in_a), ["Cid"]);
+        assert_eq!(names(&diff.only_in_b), ["Dee"]);
+        assert_eq!(diff.changed.len(), 1);
+        let changed = &diff.changed[0];
+        assert_eq!(changed.a.first_name(), "Bob");
+        assert_eq!(changed.changes.len(), 1);
+        assert_eq!(changed.changes[0].field, "notes");
+        assert_eq!(changed.changes[0].new.as_deref(), Some("new job"));
+    }
+
+    #[test]
+    fn contacts_with_different_ids_fall_back_to_a_shared_phone_number() {
+        let a = contact("Ann", "5550000001");
+        let b = contact("Annie", "(555) 000-0001");
+
+        let diff = diff_contacts(&[a], &[b]);
+
+        assert!(diff.only_in_a.is_empty() && diff.only_in_b.is_empty());
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].changes[0].field, "first_name");
+        assert!(diff_contacts(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn snapshot_changes_match_by_id_only() {
+        let kept = contact("Ann", "5550000001");
+        let recreated = contact("Bob", "5550000002");
+        let mut edited = kept.clone();
+        edited.set_last_name("Smith".to_string());
+
+        let changes = changes_since(&[kept, recreated.clone()], &[edited, contact("Bob", "5550000002")]);
+
+        assert_eq!(names(&changes.added), ["Bob"]);
+        assert_eq!(changes.modified.len(), 1);
+        assert_eq!(changes.modified[0].changes[0].field, "last_name");
+        assert_eq!(changes.unchanged, 0);
+    }
+}
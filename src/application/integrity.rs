@@ -0,0 +1,188 @@
+use crate::domain::entities::Contact;
+use crate::domain::value_objects::{Email, PhoneNumber, PhoneValidationPolicy};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A single problem found while checking stored contacts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityIssue {
+    /// Key of the entry in the contacts file
+    pub key: String,
+    pub problem: String,
+}
+
+impl fmt::Display for IntegrityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.problem)
+    }
+}
+
+/// Read-only integrity scan over stored contact entries
+/// Works on `(key, contact)` pairs so it can run against crafted in-memory data
+pub struct IntegrityChecker {
+    phone_policy: PhoneValidationPolicy,
+}
+
+impl IntegrityChecker {
+    pub fn new(phone_policy: PhoneValidationPolicy) -> Self {
+        Self { phone_policy }
+    }
+
+    /// Check every entry and return the problems found, in entry order
+    /// Entries that could not be deserialized carry their error and are reported as unreadable
+    pub fn check(&self, entries: &[(String, Result<Contact, String>)]) -> Vec<IntegrityIssue> {
+        let mut issues = Vec::new();
+        let mut seen_ids: HashMap<String, usize> = HashMap::new();
+
+        for (key, contact) in entries {
+            let mut report = |problem: String| {
+                issues.push(IntegrityIssue {
+                    key: key.clone(),
+                    problem,
+                })
+            };
+
+            let key_is_uuid = uuid::Uuid::parse_str(key).is_ok();
+            if !key_is_uuid {
+                report("key is not a valid UUID".to_string());
+            }
+
+            let contact = match contact {
+                Ok(contact) => contact,
+                Err(e) => {
+                    report(format!("entry could not be read: {}", e));
+                    continue;
+                }
+            };
+
+            let id = contact.id().to_string();
+            if key_is_uuid && *key != id {
+                report(format!("key does not match contact ID {}", id));
+            }
+
+            let occurrences = seen_ids.entry(id.clone()).or_insert(0);
+            *occurrences += 1;
+            if *occurrences == 2 {
+                report(format!("duplicate contact ID {}", id));
+            }
+
+            if let Err(e) = contact.validate() {
+                report(e.to_string());
+            }
+
+            for phone in contact.phone_numbers() {
+                if PhoneNumber::new_with_policy(phone.value().to_string(), &self.phone_policy).is_err() {
+                    report(format!("phone number '{}' does not match the current policy", phone.value()));
+                }
+            }
+
+            for email in contact.emails() {
+                match Email::new(email.value().to_string()) {
+                    Ok(parsed) if parsed.value() == email.value() => {}
+                    _ => report(format!("email '{}' has an invalid format", email.value())),
+                }
+            }
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn contact() -> Contact {
+        Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        )
+    }
+
+    /// Round-trip a contact through JSON so stored values can bypass validation
+    fn edited(contact: &Contact, edit: impl FnOnce(&mut serde_json::Value)) -> Contact {
+        let mut value = serde_json::to_value(contact).unwrap();
+        edit(&mut value);
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn check(entries: &[(String, Contact)]) -> Vec<String> {
+        let entries: Vec<_> = entries.iter().map(|(key, contact)| (key.clone(), Ok(contact.clone()))).collect();
+        IntegrityChecker::new(PhoneValidationPolicy::default())
+            .check(&entries)
+            .into_iter()
+            .map(|issue| issue.problem)
+            .collect()
+    }
+
+    #[test]
+    fn clean_entries_have_no_issues() {
+        let contact = contact();
+        assert!(check(&[(contact.id().to_string(), contact)]).is_empty());
+    }
+
+    #[test]
+    fn bad_phones_and_emails_are_reported() {
+        let contact = edited(&contact(), |value| {
+            value["phone_numbers"] = json!([{ "value": "12345" }]);
+            value["emails"] = json!([{ "value": "not-an-email" }]);
+        });
+
+        let problems = check(&[(contact.id().to_string(), contact)]);
+
+        assert!(problems.contains(&"phone number '12345' does not match the current policy".to_string()));
+        assert!(problems.contains(&"email 'not-an-email' has an invalid format".to_string()));
+    }
+
+    #[test]
+    fn invalid_contacts_are_reported() {
+        let contact = edited(&contact(), |value| value["first_name"] = json!("  "));
+
+        assert_eq!(check(&[(contact.id().to_string(), contact)]).len(), 1);
+    }
+
+    #[test]
+    fn mismatched_keys_and_duplicate_ids_are_reported() {
+        let contact = contact();
+        let id = contact.id().to_string();
+        let other = uuid::Uuid::new_v4().to_string();
+
+        let problems = check(&[
+            (id.clone(), contact.clone()),
+            (other, contact.clone()),
+            ("not-a-uuid".to_string(), contact),
+        ]);
+
+        assert_eq!(
+            problems,
+            [
+                format!("key does not match contact ID {}", id),
+                format!("duplicate contact ID {}", id),
+                "key is not a valid UUID".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn unreadable_entries_are_reported_by_key() {
+        let contact = contact();
+        let id = contact.id().to_string();
+        let entries = vec![
+            ("00000000-0000-4000-8000-000000000009".to_string(), Err("invalid type: integer `42`".to_string())),
+            (id, Ok(contact)),
+        ];
+
+        let issues = IntegrityChecker::new(PhoneValidationPolicy::default()).check(&entries);
+
+        assert_eq!(
+            issues,
+            [IntegrityIssue {
+                key: "00000000-0000-4000-8000-000000000009".to_string(),
+                problem: "entry could not be read: invalid type: integer `42`".to_string(),
+            }]
+        );
+    }
+}
@@ -1,7 +1,13 @@
 pub mod use_cases;
 pub mod services;
 pub mod validation;
+pub mod integrity;
+pub mod diff;
+pub mod query;
 
 pub use use_cases::*;
 pub use services::*;
 pub use validation::*;
+pub use integrity::*;
+pub use diff::*;
+pub use query::*;
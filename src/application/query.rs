@@ -0,0 +1,361 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::DomainError;
+use std::fmt;
+
+/// Filter expression over contacts, e.g. `tag:work and (email:*@acme.com or phone:555)`
+///
+/// Predicates have the form `field:value`; `and` binds tighter than `or`, and
+/// parentheses group. Values are matched case-insensitively: `*` matches any run
+/// of characters, and a value without `*` matches as a substring (tags must match
+/// exactly). Values containing spaces can be double-quoted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterExpr {
+    Predicate { field: FilterField, value: String },
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+/// Contact field a predicate tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Id,
+    Name,
+    FirstName,
+    LastName,
+    Phone,
+    Email,
+    Tag,
+    Notes,
+}
+
+impl FilterField {
+    fn parse(name: &str) -> Result<Self, DomainError> {
+        match name.to_lowercase().as_str() {
+            "id" => Ok(FilterField::Id),
+            "name" => Ok(FilterField::Name),
+            "first" | "first-name" => Ok(FilterField::FirstName),
+            "last" | "last-name" => Ok(FilterField::LastName),
+            "phone" => Ok(FilterField::Phone),
+            "email" => Ok(FilterField::Email),
+            "tag" => Ok(FilterField::Tag),
+            "notes" => Ok(FilterField::Notes),
+            _ => Err(DomainError::Validation(format!(
+                "Unknown filter field '{}' (expected id, name, first, last, phone, email, tag, notes)",
+                name
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for FilterField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FilterField::Id => "id",
+            FilterField::Name => "name",
+            FilterField::FirstName => "first",
+            FilterField::LastName => "last",
+            FilterField::Phone => "phone",
+            FilterField::Email => "email",
+            FilterField::Tag => "tag",
+            FilterField::Notes => "notes",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FilterExpr {
+    /// Parse a filter expression
+    pub fn parse(input: &str) -> Result<Self, DomainError> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(DomainError::Validation("Filter expression is empty".to_string()));
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(DomainError::Validation(format!("Unexpected '{}' in filter", token))),
+        }
+    }
+
+    /// Whether the contact satisfies this expression
+    pub fn matches(&self, contact: &Contact) -> bool {
+        match self {
+            FilterExpr::And(left, right) => left.matches(contact) && right.matches(contact),
+            FilterExpr::Or(left, right) => left.matches(contact) || right.matches(contact),
+            FilterExpr::Predicate { field, value } => match field {
+                FilterField::Id => matches_text(&contact.id().to_string(), value),
+                FilterField::Name => matches_text(&contact.full_name(), value),
+                FilterField::FirstName => matches_text(contact.first_name(), value),
+                FilterField::LastName => matches_text(contact.last_name(), value),
+                FilterField::Phone => contact.phone_numbers().iter().any(|p| matches_text(p.value(), value)),
+                FilterField::Email => contact.emails().iter().any(|e| matches_text(e.value(), value)),
+                FilterField::Tag => contact.tags().iter().any(|t| glob_match(&t.to_lowercase(), value)),
+                FilterField::Notes => contact.notes().is_some_and(|notes| matches_text(notes, value)),
+            },
+        }
+    }
+}
+
+/// Match `text` against a lowercased pattern: a glob if it contains `*`, else a substring
+fn matches_text(text: &str, pattern: &str) -> bool {
+    let text = text.to_lowercase();
+    if pattern.contains('*') {
+        glob_match(&text, pattern)
+    } else {
+        text.contains(pattern)
+    }
+}
+
+/// Glob match where `*` matches any run of characters (including none)
+fn glob_match(text: &str, pattern: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*` at all: the whole text must match
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Predicate(FilterField, String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+            Token::And => write!(f, "and"),
+            Token::Or => write!(f, "or"),
+            Token::Predicate(field, value) => write!(f, "{}:{}", field, value),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, DomainError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == ':' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+
+                if chars.peek() != Some(&':') {
+                    match word.to_lowercase().as_str() {
+                        "and" => tokens.push(Token::And),
+                        "or" => tokens.push(Token::Or),
+                        _ => {
+                            return Err(DomainError::Validation(format!(
+                                "Expected field:value in filter, found '{}'",
+                                word
+                            )))
+                        }
+                    }
+                    continue;
+                }
+                chars.next();
+
+                let field = FilterField::parse(&word)?;
+                let mut value = String::new();
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => value.push(c),
+                            None => return Err(DomainError::Validation("Unterminated quote in filter".to_string())),
+                        }
+                    }
+                } else {
+                    while let Some(&c) = chars.peek() {
+                        if c.is_whitespace() || c == '(' || c == ')' {
+                            break;
+                        }
+                        value.push(c);
+                        chars.next();
+                    }
+                }
+
+                if value.is_empty() {
+                    return Err(DomainError::Validation(format!("Missing value for '{}:' in filter", word)));
+                }
+                tokens.push(Token::Predicate(field, value.to_lowercase()));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive-descent parser: `or` of `and`s of primaries
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, DomainError> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, DomainError> {
+        let mut expr = self.parse_primary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.parse_primary()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_primary(&mut self) -> Result<FilterExpr, DomainError> {
+        match self.next() {
+            Some(Token::Predicate(field, value)) => Ok(FilterExpr::Predicate { field, value }),
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(DomainError::Validation("Missing ')' in filter".to_string())),
+                }
+            }
+            Some(token) => Err(DomainError::Validation(format!("Unexpected '{}' in filter", token))),
+            None => Err(DomainError::Validation("Filter ends unexpectedly".to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Email, PhoneNumber};
+
+    fn predicate(field: FilterField, value: &str) -> Box<FilterExpr> {
+        Box::new(FilterExpr::Predicate { field, value: value.to_string() })
+    }
+
+    fn contact(email: &str, tags: &[&str]) -> Contact {
+        let mut contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![Email::new(email.to_string()).unwrap()],
+        );
+        for tag in tags {
+            contact.add_tag(tag.to_string());
+        }
+        contact
+    }
+
+    fn is_invalid(input: &str) -> bool {
+        matches!(FilterExpr::parse(input), Err(DomainError::Validation(_)))
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        let expr = FilterExpr::parse("tag:a or tag:b and tag:c").unwrap();
+        assert_eq!(
+            expr,
+            FilterExpr::Or(
+                predicate(FilterField::Tag, "a"),
+                Box::new(FilterExpr::And(predicate(FilterField::Tag, "b"), predicate(FilterField::Tag, "c"))),
+            )
+        );
+
+        let grouped = FilterExpr::parse("(tag:a OR tag:b) AND tag:c").unwrap();
+        assert_eq!(
+            grouped,
+            FilterExpr::And(
+                Box::new(FilterExpr::Or(predicate(FilterField::Tag, "a"), predicate(FilterField::Tag, "b"))),
+                predicate(FilterField::Tag, "c"),
+            )
+        );
+
+        let tagged_a = contact("ann@example.com", &["a"]);
+        assert!(expr.matches(&tagged_a));
+        assert!(!grouped.matches(&tagged_a));
+    }
+
+    #[test]
+    fn values_match_as_globs_or_substrings() {
+        let work = contact("Ann@Acme.com", &["work"]);
+
+        assert!(FilterExpr::parse("tag:work and email:*@acme.com").unwrap().matches(&work));
+        assert!(FilterExpr::parse("email:acme").unwrap().matches(&work));
+        assert!(!FilterExpr::parse("email:*@acme").unwrap().matches(&work));
+        assert!(!FilterExpr::parse("tag:wor").unwrap().matches(&work), "tags match exactly");
+        assert!(FilterExpr::parse("tag:wo*").unwrap().matches(&work));
+        assert!(FilterExpr::parse(r#"name:"ann tester""#).unwrap().matches(&work));
+    }
+
+    #[test]
+    fn invalid_syntax_is_a_validation_error() {
+        for input in [
+            "",
+            "tag:",
+            "work",
+            "tag:a and",
+            "tag:a or or tag:b",
+            "(tag:a",
+            "tag:a)",
+            "color:red",
+            r#"name:"ann"#,
+        ] {
+            assert!(is_invalid(input), "{:?} should not parse", input);
+        }
+    }
+}
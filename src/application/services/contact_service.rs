@@ -1,5 +1,8 @@
 use crate::application::use_cases::*;
-use crate::domain::repositories::ContactRepositorySync;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
+use crate::domain::entities::Contact;
+use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
 use std::sync::Arc;
 
 /// Application service that orchestrates use cases
@@ -11,6 +14,16 @@ pub struct ContactService {
     delete_contact_use_case: DeleteContactUseCase,
     list_contacts_use_case: ListContactsUseCase,
     search_contacts_use_case: SearchContactsUseCase,
+    upcoming_birthdays_use_case: UpcomingBirthdaysUseCase,
+    export_contacts_use_case: ExportContactsUseCase,
+    delete_many_contacts_use_case: DeleteManyContactsUseCase,
+    purge_all_contacts_use_case: PurgeAllContactsUseCase,
+    rename_tag_use_case: RenameTagUseCase,
+    restore_contact_use_case: RestoreContactUseCase,
+    suggest_tags_use_case: SuggestTagsUseCase,
+    random_contacts_use_case: RandomContactsUseCase,
+    touch_contact_use_case: TouchContactUseCase,
+    stats_use_case: StatsUseCase,
 }
 
 impl ContactService {
@@ -21,31 +34,198 @@ impl ContactService {
             update_contact_use_case: UpdateContactUseCase::new(repository.clone()),
             delete_contact_use_case: DeleteContactUseCase::new(repository.clone()),
             list_contacts_use_case: ListContactsUseCase::new(repository.clone()),
-            search_contacts_use_case: SearchContactsUseCase::new(repository),
+            search_contacts_use_case: SearchContactsUseCase::new(repository.clone()),
+            upcoming_birthdays_use_case: UpcomingBirthdaysUseCase::new(repository.clone()),
+            export_contacts_use_case: ExportContactsUseCase::new(repository.clone()),
+            delete_many_contacts_use_case: DeleteManyContactsUseCase::new(repository.clone()),
+            purge_all_contacts_use_case: PurgeAllContactsUseCase::new(repository.clone()),
+            rename_tag_use_case: RenameTagUseCase::new(repository.clone()),
+            restore_contact_use_case: RestoreContactUseCase::new(repository.clone()),
+            suggest_tags_use_case: SuggestTagsUseCase::new(repository.clone()),
+            random_contacts_use_case: RandomContactsUseCase::new(repository.clone()),
+            touch_contact_use_case: TouchContactUseCase::new(repository.clone()),
+            stats_use_case: StatsUseCase::new(repository),
         }
     }
 
-    pub fn add_contact(&self, request: AddContactRequest) -> Result<AddContactResponse, crate::domain::repositories::RepositoryError> {
+    pub fn add_contact(&self, request: AddContactRequest) -> Result<AddContactResponse, ApplicationError> {
         self.add_contact_use_case.execute(request)
     }
 
-    pub fn find_contact(&self, request: FindContactRequest) -> Result<FindContactResponse, crate::domain::repositories::RepositoryError> {
+    /// Return the ID of a stored contact sharing a phone number or email with
+    /// `request`, or add it as a new contact. The flag is `true` when the
+    /// contact was newly created. Phones are matched in any format and emails
+    /// by their canonical form; phones are checked first, and among several
+    /// matches the lowest ID wins so repeated calls agree.
+    pub fn find_or_create(&self, request: AddContactRequest) -> Result<(ContactId, bool), ApplicationError> {
+        for phone in &request.phone_numbers {
+            if let Some(existing) = self.find_contacts_by_phone(phone)?.iter().map(Contact::id).min() {
+                return Ok((existing.clone(), false));
+            }
+        }
+
+        for email in &request.emails {
+            if let Some(existing) = self.find_contacts_by_email(email)?.iter().map(Contact::id).min() {
+                return Ok((existing.clone(), false));
+            }
+        }
+
+        let response = self.add_contact(request)?;
+        Ok((response.contact_id, true))
+    }
+
+    pub fn find_contact(&self, request: FindContactRequest) -> Result<FindContactResponse, ApplicationError> {
         self.find_contact_use_case.execute(request)
     }
 
-    pub fn update_contact(&self, request: UpdateContactRequest) -> Result<UpdateContactResponse, crate::domain::repositories::RepositoryError> {
+    /// Resolve a full contact ID or a unique ID prefix
+    pub fn resolve_contact_id(&self, input: &str) -> Result<ContactId, ApplicationError> {
+        self.find_contact_use_case.resolve_id(input)
+    }
+
+    /// Resolve a full contact ID or a unique ID prefix of a contact in the trash
+    pub fn resolve_deleted_contact_id(&self, input: &str) -> Result<ContactId, ApplicationError> {
+        self.find_contact_use_case.resolve_deleted_id(input)
+    }
+
+    /// Resolve a full contact ID or a unique ID prefix, in the trash or not
+    pub fn resolve_any_contact_id(&self, input: &str) -> Result<ContactId, ApplicationError> {
+        self.find_contact_use_case.resolve_any_id(input)
+    }
+
+    /// Find contacts by phone number, in any format
+    pub fn find_contacts_by_phone(&self, phone: &PhoneNumber) -> Result<Vec<Contact>, ApplicationError> {
+        self.find_contact_use_case.find_by_phone(phone)
+    }
+
+    /// Find contacts by email address
+    pub fn find_contacts_by_email(&self, email: &Email) -> Result<Vec<Contact>, ApplicationError> {
+        self.find_contact_use_case.find_by_email(email)
+    }
+
+    /// Find contacts sharing tags with a contact, most shared tags first
+    pub fn find_related_contacts(&self, id: &ContactId) -> Result<Vec<RelatedContact>, ApplicationError> {
+        self.find_contact_use_case.find_related(id)
+    }
+
+    pub fn update_contact(&self, request: UpdateContactRequest) -> Result<UpdateContactResponse, ApplicationError> {
         self.update_contact_use_case.execute(request)
     }
 
-    pub fn delete_contact(&self, request: DeleteContactRequest) -> Result<DeleteContactResponse, crate::domain::repositories::RepositoryError> {
+    pub fn delete_contact(&self, request: DeleteContactRequest) -> Result<DeleteContactResponse, ApplicationError> {
         self.delete_contact_use_case.execute(request)
     }
 
-    pub fn list_contacts(&self, request: ListContactsRequest) -> Result<ListContactsResponse, crate::domain::repositories::RepositoryError> {
+    pub fn list_contacts(&self, request: ListContactsRequest) -> Result<ListContactsResponse, ApplicationError> {
         self.list_contacts_use_case.execute(request)
     }
 
-    pub fn search_contacts(&self, request: SearchContactsRequest) -> Result<SearchContactsResponse, crate::domain::repositories::RepositoryError> {
+    pub fn search_contacts(&self, request: SearchContactsRequest) -> Result<SearchContactsResponse, ApplicationError> {
         self.search_contacts_use_case.execute(request)
     }
+
+    pub fn upcoming_birthdays(&self, request: UpcomingBirthdaysRequest) -> Result<UpcomingBirthdaysResponse, ApplicationError> {
+        self.upcoming_birthdays_use_case.execute(request)
+    }
+
+    pub fn export_contacts(&self, request: ExportContactsRequest) -> Result<ExportContactsResponse, ApplicationError> {
+        self.export_contacts_use_case.execute(request)
+    }
+
+    pub fn export_contacts_streaming(
+        &self,
+        request: &ExportContactsRequest,
+        write: &mut dyn FnMut(&[&Contact]) -> Result<(), RepositoryError>,
+    ) -> Result<ExportContactsSummary, ApplicationError> {
+        self.export_contacts_use_case.execute_streaming(request, write)
+    }
+
+    pub fn delete_many_contacts(&self, request: DeleteManyContactsRequest) -> Result<DeleteManyContactsResponse, ApplicationError> {
+        self.delete_many_contacts_use_case.execute(request)
+    }
+
+    pub fn purge_all_contacts(&self, request: PurgeAllContactsRequest) -> Result<PurgeAllContactsResponse, ApplicationError> {
+        self.purge_all_contacts_use_case.execute(request)
+    }
+
+    pub fn rename_tag(&self, request: RenameTagRequest) -> Result<RenameTagResponse, ApplicationError> {
+        self.rename_tag_use_case.execute(request)
+    }
+
+    pub fn restore_contact(&self, request: RestoreContactRequest) -> Result<RestoreContactResponse, ApplicationError> {
+        self.restore_contact_use_case.execute(request)
+    }
+
+    pub fn suggest_tags(&self, request: SuggestTagsRequest) -> Result<SuggestTagsResponse, ApplicationError> {
+        self.suggest_tags_use_case.execute(request)
+    }
+
+    pub fn random_contacts(&self, request: RandomContactsRequest) -> Result<RandomContactsResponse, ApplicationError> {
+        self.random_contacts_use_case.execute(request)
+    }
+
+    pub fn touch_contact(&self, request: TouchContactRequest) -> Result<TouchContactResponse, ApplicationError> {
+        self.touch_contact_use_case.execute(request)
+    }
+
+    pub fn stats(&self, request: StatsRequest) -> Result<StatsResponse, ApplicationError> {
+        self.stats_use_case.execute(request)
+    }
+
+    /// Count contacts per creation day, week or month
+    pub fn stats_over_time(&self, bucket: TimeBucket) -> Result<Vec<(String, usize)>, ApplicationError> {
+        self.stats_use_case.over_time(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::FileContactRepository;
+
+    fn service_in(dir: &tempfile::TempDir) -> ContactService {
+        ContactService::new(Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        )))
+    }
+
+    fn request(first_name: &str, phone: Option<&str>, email: Option<&str>) -> AddContactRequest {
+        AddContactRequest {
+            first_name: first_name.to_string(),
+            last_name: "Tester".to_string(),
+            phone_numbers: phone.map(|p| PhoneNumber::new(p.to_string()).unwrap()).into_iter().collect(),
+            emails: email.map(|e| Email::new(e.to_string()).unwrap()).into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn find_or_create_adds_a_contact_with_unknown_details() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service_in(&dir);
+
+        let (ann, created) = service.find_or_create(request("Ann", Some("555-000-0001"), None)).unwrap();
+        assert!(created);
+        let (bob, created) = service.find_or_create(request("Bob", Some("5550000002"), Some("bob@example.com"))).unwrap();
+        assert!(created);
+
+        assert_ne!(ann, bob);
+        let found = service.find_contact(FindContactRequest { contact_id: bob }).unwrap().contact.unwrap();
+        assert_eq!(found.first_name(), "Bob");
+    }
+
+    #[test]
+    fn find_or_create_returns_the_contact_sharing_a_phone_or_email() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service_in(&dir);
+        let (ann, _) = service.find_or_create(request("Ann", Some("5550000001"), Some("ann.t@gmail.com"))).unwrap();
+
+        let by_phone = service.find_or_create(request("Annie", Some("(555) 000-0001"), None)).unwrap();
+        let by_email = service.find_or_create(request("Anna", None, Some("AnnT+news@gmail.com"))).unwrap();
+
+        assert_eq!(by_phone, (ann.clone(), false));
+        assert_eq!(by_email, (ann.clone(), false));
+        let found = service.find_contact(FindContactRequest { contact_id: ann }).unwrap().contact.unwrap();
+        assert_eq!(found.first_name(), "Ann", "the found contact is left unchanged");
+    }
 }
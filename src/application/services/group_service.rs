@@ -0,0 +1,40 @@
+use crate::application::use_cases::*;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::{ContactRepositorySync, GroupRepositorySync};
+use std::sync::Arc;
+
+/// Application service for contact groups
+/// Follows the Facade pattern to provide a simplified interface
+pub struct GroupService {
+    create_group_use_case: CreateGroupUseCase,
+    add_group_member_use_case: AddGroupMemberUseCase,
+    remove_group_member_use_case: RemoveGroupMemberUseCase,
+    list_groups_use_case: ListGroupsUseCase,
+}
+
+impl GroupService {
+    pub fn new(groups: Arc<dyn GroupRepositorySync>, contacts: Arc<dyn ContactRepositorySync>) -> Self {
+        Self {
+            create_group_use_case: CreateGroupUseCase::new(groups.clone()),
+            add_group_member_use_case: AddGroupMemberUseCase::new(groups.clone(), contacts.clone()),
+            remove_group_member_use_case: RemoveGroupMemberUseCase::new(groups.clone()),
+            list_groups_use_case: ListGroupsUseCase::new(groups, contacts),
+        }
+    }
+
+    pub fn create_group(&self, request: CreateGroupRequest) -> Result<CreateGroupResponse, ApplicationError> {
+        self.create_group_use_case.execute(request)
+    }
+
+    pub fn add_group_member(&self, request: AddGroupMemberRequest) -> Result<AddGroupMemberResponse, ApplicationError> {
+        self.add_group_member_use_case.execute(request)
+    }
+
+    pub fn remove_group_member(&self, request: RemoveGroupMemberRequest) -> Result<RemoveGroupMemberResponse, ApplicationError> {
+        self.remove_group_member_use_case.execute(request)
+    }
+
+    pub fn list_groups(&self, request: ListGroupsRequest) -> Result<ListGroupsResponse, ApplicationError> {
+        self.list_groups_use_case.execute(request)
+    }
+}
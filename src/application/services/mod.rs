@@ -1,3 +1,5 @@
 pub mod contact_service;
+pub mod group_service;
 
 pub use contact_service::ContactService;
+pub use group_service::GroupService;
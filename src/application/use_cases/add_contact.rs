@@ -1,7 +1,9 @@
 use crate::application::validation::Validator;
-use crate::domain::entities::Contact;
-use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
-use crate::domain::value_objects::{Email, PhoneNumber};
+use crate::domain::entities::{Contact, EmailDedupPolicy};
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::ContactRepositorySync;
+use crate::domain::value_objects::{ContactId, Date, Email, PhoneNumber};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Use case for adding a new contact to the phonebook
@@ -16,47 +18,104 @@ impl AddContactUseCase {
     }
 
     /// Execute the add contact use case
-    pub fn execute(&self, request: AddContactRequest) -> Result<AddContactResponse, RepositoryError> {
+    pub fn execute(&self, request: AddContactRequest) -> Result<AddContactResponse, ApplicationError> {
         // Validate input using domain validation
         Validator::validate_name_component(&request.first_name, "First name")
-            .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+            .map_err(|e| DomainError::Validation(e.to_string()))?;
 
         Validator::validate_name_component(&request.last_name, "Last name")
-            .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+            .map_err(|e| DomainError::Validation(e.to_string()))?;
 
         Validator::validate_contact_methods(&request.phone_numbers, &request.emails)
-            .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+            .map_err(|e| DomainError::Validation(e.to_string()))?;
 
-        // Create contact entity
-        let contact = Contact::new(
-            request.first_name,
-            request.last_name,
-            request.phone_numbers,
-            request.emails,
-        );
+        // Create contact entity, keeping a caller-supplied ID if there is one
+        let mut contact = match request.id {
+            Some(id) => Contact::new_with_id(id, request.first_name, request.last_name, request.phone_numbers, Vec::new()),
+            None => Contact::new(request.first_name, request.last_name, request.phone_numbers, Vec::new()),
+        };
 
-        // Save to repository
-        self.repository.save(contact.clone())?;
+        for email in request.emails {
+            contact.add_email_with_policy(email, request.email_policy);
+        }
+
+        if let Some(notes) = request.notes {
+            if !notes.trim().is_empty() {
+                contact.set_notes(Some(notes));
+            }
+        }
+
+        contact.set_birthday(request.birthday);
+        contact.set_photo_path(request.photo)?;
+
+        for tag in request.tags {
+            if !tag.trim().is_empty() {
+                contact.add_tag(tag);
+            }
+        }
+
+        for (key, value) in request.metadata {
+            Validator::validate_metadata_key(&key)?;
+            contact.set_metadata(key, value);
+        }
+
+        // Save to repository; a replaced contact keeps its creation time, history and version
+        let stored = if request.upsert {
+            self.repository.find_by_id(contact.id())?
+        } else {
+            None
+        };
+        let replaced = stored.is_some();
+        if let Some(stored) = &stored {
+            contact.replace(stored);
+        }
+
+        if request.upsert {
+            self.repository.upsert(contact.clone())?;
+        } else {
+            self.repository.save(contact.clone())?;
+        }
+
+        let message = if replaced {
+            "Contact replaced successfully"
+        } else {
+            "Contact added successfully"
+        };
 
         Ok(AddContactResponse {
             contact_id: contact.id().clone(),
-            message: "Contact added successfully".to_string(),
+            replaced,
+            message: message.to_string(),
         })
     }
 }
 
 /// Request DTO for adding a contact
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct AddContactRequest {
     pub first_name: String,
     pub last_name: String,
     pub phone_numbers: Vec<PhoneNumber>,
     pub emails: Vec<Email>,
+    pub notes: Option<String>,
+    pub birthday: Option<Date>,
+    pub tags: Vec<String>,
+    pub photo: Option<PathBuf>,
+    /// Extra `key=value` fields
+    pub metadata: Vec<(String, String)>,
+    /// Use this ID instead of generating one, e.g. when importing
+    pub id: Option<ContactId>,
+    /// Replace a stored contact with the same ID instead of failing
+    pub upsert: bool,
+    /// Which repeated emails are dropped
+    pub email_policy: EmailDedupPolicy,
 }
 
 /// Response DTO for adding a contact
 #[derive(Debug)]
 pub struct AddContactResponse {
-    pub contact_id: crate::domain::value_objects::ContactId,
+    pub contact_id: ContactId,
+    /// Whether an existing contact was replaced (only with `upsert`)
+    pub replaced: bool,
     pub message: String,
 }
@@ -0,0 +1,57 @@
+use crate::domain::entities::Group;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::{ContactRepositorySync, GroupRepositorySync, RepositoryError};
+use crate::domain::value_objects::ContactId;
+use std::sync::Arc;
+
+/// Use case for adding a contact to a group
+/// Follows Single Responsibility Principle - only handles adding group members
+pub struct AddGroupMemberUseCase {
+    groups: Arc<dyn GroupRepositorySync>,
+    contacts: Arc<dyn ContactRepositorySync>,
+}
+
+impl AddGroupMemberUseCase {
+    pub fn new(groups: Arc<dyn GroupRepositorySync>, contacts: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { groups, contacts }
+    }
+
+    /// Execute the add group member use case
+    pub fn execute(&self, request: AddGroupMemberRequest) -> Result<AddGroupMemberResponse, ApplicationError> {
+        let mut group = self.groups
+            .find_by_name(&request.group)?
+            .ok_or_else(|| RepositoryError::GroupNotFound(request.group.clone()))?;
+
+        if !self.contacts.exists(&request.contact_id)? {
+            return Err(RepositoryError::ContactNotFound(request.contact_id.to_string()).into());
+        }
+
+        let added = group.add_member(request.contact_id);
+        if added {
+            self.groups.update(group.clone())?;
+        }
+
+        Ok(AddGroupMemberResponse {
+            group,
+            message: if added {
+                "Contact added to group".to_string()
+            } else {
+                "Contact is already in the group".to_string()
+            },
+        })
+    }
+}
+
+/// Request DTO for adding a contact to a group
+#[derive(Debug)]
+pub struct AddGroupMemberRequest {
+    pub group: String,
+    pub contact_id: ContactId,
+}
+
+/// Response DTO for adding a contact to a group
+#[derive(Debug)]
+pub struct AddGroupMemberResponse {
+    pub group: Group,
+    pub message: String,
+}
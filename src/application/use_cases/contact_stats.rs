@@ -0,0 +1,175 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::ContactRepositorySync;
+use crate::domain::value_objects::Date;
+use chrono::Weekday;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Use case for summarising the phonebook
+/// Follows Single Responsibility Principle - only handles computing statistics
+pub struct StatsUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl StatsUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the stats use case
+    pub fn execute(&self, _request: StatsRequest) -> Result<StatsResponse, ApplicationError> {
+        let contacts = self.repository.find_all()?;
+        let deleted = self.repository.find_deleted()?;
+
+        let mut stats = Stats {
+            total_contacts: contacts.len(),
+            deleted_contacts: deleted.len(),
+            ..Default::default()
+        };
+
+        for contact in &contacts {
+            stats.phone_numbers += contact.phone_numbers().len();
+            stats.emails += contact.emails().len();
+            if contact.phone_numbers().is_empty() {
+                stats.without_phone += 1;
+            }
+            if contact.emails().is_empty() {
+                stats.without_email += 1;
+            }
+            if contact.birthday().is_some() {
+                stats.with_birthday += 1;
+            }
+            if contact.tags().is_empty() {
+                stats.untagged += 1;
+            }
+            for tag in contact.tags() {
+                *stats.tags.entry(tag.clone()).or_insert(0) += 1;
+            }
+        }
+
+        Ok(StatsResponse { stats })
+    }
+
+    /// Count live contacts by when they were created
+    pub fn over_time(&self, bucket: TimeBucket) -> Result<Vec<(String, usize)>, ApplicationError> {
+        Ok(bucket_by_created(&self.repository.find_all()?, bucket))
+    }
+}
+
+/// Granularity of `bucket_by_created`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeBucket {
+    Day,
+    /// Weeks starting on Monday, labelled by that Monday's date
+    Week,
+    Month,
+}
+
+/// Count contacts per creation day, week or month, oldest bucket first
+/// Only buckets with at least one contact are returned; contacts without
+/// a `created_at` (saved before it was recorded) are left out
+pub fn bucket_by_created(contacts: &[Contact], bucket: TimeBucket) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+
+    for created_at in contacts.iter().filter_map(|contact| contact.created_at()) {
+        let date = created_at.date();
+        let label = match bucket {
+            TimeBucket::Day => date.to_string(),
+            TimeBucket::Week => Date::from(date.naive().week(Weekday::Mon).first_day()).to_string(),
+            TimeBucket::Month => format!("{:04}-{:02}", date.year(), date.month()),
+        };
+        *counts.entry(label).or_insert(0) += 1;
+    }
+
+    // ISO labels sort chronologically
+    counts.into_iter().collect()
+}
+
+/// Summary of the phonebook; counts cover live contacts unless noted
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Stats {
+    pub total_contacts: usize,
+    /// Contacts in the trash
+    pub deleted_contacts: usize,
+    pub phone_numbers: usize,
+    pub emails: usize,
+    pub without_phone: usize,
+    pub without_email: usize,
+    pub with_birthday: usize,
+    pub untagged: usize,
+    /// Number of contacts carrying each tag
+    pub tags: BTreeMap<String, usize>,
+}
+
+/// Request DTO for the stats
+#[derive(Debug, Default)]
+pub struct StatsRequest;
+
+/// Response DTO for the stats
+#[derive(Debug)]
+pub struct StatsResponse {
+    pub stats: Stats,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{PhoneNumber, Timestamp};
+
+    fn created(at: Option<&str>) -> Contact {
+        let contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let mut value = serde_json::to_value(contact).unwrap();
+        value["created_at"] = serde_json::to_value(at.map(|at| Timestamp::parse(at).unwrap())).unwrap();
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn seeded() -> Vec<Contact> {
+        [
+            Some("2024-01-08T09:00:00Z"),
+            Some("2023-12-31T23:59:59Z"),
+            Some("2024-01-01T00:00:00Z"),
+            Some("2024-01-01T23:00:00Z"),
+            Some("2024-01-03T12:00:00Z"),
+            Some("2024-02-29T12:00:00Z"),
+            None,
+        ]
+        .into_iter()
+        .map(created)
+        .collect()
+    }
+
+    fn buckets(pairs: &[(&str, usize)]) -> Vec<(String, usize)> {
+        pairs.iter().map(|(label, count)| (label.to_string(), *count)).collect()
+    }
+
+    #[test]
+    fn contacts_are_counted_per_day_week_and_month() {
+        let contacts = seeded();
+
+        assert_eq!(
+            bucket_by_created(&contacts, TimeBucket::Day),
+            buckets(&[("2023-12-31", 1), ("2024-01-01", 2), ("2024-01-03", 1), ("2024-01-08", 1), ("2024-02-29", 1)])
+        );
+        assert_eq!(
+            bucket_by_created(&contacts, TimeBucket::Week),
+            buckets(&[("2023-12-25", 1), ("2024-01-01", 3), ("2024-01-08", 1), ("2024-02-26", 1)])
+        );
+        assert_eq!(
+            bucket_by_created(&contacts, TimeBucket::Month),
+            buckets(&[("2023-12", 1), ("2024-01", 4), ("2024-02", 1)])
+        );
+    }
+
+    #[test]
+    fn no_dated_contacts_means_no_buckets() {
+        assert!(bucket_by_created(&[], TimeBucket::Month).is_empty());
+        assert!(bucket_by_created(&[created(None)], TimeBucket::Day).is_empty());
+    }
+}
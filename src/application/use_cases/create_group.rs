@@ -0,0 +1,45 @@
+use crate::domain::entities::Group;
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::GroupRepositorySync;
+use std::sync::Arc;
+
+/// Use case for creating a contact group
+/// Follows Single Responsibility Principle - only handles group creation
+pub struct CreateGroupUseCase {
+    groups: Arc<dyn GroupRepositorySync>,
+}
+
+impl CreateGroupUseCase {
+    pub fn new(groups: Arc<dyn GroupRepositorySync>) -> Self {
+        Self { groups }
+    }
+
+    /// Execute the create group use case
+    pub fn execute(&self, request: CreateGroupRequest) -> Result<CreateGroupResponse, ApplicationError> {
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(DomainError::Validation("Group name cannot be empty".to_string()).into());
+        }
+
+        let group = Group::new(name.to_string());
+        self.groups.save(group.clone())?;
+
+        Ok(CreateGroupResponse {
+            group,
+            message: "Group created successfully".to_string(),
+        })
+    }
+}
+
+/// Request DTO for creating a group
+#[derive(Debug)]
+pub struct CreateGroupRequest {
+    pub name: String,
+}
+
+/// Response DTO for creating a group
+#[derive(Debug)]
+pub struct CreateGroupResponse {
+    pub group: Group,
+    pub message: String,
+}
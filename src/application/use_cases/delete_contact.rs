@@ -1,5 +1,7 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::ApplicationError;
 use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
-use crate::domain::value_objects::ContactId;
+use crate::domain::value_objects::{ContactId, Timestamp};
 use std::sync::Arc;
 
 /// Use case for deleting a contact
@@ -14,31 +16,104 @@ impl DeleteContactUseCase {
     }
 
     /// Execute the delete contact use case
-    pub fn execute(&self, request: DeleteContactRequest) -> Result<DeleteContactResponse, RepositoryError> {
-        // Check if contact exists
-        if !self.repository.exists(&request.contact_id)? {
-            return Err(RepositoryError::ContactNotFound(request.contact_id.to_string()));
+    pub fn execute(&self, request: DeleteContactRequest) -> Result<DeleteContactResponse, ApplicationError> {
+        // Check if contact exists; trashed contacts can only be purged
+        let mut contact = self.repository
+            .find_by_id(&request.contact_id)?
+            .filter(|contact| request.purge || !contact.is_deleted())
+            .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+
+        if request.dry_run {
+            return Ok(DeleteContactResponse {
+                contact_id: request.contact_id,
+                contact,
+                message: "Dry run: contact would be deleted".to_string(),
+            });
+        }
+
+        if request.purge {
+            self.repository.delete(&request.contact_id)?;
+
+            return Ok(DeleteContactResponse {
+                contact_id: request.contact_id,
+                contact,
+                message: "Contact permanently deleted".to_string(),
+            });
         }
 
-        // Delete the contact
-        self.repository.delete(&request.contact_id)?;
+        // Move the contact to the trash; marked on the copy the transaction loaded, so
+        // changes another writer saved in the meantime are kept
+        let now = Timestamp::now();
+        self.repository.transaction(&mut |contacts| {
+            let stored = contacts
+                .get_mut(&request.contact_id)
+                .filter(|contact| !contact.is_deleted())
+                .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+            stored.mark_deleted(now);
+            contact = stored.clone();
+            Ok(())
+        })?;
 
         Ok(DeleteContactResponse {
             contact_id: request.contact_id,
-            message: "Contact deleted successfully".to_string(),
+            contact,
+            message: "Contact moved to trash".to_string(),
         })
     }
 }
 
 /// Request DTO for deleting a contact
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DeleteContactRequest {
     pub contact_id: ContactId,
+    /// Report what would be deleted without deleting it
+    pub dry_run: bool,
+    /// Remove the contact permanently instead of moving it to the trash
+    pub purge: bool,
 }
 
 /// Response DTO for deleting a contact
 #[derive(Debug)]
 pub struct DeleteContactResponse {
     pub contact_id: ContactId,
+    pub contact: Contact,
     pub message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    #[test]
+    fn dry_run_reports_the_contact_and_deletes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+        let contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let id = contact.id().clone();
+        repository.save(contact.clone()).unwrap();
+        let use_case = DeleteContactUseCase::new(repository.clone());
+
+        for purge in [false, true] {
+            let response = use_case
+                .execute(DeleteContactRequest {
+                    contact_id: id.clone(),
+                    dry_run: true,
+                    purge,
+                })
+                .unwrap();
+            assert_eq!(response.contact, contact);
+        }
+
+        assert_eq!(repository.find_by_id(&id).unwrap(), Some(contact));
+        assert!(repository.find_deleted().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
+use crate::domain::value_objects::{ContactId, Timestamp};
+use std::sync::Arc;
+
+/// Use case for deleting several contacts at once
+/// Follows Single Responsibility Principle - only handles bulk deletion
+pub struct DeleteManyContactsUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl DeleteManyContactsUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the bulk delete use case
+    /// Individual failures are collected rather than aborting the whole batch
+    pub fn execute(&self, request: DeleteManyContactsRequest) -> Result<DeleteManyContactsResponse, ApplicationError> {
+        if request.ids.is_empty() && request.tag.is_none() {
+            return Err(DomainError::Validation(
+                "Specify at least one contact ID or a tag".to_string(),
+            )
+            .into());
+        }
+
+        let mut targets: Vec<ContactId> = Vec::new();
+        let mut failed = Vec::new();
+
+        for id_str in &request.ids {
+            match ContactId::parse(id_str) {
+                Ok(id) => {
+                    let active = self
+                        .repository
+                        .find_by_id(&id)?
+                        .is_some_and(|contact| !contact.is_deleted());
+                    if !active {
+                        failed.push((id_str.clone(), "Contact not found".to_string()));
+                    } else if !targets.contains(&id) {
+                        targets.push(id);
+                    }
+                }
+                Err(e) => failed.push((id_str.clone(), e.to_string())),
+            }
+        }
+
+        if let Some(tag) = &request.tag {
+            for contact in self.repository.find_all()? {
+                if contact.tags().iter().any(|t| t == tag) && !targets.contains(contact.id()) {
+                    targets.push(contact.id().clone());
+                }
+            }
+        }
+
+        if request.dry_run {
+            return Ok(DeleteManyContactsResponse {
+                deleted: targets,
+                failed,
+            });
+        }
+
+        // Move every contact to the trash in one save
+        let now = Timestamp::now();
+        let unresolved = failed;
+        let mut deleted = Vec::new();
+        let mut failed = Vec::new();
+        self.repository.transaction(&mut |contacts| {
+            deleted.clear();
+            failed = unresolved.clone();
+            for id in &targets {
+                match contacts.get_mut(id) {
+                    Some(contact) => {
+                        contact.mark_deleted(now);
+                        deleted.push(id.clone());
+                    }
+                    None => failed.push((id.to_string(), RepositoryError::ContactNotFound(id.to_string()).to_string())),
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(DeleteManyContactsResponse { deleted, failed })
+    }
+}
+
+/// Request DTO for deleting several contacts
+#[derive(Debug, Default)]
+pub struct DeleteManyContactsRequest {
+    pub ids: Vec<String>,
+    /// Delete every contact carrying this tag
+    pub tag: Option<String>,
+    /// Resolve the contacts that would be deleted without deleting them
+    pub dry_run: bool,
+}
+
+/// Response DTO for deleting several contacts
+#[derive(Debug)]
+pub struct DeleteManyContactsResponse {
+    /// Contacts that were deleted (or would be, on a dry run)
+    pub deleted: Vec<ContactId>,
+    /// Inputs that could not be deleted, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Contact;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    #[test]
+    fn deleting_by_tag_only_trashes_tagged_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(path.clone()));
+        let mut tagged = Vec::new();
+        for i in 0..4 {
+            let mut contact = Contact::new(
+                format!("Person{}", i),
+                "Tester".to_string(),
+                vec![PhoneNumber::new(format!("555000000{}", i)).unwrap()],
+                vec![],
+            );
+            if i % 2 == 0 {
+                contact.add_tag("old".to_string());
+                tagged.push(contact.id().clone());
+            }
+            repository.save(contact).unwrap();
+        }
+        let use_case = DeleteManyContactsUseCase::new(repository.clone());
+        let request = |dry_run| DeleteManyContactsRequest {
+            tag: Some("old".to_string()),
+            dry_run,
+            ..Default::default()
+        };
+
+        let sorted = |mut ids: Vec<ContactId>| {
+            ids.sort_by_key(|id| id.to_string());
+            ids
+        };
+        tagged = sorted(tagged);
+
+        let preview = use_case.execute(request(true)).unwrap();
+        assert_eq!(sorted(preview.deleted), tagged);
+        assert_eq!(repository.count().unwrap(), 4);
+
+        let response = use_case.execute(request(false)).unwrap();
+        assert_eq!(sorted(response.deleted), tagged);
+        assert!(response.failed.is_empty());
+
+        let reloaded = FileContactRepository::new(path);
+        assert_eq!(reloaded.count().unwrap(), 2);
+        let trashed = reloaded.find_deleted().unwrap().iter().map(|c| c.id().clone()).collect();
+        assert_eq!(sorted(trashed), tagged);
+    }
+}
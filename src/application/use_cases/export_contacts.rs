@@ -0,0 +1,188 @@
+use crate::application::query::FilterExpr;
+use crate::domain::entities::Contact;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
+use crate::domain::value_objects::Timestamp;
+use std::sync::Arc;
+
+/// Use case for collecting contacts to export
+/// Follows Single Responsibility Principle - only handles export selection
+pub struct ExportContactsUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl ExportContactsUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the export contacts use case
+    /// The response holds a copy of every selected contact; use `execute_streaming`
+    /// to write a large export without one
+    pub fn execute(&self, request: ExportContactsRequest) -> Result<ExportContactsResponse, ApplicationError> {
+        let mut contacts = Vec::new();
+        let summary = self.execute_streaming(&request, &mut |selected| {
+            contacts = selected.iter().map(|contact| (*contact).clone()).collect();
+            Ok(())
+        })?;
+
+        Ok(ExportContactsResponse {
+            contacts,
+            total_count: summary.total_count,
+        })
+    }
+
+    /// Pass the selected contacts, in export order, to `write` straight from the
+    /// repository's storage. The repository stays locked while `write` runs.
+    pub fn execute_streaming(
+        &self,
+        request: &ExportContactsRequest,
+        write: &mut dyn FnMut(&[&Contact]) -> Result<(), RepositoryError>,
+    ) -> Result<ExportContactsSummary, ApplicationError> {
+        let mut summary = ExportContactsSummary::default();
+        self.repository.scan(&mut |all| {
+            let mut total_count = 0;
+            let mut contacts: Vec<&Contact> = all
+                .inspect(|_| total_count += 1)
+                .filter(|contact| request.tag.as_ref().is_none_or(|tag| contact.tags().contains(tag)))
+                .filter(|contact| request.filter.as_ref().is_none_or(|filter| filter.matches(contact)))
+                .filter(|contact| {
+                    request
+                        .since
+                        .is_none_or(|since| contact.updated_at().is_some_and(|updated| *updated >= since))
+                })
+                .collect();
+
+            // Export in a stable order so repeated exports diff cleanly
+            contacts.sort_by(|a, b| {
+                a.last_name()
+                    .cmp(b.last_name())
+                    .then_with(|| a.first_name().cmp(b.first_name()))
+                    .then_with(|| a.id().as_uuid().cmp(b.id().as_uuid()))
+            });
+
+            write(&contacts)?;
+            summary = ExportContactsSummary {
+                exported: contacts.len(),
+                total_count,
+            };
+            Ok(())
+        })?;
+
+        Ok(summary)
+    }
+}
+
+/// Request DTO for exporting contacts
+#[derive(Debug, Default)]
+pub struct ExportContactsRequest {
+    /// Only export contacts with this tag
+    pub tag: Option<String>,
+    /// Only export contacts matching this expression
+    pub filter: Option<FilterExpr>,
+    /// Only export contacts changed at or after this time, for incremental syncs.
+    /// Contacts stored before timestamps were tracked are left out.
+    pub since: Option<Timestamp>,
+}
+
+/// Response DTO for exporting contacts
+#[derive(Debug)]
+pub struct ExportContactsResponse {
+    /// Contacts selected for export
+    pub contacts: Vec<Contact>,
+    /// Number of live contacts before the tag, filter and since bound were applied
+    pub total_count: usize,
+}
+
+/// Outcome of a streamed export
+#[derive(Debug, Default)]
+pub struct ExportContactsSummary {
+    /// Number of contacts written
+    pub exported: usize,
+    /// Number of live contacts before the tag, filter and since bound were applied
+    pub total_count: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    #[test]
+    fn streamed_export_matches_the_copied_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+        for (i, last_name) in ["Clark", "Adams", "Brown"].iter().enumerate() {
+            let mut contact = Contact::new(
+                "Ann".to_string(),
+                last_name.to_string(),
+                vec![PhoneNumber::new(format!("555000000{}", i)).unwrap()],
+                vec![],
+            );
+            if *last_name != "Brown" {
+                contact.add_tag("work".to_string());
+            }
+            repository.save(contact).unwrap();
+        }
+        let use_case = ExportContactsUseCase::new(repository);
+        let request = || ExportContactsRequest {
+            tag: Some("work".to_string()),
+            ..Default::default()
+        };
+
+        let mut streamed = Vec::new();
+        let summary = use_case
+            .execute_streaming(&request(), &mut |contacts| {
+                streamed = contacts.iter().map(|contact| contact.last_name().to_string()).collect();
+                Ok(())
+            })
+            .unwrap();
+        let copied = use_case.execute(request()).unwrap();
+
+        assert_eq!(streamed, ["Adams", "Clark"]);
+        assert_eq!(summary.exported, 2);
+        assert_eq!(summary.total_count, 3);
+        let copied: Vec<&str> = copied.contacts.iter().map(|contact| contact.last_name()).collect();
+        assert_eq!(copied, streamed);
+    }
+
+    #[test]
+    fn since_keeps_only_contacts_updated_at_or_after_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+        let updated_at = [
+            ("Adams", Some("2024-01-01T00:00:00Z")),
+            ("Brown", Some("2024-06-01T09:30:00Z")),
+            ("Clark", Some("2024-07-01T00:00:00Z")),
+            ("Davis", None),
+        ];
+        for (i, (last_name, updated)) in updated_at.iter().enumerate() {
+            let contact = Contact::new(
+                "Ann".to_string(),
+                last_name.to_string(),
+                vec![PhoneNumber::new(format!("555000000{}", i)).unwrap()],
+                vec![],
+            );
+            // Stored contacts only carry past update times when loaded from a file
+            let mut value = serde_json::to_value(contact).unwrap();
+            value["updated_at"] = serde_json::json!(updated);
+            repository.save(serde_json::from_value(value).unwrap()).unwrap();
+        }
+
+        let response = ExportContactsUseCase::new(repository)
+            .execute(ExportContactsRequest {
+                since: Some(Timestamp::parse("2024-06-01T09:30:00Z").unwrap()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let exported: Vec<&str> = response.contacts.iter().map(|contact| contact.last_name()).collect();
+        assert_eq!(exported, ["Brown", "Clark"], "the bound is inclusive and untimed contacts are left out");
+        assert_eq!(response.total_count, 4);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::domain::entities::Contact;
+use crate::domain::errors::ApplicationError;
 use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
-use crate::domain::value_objects::ContactId;
+use crate::application::use_cases::list_contacts::{sort_contacts, SortBy};
+use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
 use std::sync::Arc;
 
 /// Use case for finding a contact by ID
@@ -15,8 +17,12 @@ impl FindContactUseCase {
     }
 
     /// Execute the find contact use case
-    pub fn execute(&self, request: FindContactRequest) -> Result<FindContactResponse, RepositoryError> {
-        let contact = self.repository.find_by_id(&request.contact_id)?;
+    /// Contacts in the trash are not found; `list --trash` shows them
+    pub fn execute(&self, request: FindContactRequest) -> Result<FindContactResponse, ApplicationError> {
+        let contact = self
+            .repository
+            .find_by_id(&request.contact_id)?
+            .filter(|contact| !contact.is_deleted());
 
         match contact {
             Some(contact) => Ok(FindContactResponse {
@@ -31,6 +37,115 @@ impl FindContactUseCase {
     }
 }
 
+impl FindContactUseCase {
+    /// Resolve a full contact ID or a unique prefix of one (e.g. the first 8 characters)
+    /// Prefixes only match contacts outside the trash
+    pub fn resolve_id(&self, input: &str) -> Result<ContactId, ApplicationError> {
+        self.resolve_among(input, || self.repository.find_all())
+    }
+
+    /// Resolve a full contact ID or a unique prefix of a contact in the trash
+    pub fn resolve_deleted_id(&self, input: &str) -> Result<ContactId, ApplicationError> {
+        self.resolve_among(input, || self.repository.find_deleted())
+    }
+
+    /// Resolve a full contact ID or a unique prefix of any stored contact, in the trash or not
+    pub fn resolve_any_id(&self, input: &str) -> Result<ContactId, ApplicationError> {
+        self.resolve_among(input, || {
+            let mut contacts = self.repository.find_all()?;
+            contacts.extend(self.repository.find_deleted()?);
+            Ok(contacts)
+        })
+    }
+
+    /// Full IDs are returned as given; prefixes must match exactly one of `candidates`
+    fn resolve_among<F>(&self, input: &str, candidates: F) -> Result<ContactId, ApplicationError>
+    where
+        F: FnOnce() -> Result<Vec<Contact>, RepositoryError>,
+    {
+        let input = input.trim();
+
+        match ContactId::parse(input) {
+            Ok(id) => return Ok(id),
+            // Not a plausible prefix either, so report why it isn't a valid ID
+            Err(e) if input.is_empty() || !input.chars().all(|c| c.is_ascii_hexdigit() || c == '-') => {
+                return Err(e.into());
+            }
+            Err(_) => {}
+        }
+
+        let prefix = input.to_lowercase();
+        let mut matches = candidates()?
+            .into_iter()
+            .filter(|contact| contact.id().to_string().starts_with(&prefix));
+
+        match (matches.next(), matches.next()) {
+            (Some(contact), None) => Ok(contact.id().clone()),
+            (Some(_), Some(_)) => Err(RepositoryError::AmbiguousId(input.to_string()).into()),
+            (None, _) => Err(RepositoryError::ContactNotFound(input.to_string()).into()),
+        }
+    }
+}
+
+impl FindContactUseCase {
+    /// Find every contact with the given phone number, whatever format it was stored in
+    pub fn find_by_phone(&self, phone: &PhoneNumber) -> Result<Vec<Contact>, ApplicationError> {
+        let mut contacts = self.repository.find_by_phone(phone)?;
+        sort_contacts(&mut contacts, &[SortBy::LastName, SortBy::FirstName], false);
+        Ok(contacts)
+    }
+
+    /// Find every contact with the given email address
+    pub fn find_by_email(&self, email: &Email) -> Result<Vec<Contact>, ApplicationError> {
+        let mut contacts = self.repository.find_by_email(email)?;
+        sort_contacts(&mut contacts, &[SortBy::LastName, SortBy::FirstName], false);
+        Ok(contacts)
+    }
+}
+
+/// Most related contacts `find_related` returns
+pub const MAX_RELATED_CONTACTS: usize = 10;
+
+impl FindContactUseCase {
+    /// Other contacts sharing at least one tag with the given contact, most shared tags first
+    pub fn find_related(&self, id: &ContactId) -> Result<Vec<RelatedContact>, ApplicationError> {
+        let contact = self
+            .repository
+            .find_by_id(id)?
+            .filter(|contact| !contact.is_deleted())
+            .ok_or_else(|| RepositoryError::ContactNotFound(id.to_string()))?;
+
+        let mut candidates = self.repository.find_all()?;
+        sort_contacts(&mut candidates, &[SortBy::LastName, SortBy::FirstName], false);
+
+        let mut related: Vec<RelatedContact> = candidates
+            .into_iter()
+            .filter(|other| other.id() != contact.id())
+            .filter_map(|other| {
+                let shared_tags: Vec<String> = contact
+                    .tags()
+                    .iter()
+                    .filter(|tag| other.tags().iter().any(|t| t.eq_ignore_ascii_case(tag)))
+                    .cloned()
+                    .collect();
+                (!shared_tags.is_empty()).then_some(RelatedContact { contact: other, shared_tags })
+            })
+            .collect();
+
+        // Stable sort keeps name order among contacts with the same overlap
+        related.sort_by_key(|entry| std::cmp::Reverse(entry.shared_tags.len()));
+        related.truncate(MAX_RELATED_CONTACTS);
+        Ok(related)
+    }
+}
+
+/// A contact sharing tags with another, with the tags they have in common
+#[derive(Debug)]
+pub struct RelatedContact {
+    pub contact: Contact,
+    pub shared_tags: Vec<String>,
+}
+
 /// Request DTO for finding a contact
 #[derive(Debug)]
 pub struct FindContactRequest {
@@ -43,3 +158,166 @@ pub struct FindContactResponse {
     pub contact: Option<Contact>,
     pub found: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::{
+        DeleteContactRequest, DeleteContactUseCase, UpdateContactRequest, UpdateContactUseCase,
+    };
+    use crate::infrastructure::FileContactRepository;
+
+    fn repository_in(dir: &tempfile::TempDir) -> Arc<dyn ContactRepositorySync> {
+        Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ))
+    }
+
+    fn add(repository: &Arc<dyn ContactRepositorySync>, first_name: &str) -> ContactId {
+        let contact = Contact::new(
+            first_name.to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let id = contact.id().clone();
+        repository.save(contact).unwrap();
+        id
+    }
+
+    fn trash(repository: &Arc<dyn ContactRepositorySync>, id: &ContactId) {
+        DeleteContactUseCase::new(repository.clone())
+            .execute(DeleteContactRequest {
+                contact_id: id.clone(),
+                ..Default::default()
+            })
+            .unwrap();
+    }
+
+    fn prefix(id: &ContactId) -> String {
+        id.to_string()[..8].to_string()
+    }
+
+    #[test]
+    fn unique_prefixes_resolve_and_others_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        for id in ["abcd1234-0000-4000-8000-000000000001", "abcd5678-0000-4000-8000-000000000002"] {
+            let contact = Contact::with_id(
+                ContactId::parse(id).unwrap(),
+                "Ann".to_string(),
+                "Tester".to_string(),
+                vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+                vec![],
+            );
+            repository.save(contact).unwrap();
+        }
+        let use_case = FindContactUseCase::new(repository);
+
+        let resolved = use_case.resolve_id("abcd1").unwrap();
+        assert_eq!(resolved.to_string(), "abcd1234-0000-4000-8000-000000000001");
+        assert_eq!(use_case.resolve_id(" ABCD5678 ").unwrap().to_string(), "abcd5678-0000-4000-8000-000000000002");
+
+        let ambiguous = use_case.resolve_id("abcd").unwrap_err();
+        assert!(matches!(ambiguous, ApplicationError::Repository(RepositoryError::AmbiguousId(_))));
+        assert!(use_case.resolve_id("ffff").unwrap_err().is_not_found());
+        assert!(use_case.resolve_id("not-an-id").unwrap_err().is_validation());
+    }
+
+    #[test]
+    fn prefixes_resolve_live_or_trashed_contacts_by_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let live = add(&repository, "Ann");
+        let trashed = add(&repository, "Bob");
+        trash(&repository, &trashed);
+        let use_case = FindContactUseCase::new(repository);
+
+        assert_eq!(use_case.resolve_id(&prefix(&live)).unwrap(), live);
+        assert!(use_case.resolve_id(&prefix(&trashed)).unwrap_err().is_not_found());
+
+        assert_eq!(use_case.resolve_deleted_id(&prefix(&trashed)).unwrap(), trashed);
+        assert!(use_case.resolve_deleted_id(&prefix(&live)).unwrap_err().is_not_found());
+
+        assert_eq!(use_case.resolve_any_id(&prefix(&live)).unwrap(), live);
+        assert_eq!(use_case.resolve_any_id(&prefix(&trashed)).unwrap(), trashed);
+    }
+
+    #[test]
+    fn trashed_contacts_are_not_found_or_updated() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let id = add(&repository, "Ann");
+        trash(&repository, &id);
+
+        let found = FindContactUseCase::new(repository.clone())
+            .execute(FindContactRequest { contact_id: id.clone() })
+            .unwrap();
+        assert!(!found.found);
+
+        let updated = UpdateContactUseCase::new(repository.clone()).execute(UpdateContactRequest {
+            contact_id: id.clone(),
+            first_name: Some("Changed".to_string()),
+            ..Default::default()
+        });
+        assert!(updated.unwrap_err().is_not_found());
+        assert_eq!(repository.find_by_id(&id).unwrap().unwrap().first_name(), "Ann");
+    }
+
+    fn add_tagged(repository: &Arc<dyn ContactRepositorySync>, first_name: &str, tags: &[&str]) -> ContactId {
+        let id = add(repository, first_name);
+        UpdateContactUseCase::new(repository.clone())
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                add_tags: tags.iter().map(|tag| tag.to_string()).collect(),
+                ..Default::default()
+            })
+            .unwrap();
+        id
+    }
+
+    #[test]
+    fn related_contacts_are_ordered_by_shared_tags_then_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let ann = add_tagged(&repository, "Ann", &["a", "b", "c"]);
+        add_tagged(&repository, "Fay", &["A"]);
+        add_tagged(&repository, "Dee", &["b", "c"]);
+        add_tagged(&repository, "Cal", &["a"]);
+        add_tagged(&repository, "Bob", &["a", "b", "x"]);
+        add_tagged(&repository, "Eve", &["x"]);
+        let gus = add_tagged(&repository, "Gus", &["a", "b", "c"]);
+        trash(&repository, &gus);
+
+        let related = FindContactUseCase::new(repository).find_related(&ann).unwrap();
+
+        let summary: Vec<(&str, Vec<&str>)> = related
+            .iter()
+            .map(|entry| (entry.contact.first_name(), entry.shared_tags.iter().map(String::as_str).collect()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Bob", vec!["a", "b"]),
+                ("Dee", vec!["b", "c"]),
+                ("Cal", vec!["a"]),
+                ("Fay", vec!["a"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn related_contacts_are_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let ann = add_tagged(&repository, "Ann", &["club"]);
+        for i in 0..MAX_RELATED_CONTACTS + 2 {
+            add_tagged(&repository, &format!("Member{:02}", i), &["club"]);
+        }
+
+        let related = FindContactUseCase::new(repository).find_related(&ann).unwrap();
+
+        assert_eq!(related.len(), MAX_RELATED_CONTACTS);
+        assert_eq!(related[0].contact.first_name(), "Member00");
+    }
+}
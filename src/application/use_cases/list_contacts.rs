@@ -1,5 +1,10 @@
+use crate::application::query::FilterExpr;
+use crate::application::validation::{Validator, RECOMMENDED_MAX_PAGE_SIZE};
 use crate::domain::entities::Contact;
-use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::ContactRepositorySync;
+use crate::domain::value_objects::Timestamp;
+use std::cmp::Ordering;
 use std::sync::Arc;
 
 /// Use case for listing all contacts
@@ -14,24 +19,49 @@ impl ListContactsUseCase {
     }
 
     /// Execute the list contacts use case
-    pub fn execute(&self, request: ListContactsRequest) -> Result<ListContactsResponse, RepositoryError> {
-        let mut contacts = self.repository.find_all()?;
-
-        // Apply sorting
-        match request.sort_by {
-            SortBy::FirstName => contacts.sort_by(|a, b| a.first_name().cmp(b.first_name())),
-            SortBy::LastName => contacts.sort_by(|a, b| a.last_name().cmp(b.last_name())),
-            SortBy::FullName => contacts.sort_by(|a, b| a.full_name().cmp(&b.full_name())),
+    pub fn execute(&self, request: ListContactsRequest) -> Result<ListContactsResponse, ApplicationError> {
+        // `all` ignores the page size, so the cap doesn't apply to it
+        if !request.all {
+            Validator::validate_pagination(request.page, request.page_size, request.max_page_size)?;
         }
 
-        if request.reverse {
-            contacts.reverse();
+        let mut contacts = match request.deleted {
+            DeletedFilter::Exclude => self.repository.find_all()?,
+            DeletedFilter::Include => {
+                let mut contacts = self.repository.find_all()?;
+                contacts.extend(self.repository.find_deleted()?);
+                contacts
+            }
+            DeletedFilter::Only => self.repository.find_deleted()?,
+        };
+
+        if let Some(filter) = &request.filter {
+            contacts.retain(|contact| filter.matches(contact));
         }
 
-        // Apply pagination
+        contacts.retain(|contact| request.dates.matches(contact));
+
+        // Apply sorting; the ID tiebreak keeps pages consistent across runs,
+        // since `find_all` returns contacts in hash map order
+        sort_contacts(&mut contacts, &request.sort_by, request.reverse);
+
+        // Apply pagination; `all` makes the whole list a single page
         let total_count = contacts.len();
-        let start_index = request.page * request.page_size;
-        let end_index = std::cmp::min(start_index + request.page_size, contacts.len());
+        if request.all {
+            return Ok(ListContactsResponse {
+                contacts,
+                total_count,
+                page: 0,
+                page_size: total_count,
+                has_more: false,
+                total_pages: usize::from(total_count > 0),
+                current_page: 1,
+            });
+        }
+
+        let total_pages = total_count.div_ceil(request.page_size);
+        let start_index = request.page.saturating_mul(request.page_size);
+        let end_index = std::cmp::min(start_index.saturating_add(request.page_size), contacts.len());
 
         let paginated_contacts = if start_index < contacts.len() {
             contacts[start_index..end_index].to_vec()
@@ -45,17 +75,44 @@ impl ListContactsUseCase {
             page: request.page,
             page_size: request.page_size,
             has_more: end_index < total_count,
+            total_pages,
+            current_page: request.page + 1,
         })
     }
 }
 
+/// Sort contacts by the given keys in priority order
+/// Later keys break ties left by earlier ones, and the contact ID breaks any
+/// remaining tie so the order never depends on storage iteration order
+pub fn sort_contacts(contacts: &mut [Contact], keys: &[SortBy], reverse: bool) {
+    contacts.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| key.compare(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.id().cmp(b.id()))
+    });
+
+    if reverse {
+        contacts.reverse();
+    }
+}
+
 /// Request DTO for listing contacts
 #[derive(Debug)]
 pub struct ListContactsRequest {
     pub page: usize,
     pub page_size: usize,
-    pub sort_by: SortBy,
+    /// Largest `page_size` accepted
+    pub max_page_size: usize,
+    /// Return every matching contact, ignoring `page` and `page_size`
+    pub all: bool,
+    /// Sort keys in priority order
+    pub sort_by: Vec<SortBy>,
     pub reverse: bool,
+    pub deleted: DeletedFilter,
+    /// Only list contacts matching this expression
+    pub filter: Option<FilterExpr>,
+    pub dates: DateFilter,
 }
 
 impl Default for ListContactsRequest {
@@ -63,20 +120,80 @@ impl Default for ListContactsRequest {
         Self {
             page: 0,
             page_size: 10,
-            sort_by: SortBy::LastName,
+            max_page_size: RECOMMENDED_MAX_PAGE_SIZE,
+            all: false,
+            sort_by: vec![SortBy::LastName],
             reverse: false,
+            deleted: DeletedFilter::Exclude,
+            filter: None,
+            dates: DateFilter::default(),
         }
     }
 }
 
+/// Bounds on when contacts were created or last updated
+/// `created_after` and `updated_since` are inclusive, `created_before` is exclusive,
+/// so consecutive ranges don't overlap. Contacts stored before timestamps were
+/// tracked have none, and are left out whenever a bound is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateFilter {
+    pub created_after: Option<Timestamp>,
+    pub created_before: Option<Timestamp>,
+    pub updated_since: Option<Timestamp>,
+}
+
+impl DateFilter {
+    /// Whether the contact falls within every bound that is set
+    pub fn matches(&self, contact: &Contact) -> bool {
+        let created = contact.created_at().copied();
+        let updated = contact.updated_at().copied();
+
+        self.created_after.is_none_or(|after| created.is_some_and(|c| c >= after))
+            && self.created_before.is_none_or(|before| created.is_some_and(|c| c < before))
+            && self.updated_since.is_none_or(|since| updated.is_some_and(|u| u >= since))
+    }
+}
+
+/// Which contacts to list with respect to the trash
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeletedFilter {
+    /// Only contacts that aren't in the trash
+    #[default]
+    Exclude,
+    /// Both active and trashed contacts
+    Include,
+    /// Only trashed contacts
+    Only,
+}
+
 /// Sorting options for contacts
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub enum SortBy {
     FirstName,
     LastName,
     FullName,
 }
 
+impl SortBy {
+    /// Compare two contacts on this key alone
+    pub fn compare(&self, a: &Contact, b: &Contact) -> Ordering {
+        match self {
+            SortBy::FirstName => a.first_name().cmp(b.first_name()),
+            SortBy::LastName => a.last_name().cmp(b.last_name()),
+            SortBy::FullName => a.full_name().cmp(&b.full_name()),
+        }
+    }
+
+    /// The value this key sorts a contact by
+    pub fn value(&self, contact: &Contact) -> String {
+        match self {
+            SortBy::FirstName => contact.first_name().to_string(),
+            SortBy::LastName => contact.last_name().to_string(),
+            SortBy::FullName => contact.full_name(),
+        }
+    }
+}
+
 /// Response DTO for listing contacts
 #[derive(Debug)]
 pub struct ListContactsResponse {
@@ -85,4 +202,208 @@ pub struct ListContactsResponse {
     pub page: usize,
     pub page_size: usize,
     pub has_more: bool,
+    /// Number of pages needed to show every contact
+    pub total_pages: usize,
+    /// 1-based page number for display
+    pub current_page: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    fn repository_with(dir: &tempfile::TempDir, count: usize) -> Arc<dyn ContactRepositorySync> {
+        let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(path));
+        for i in 0..count {
+            repository
+                .save(Contact::new(
+                    format!("Person{}", i),
+                    "Tester".to_string(),
+                    vec![PhoneNumber::new(format!("55500{:05}", i)).unwrap()],
+                    vec![],
+                ))
+                .unwrap();
+        }
+        repository
+    }
+
+    fn save_named(repository: &Arc<dyn ContactRepositorySync>, first_name: &str, last_name: &str) {
+        repository
+            .save(Contact::new(
+                first_name.to_string(),
+                last_name.to_string(),
+                vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+                vec![],
+            ))
+            .unwrap();
+    }
+
+    fn full_names(response: &ListContactsResponse) -> Vec<String> {
+        response.contacts.iter().map(Contact::full_name).collect()
+    }
+
+    #[test]
+    fn later_sort_keys_break_ties_left_by_earlier_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, 0);
+        for (first_name, last_name) in [("Zoe", "Smith"), ("Amy", "Smith"), ("Bob", "Jones"), ("Mia", "Smith")] {
+            save_named(&repository, first_name, last_name);
+        }
+        let use_case = ListContactsUseCase::new(repository);
+
+        let response = use_case
+            .execute(ListContactsRequest {
+                sort_by: vec![SortBy::LastName, SortBy::FirstName],
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(full_names(&response), ["Bob Jones", "Amy Smith", "Mia Smith", "Zoe Smith"]);
+    }
+
+    #[test]
+    fn total_pages_rounds_up_partial_pages() {
+        for (count, expected) in [(0, 0), (1, 1), (10, 1), (11, 2), (20, 2), (21, 3)] {
+            let dir = tempfile::tempdir().unwrap();
+            let use_case = ListContactsUseCase::new(repository_with(&dir, count));
+
+            let response = use_case.execute(ListContactsRequest::default()).unwrap();
+
+            assert_eq!(response.total_pages, expected, "{} contacts", count);
+            assert_eq!(response.current_page, 1);
+        }
+    }
+
+    #[test]
+    fn an_exactly_full_last_page_has_nothing_more() {
+        let dir = tempfile::tempdir().unwrap();
+        let use_case = ListContactsUseCase::new(repository_with(&dir, 20));
+
+        let response = use_case.execute(ListContactsRequest { page: 1, ..Default::default() }).unwrap();
+
+        assert_eq!(response.contacts.len(), 10);
+        assert_eq!(response.current_page, 2);
+        assert_eq!(response.total_pages, 2);
+        assert!(!response.has_more);
+    }
+
+    #[test]
+    fn zero_page_size_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let use_case = ListContactsUseCase::new(repository_with(&dir, 1));
+
+        assert!(use_case.execute(ListContactsRequest { page_size: 0, ..Default::default() }).is_err());
+    }
+
+    #[test]
+    fn pages_over_shared_sort_keys_are_stable_across_reloads() {
+        let dir = tempfile::tempdir().unwrap();
+        repository_with(&dir, 7);
+        let page_ids = |page| {
+            // A fresh repository reloads the file, so its hash map order can differ
+            let repository = FileContactRepository::new(dir.path().join("contacts.json").to_string_lossy().into_owned());
+            ListContactsUseCase::new(Arc::new(repository))
+                .execute(ListContactsRequest { page, page_size: 3, ..Default::default() })
+                .unwrap()
+                .contacts
+                .iter()
+                .map(|contact| contact.id().clone())
+                .collect::<Vec<_>>()
+        };
+
+        let first_run: Vec<_> = (0..3).flat_map(page_ids).collect();
+        for _ in 0..3 {
+            assert_eq!((0..3).flat_map(page_ids).collect::<Vec<_>>(), first_run);
+        }
+
+        assert_eq!(first_run.len(), 7);
+        let mut unique = first_run.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique, first_run, "every contact appears once, in ID order");
+    }
+
+    fn at(value: &str) -> Timestamp {
+        Timestamp::parse_date_or_time(value).unwrap()
+    }
+
+    #[test]
+    fn created_after_is_inclusive_and_created_before_exclusive() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, 0);
+        for (first_name, created, updated) in [
+            ("Ann", Some("2024-01-01"), Some("2024-01-01")),
+            ("Bob", Some("2024-01-15"), Some("2024-03-01")),
+            ("Cal", Some("2024-02-01"), Some("2024-02-01")),
+            ("Dee", Some("2024-02-01T12:00:00Z"), Some("2024-01-31T23:59:59Z")),
+            ("Eve", None, None),
+        ] {
+            let contact = Contact::new(
+                first_name.to_string(),
+                "Tester".to_string(),
+                vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+                vec![],
+            );
+            let mut value = serde_json::to_value(contact).unwrap();
+            value["created_at"] = serde_json::to_value(created.map(at)).unwrap();
+            value["updated_at"] = serde_json::to_value(updated.map(at)).unwrap();
+            repository.save(serde_json::from_value(value).unwrap()).unwrap();
+        }
+        let use_case = ListContactsUseCase::new(repository);
+        let listed = |dates: DateFilter| {
+            let response = use_case
+                .execute(ListContactsRequest { sort_by: vec![SortBy::FirstName], dates, ..Default::default() })
+                .unwrap();
+            response.contacts.iter().map(|c| c.first_name().to_string()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(listed(DateFilter::default()), ["Ann", "Bob", "Cal", "Dee", "Eve"]);
+        assert_eq!(
+            listed(DateFilter { created_after: Some(at("2024-01-15")), ..Default::default() }),
+            ["Bob", "Cal", "Dee"]
+        );
+        assert_eq!(
+            listed(DateFilter { created_before: Some(at("2024-02-01")), ..Default::default() }),
+            ["Ann", "Bob"]
+        );
+        assert_eq!(
+            listed(DateFilter {
+                created_after: Some(at("2024-01-15")),
+                created_before: Some(at("2024-02-01")),
+                ..Default::default()
+            }),
+            ["Bob"]
+        );
+        assert_eq!(
+            listed(DateFilter { updated_since: Some(at("2024-02-01")), ..Default::default() }),
+            ["Bob", "Cal"]
+        );
+    }
+
+    #[test]
+    fn all_returns_every_contact_past_the_page_size_cap() {
+        let count = RECOMMENDED_MAX_PAGE_SIZE + 5;
+        let dir = tempfile::tempdir().unwrap();
+        let use_case = ListContactsUseCase::new(repository_with(&dir, count));
+
+        let response = use_case
+            .execute(ListContactsRequest {
+                all: true,
+                sort_by: vec![SortBy::FirstName],
+                reverse: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(response.contacts.len(), count);
+        assert_eq!(response.total_pages, 1);
+        assert!(!response.has_more);
+        let first_names: Vec<&str> = response.contacts.iter().map(Contact::first_name).collect();
+        let mut sorted = first_names.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+        assert_eq!(first_names, sorted);
+    }
 }
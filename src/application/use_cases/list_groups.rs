@@ -0,0 +1,61 @@
+use crate::domain::entities::{Contact, Group};
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::{ContactRepositorySync, GroupRepositorySync, RepositoryError};
+use std::sync::Arc;
+
+/// Use case for listing groups with their member contacts
+/// Follows Single Responsibility Principle - only handles group listing
+pub struct ListGroupsUseCase {
+    groups: Arc<dyn GroupRepositorySync>,
+    contacts: Arc<dyn ContactRepositorySync>,
+}
+
+impl ListGroupsUseCase {
+    pub fn new(groups: Arc<dyn GroupRepositorySync>, contacts: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { groups, contacts }
+    }
+
+    /// Execute the list groups use case
+    pub fn execute(&self, request: ListGroupsRequest) -> Result<ListGroupsResponse, ApplicationError> {
+        let groups = match &request.name {
+            Some(name) => vec![self.groups
+                .find_by_name(name)?
+                .ok_or_else(|| RepositoryError::GroupNotFound(name.clone()))?],
+            None => self.groups.find_all()?,
+        };
+
+        let mut listings = Vec::with_capacity(groups.len());
+        for group in groups {
+            // Members that were permanently deleted are skipped
+            let mut members = Vec::new();
+            for id in group.members() {
+                if let Some(contact) = self.contacts.find_by_id(id)? {
+                    members.push(contact);
+                }
+            }
+            listings.push(GroupListing { group, members });
+        }
+
+        Ok(ListGroupsResponse { groups: listings })
+    }
+}
+
+/// Request DTO for listing groups
+#[derive(Debug, Default)]
+pub struct ListGroupsRequest {
+    /// Only list this group
+    pub name: Option<String>,
+}
+
+/// A group together with its resolved member contacts
+#[derive(Debug)]
+pub struct GroupListing {
+    pub group: Group,
+    pub members: Vec<Contact>,
+}
+
+/// Response DTO for listing groups
+#[derive(Debug)]
+pub struct ListGroupsResponse {
+    pub groups: Vec<GroupListing>,
+}
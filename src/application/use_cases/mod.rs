@@ -4,10 +4,38 @@ pub mod update_contact;
 pub mod delete_contact;
 pub mod list_contacts;
 pub mod search_contacts;
+pub mod upcoming_birthdays;
+pub mod export_contacts;
+pub mod delete_many_contacts;
+pub mod purge_all_contacts;
+pub mod rename_tag;
+pub mod restore_contact;
+pub mod create_group;
+pub mod add_group_member;
+pub mod remove_group_member;
+pub mod list_groups;
+pub mod suggest_tags;
+pub mod random_contacts;
+pub mod touch_contact;
+pub mod contact_stats;
 
 pub use add_contact::{AddContactUseCase, AddContactRequest, AddContactResponse};
-pub use find_contact::{FindContactUseCase, FindContactRequest, FindContactResponse};
+pub use find_contact::{FindContactUseCase, FindContactRequest, FindContactResponse, RelatedContact, MAX_RELATED_CONTACTS};
 pub use update_contact::{UpdateContactUseCase, UpdateContactRequest, UpdateContactResponse};
 pub use delete_contact::{DeleteContactUseCase, DeleteContactRequest, DeleteContactResponse};
-pub use list_contacts::{ListContactsUseCase, ListContactsRequest, ListContactsResponse, SortBy};
+pub use list_contacts::{ListContactsUseCase, ListContactsRequest, ListContactsResponse, SortBy, DeletedFilter, DateFilter, sort_contacts};
 pub use search_contacts::{SearchContactsUseCase, SearchContactsRequest, SearchContactsResponse};
+pub use upcoming_birthdays::{UpcomingBirthdaysUseCase, UpcomingBirthdaysRequest, UpcomingBirthdaysResponse, UpcomingBirthday, days_until_next_birthday};
+pub use export_contacts::{ExportContactsUseCase, ExportContactsRequest, ExportContactsResponse, ExportContactsSummary};
+pub use delete_many_contacts::{DeleteManyContactsUseCase, DeleteManyContactsRequest, DeleteManyContactsResponse};
+pub use purge_all_contacts::{PurgeAllContactsUseCase, PurgeAllContactsRequest, PurgeAllContactsResponse};
+pub use rename_tag::{RenameTagUseCase, RenameTagRequest, RenameTagResponse};
+pub use restore_contact::{RestoreContactUseCase, RestoreContactRequest, RestoreContactResponse};
+pub use create_group::{CreateGroupUseCase, CreateGroupRequest, CreateGroupResponse};
+pub use add_group_member::{AddGroupMemberUseCase, AddGroupMemberRequest, AddGroupMemberResponse};
+pub use remove_group_member::{RemoveGroupMemberUseCase, RemoveGroupMemberRequest, RemoveGroupMemberResponse};
+pub use list_groups::{ListGroupsUseCase, ListGroupsRequest, ListGroupsResponse, GroupListing};
+pub use suggest_tags::{SuggestTagsUseCase, SuggestTagsRequest, SuggestTagsResponse, TagIndex};
+pub use random_contacts::{RandomContactsUseCase, RandomContactsRequest, RandomContactsResponse};
+pub use touch_contact::{TouchContactUseCase, TouchContactRequest, TouchContactResponse};
+pub use contact_stats::{StatsUseCase, StatsRequest, StatsResponse, Stats, TimeBucket, bucket_by_created};
@@ -0,0 +1,86 @@
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::ContactRepositorySync;
+use std::sync::Arc;
+
+/// Use case for permanently removing every contact, e.g. to reset a test phonebook
+/// Follows Single Responsibility Principle - only handles emptying the store
+pub struct PurgeAllContactsUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl PurgeAllContactsUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the purge all contacts use case
+    /// Active and trashed contacts are all removed in one save
+    pub fn execute(&self, request: PurgeAllContactsRequest) -> Result<PurgeAllContactsResponse, ApplicationError> {
+        if request.dry_run {
+            let removed = self.repository.count()? + self.repository.find_deleted()?.len();
+            return Ok(PurgeAllContactsResponse { removed });
+        }
+
+        let mut removed = 0;
+        self.repository.transaction(&mut |contacts| {
+            removed = contacts.len();
+            contacts.clear();
+            Ok(())
+        })?;
+
+        Ok(PurgeAllContactsResponse { removed })
+    }
+}
+
+/// Request DTO for purging every contact
+#[derive(Debug, Default)]
+pub struct PurgeAllContactsRequest {
+    /// Count the contacts that would be removed without removing them
+    pub dry_run: bool,
+}
+
+/// Response DTO for purging every contact
+#[derive(Debug)]
+pub struct PurgeAllContactsResponse {
+    /// Number of contacts removed (or that would be, on a dry run), including trashed ones
+    pub removed: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Contact;
+    use crate::domain::value_objects::{PhoneNumber, Timestamp};
+    use crate::infrastructure::FileContactRepository;
+
+    #[test]
+    fn purge_removes_live_and_trashed_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(path.clone()));
+        for i in 0..3 {
+            let mut contact = Contact::new(
+                format!("Person{}", i),
+                "Tester".to_string(),
+                vec![PhoneNumber::new(format!("555000000{}", i)).unwrap()],
+                vec![],
+            );
+            if i == 0 {
+                contact.mark_deleted(Timestamp::now());
+            }
+            repository.save(contact).unwrap();
+        }
+        let use_case = PurgeAllContactsUseCase::new(repository.clone());
+
+        let preview = use_case.execute(PurgeAllContactsRequest { dry_run: true }).unwrap();
+        assert_eq!(preview.removed, 3);
+        assert_eq!(repository.count().unwrap(), 2);
+
+        let response = use_case.execute(PurgeAllContactsRequest { dry_run: false }).unwrap();
+        assert_eq!(response.removed, 3);
+
+        let reloaded = FileContactRepository::new(path);
+        assert_eq!(reloaded.count().unwrap(), 0);
+        assert!(reloaded.find_deleted().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::ContactRepositorySync;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::sync::Arc;
+
+/// Use case for picking random contacts, e.g. for keep-in-touch prompts
+/// Follows Single Responsibility Principle - only handles random selection
+pub struct RandomContactsUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl RandomContactsUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the random contacts use case
+    pub fn execute(&self, request: RandomContactsRequest) -> Result<RandomContactsResponse, ApplicationError> {
+        if request.count == 0 {
+            return Err(DomainError::Validation("Count must be greater than 0".to_string()).into());
+        }
+
+        let mut contacts: Vec<Contact> = self
+            .repository
+            .find_all()?
+            .into_iter()
+            .filter(|contact| {
+                request
+                    .tag
+                    .as_ref()
+                    .is_none_or(|tag| contact.tags().iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            })
+            .collect();
+
+        // Start from a fixed order so a given seed always picks the same contacts
+        contacts.sort_by(|a, b| a.id().cmp(b.id()));
+
+        let seed = request.seed.unwrap_or_else(rand::random);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let count = request.count.min(contacts.len());
+        let (chosen, _) = contacts.partial_shuffle(&mut rng, count);
+        let contacts = chosen.to_vec();
+
+        Ok(RandomContactsResponse { contacts, seed })
+    }
+}
+
+/// Request DTO for picking random contacts
+#[derive(Debug)]
+pub struct RandomContactsRequest {
+    pub count: usize,
+    /// Only pick contacts with this tag
+    pub tag: Option<String>,
+    /// Fixed seed for a reproducible selection
+    pub seed: Option<u64>,
+}
+
+/// Response DTO for picking random contacts
+#[derive(Debug)]
+pub struct RandomContactsResponse {
+    pub contacts: Vec<Contact>,
+    /// Seed used, so the selection can be repeated
+    pub seed: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+    use std::collections::HashSet;
+
+    fn use_case_with(dir: &tempfile::TempDir, count: usize) -> RandomContactsUseCase {
+        let repository = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+        for i in 0..count {
+            let mut contact = Contact::new(
+                format!("Person{}", i),
+                "Tester".to_string(),
+                vec![PhoneNumber::new(format!("555{:07}", i)).unwrap()],
+                vec![],
+            );
+            if i % 2 == 0 {
+                contact.add_tag("even".to_string());
+            }
+            repository.save(contact).unwrap();
+        }
+        RandomContactsUseCase::new(repository)
+    }
+
+    fn pick(use_case: &RandomContactsUseCase, count: usize, tag: Option<&str>, seed: Option<u64>) -> RandomContactsResponse {
+        use_case
+            .execute(RandomContactsRequest {
+                count,
+                tag: tag.map(str::to_string),
+                seed,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn same_seed_picks_the_same_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let use_case = use_case_with(&dir, 20);
+
+        let first = pick(&use_case, 5, None, None);
+        let again = pick(&use_case, 5, None, Some(first.seed));
+
+        assert_eq!(first.contacts, again.contacts);
+        let ids: HashSet<_> = first.contacts.iter().map(|contact| contact.id().clone()).collect();
+        assert_eq!(ids.len(), 5, "picks must be distinct");
+    }
+
+    #[test]
+    fn picks_are_limited_to_the_tag_and_the_available_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let use_case = use_case_with(&dir, 6);
+
+        let response = pick(&use_case, 10, Some("EVEN"), Some(7));
+
+        assert_eq!(response.contacts.len(), 3);
+        assert!(response.contacts.iter().all(|contact| contact.tags() == ["even".to_string()]));
+    }
+
+    #[test]
+    fn zero_count_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let use_case = use_case_with(&dir, 1);
+
+        let result = use_case.execute(RandomContactsRequest { count: 0, tag: None, seed: None });
+
+        assert!(result.unwrap_err().is_validation());
+    }
+}
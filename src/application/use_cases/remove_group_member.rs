@@ -0,0 +1,49 @@
+use crate::domain::entities::Group;
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::{GroupRepositorySync, RepositoryError};
+use crate::domain::value_objects::ContactId;
+use std::sync::Arc;
+
+/// Use case for removing a contact from a group
+/// Follows Single Responsibility Principle - only handles removing group members
+pub struct RemoveGroupMemberUseCase {
+    groups: Arc<dyn GroupRepositorySync>,
+}
+
+impl RemoveGroupMemberUseCase {
+    pub fn new(groups: Arc<dyn GroupRepositorySync>) -> Self {
+        Self { groups }
+    }
+
+    /// Execute the remove group member use case
+    pub fn execute(&self, request: RemoveGroupMemberRequest) -> Result<RemoveGroupMemberResponse, ApplicationError> {
+        let mut group = self.groups
+            .find_by_name(&request.group)?
+            .ok_or_else(|| RepositoryError::GroupNotFound(request.group.clone()))?;
+
+        if !group.remove_member(&request.contact_id) {
+            return Err(DomainError::Validation("Contact is not in the group".to_string()).into());
+        }
+
+        self.groups.update(group.clone())?;
+
+        Ok(RemoveGroupMemberResponse {
+            group,
+            message: "Contact removed from group".to_string(),
+        })
+    }
+}
+
+/// Request DTO for removing a contact from a group
+#[derive(Debug)]
+pub struct RemoveGroupMemberRequest {
+    pub group: String,
+    pub contact_id: ContactId,
+}
+
+/// Response DTO for removing a contact from a group
+#[derive(Debug)]
+pub struct RemoveGroupMemberResponse {
+    pub group: Group,
+    pub message: String,
+}
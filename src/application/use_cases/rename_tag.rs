@@ -0,0 +1,127 @@
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::ContactRepositorySync;
+use std::sync::Arc;
+
+/// Use case for renaming a tag across all contacts
+/// Follows Single Responsibility Principle - only handles tag renaming
+pub struct RenameTagUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl RenameTagUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the rename tag use case
+    pub fn execute(&self, request: RenameTagRequest) -> Result<RenameTagResponse, ApplicationError> {
+        if request.from.trim().is_empty() || request.to.trim().is_empty() {
+            return Err(DomainError::Validation("Tag names cannot be empty".to_string()).into());
+        }
+
+        if request.from == request.to {
+            return Err(DomainError::Validation(
+                "New tag name must differ from the old one".to_string(),
+            )
+            .into());
+        }
+
+        // Rename across all live contacts in one save
+        let mut affected = 0;
+        self.repository.transaction(&mut |contacts| {
+            affected = 0;
+            for contact in contacts.values_mut().filter(|contact| !contact.is_deleted()) {
+                if contact.rename_tag(&request.from, &request.to) {
+                    affected += 1;
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(RenameTagResponse {
+            from: request.from,
+            to: request.to,
+            affected,
+        })
+    }
+}
+
+/// Request DTO for renaming a tag
+#[derive(Debug)]
+pub struct RenameTagRequest {
+    pub from: String,
+    pub to: String,
+}
+
+/// Response DTO for renaming a tag
+#[derive(Debug)]
+pub struct RenameTagResponse {
+    pub from: String,
+    pub to: String,
+    /// Number of contacts whose tags changed
+    pub affected: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Contact;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    fn rename(repository: &Arc<dyn ContactRepositorySync>, from: &str, to: &str) -> Result<RenameTagResponse, ApplicationError> {
+        RenameTagUseCase::new(repository.clone()).execute(RenameTagRequest {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+    }
+
+    #[test]
+    fn tag_is_renamed_on_every_contact_without_duplicates() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+        let tag_sets: [&[&str]; 4] = [&["work"], &["work", "friends"], &["work", "job"], &["family"]];
+        for (i, tags) in tag_sets.iter().enumerate() {
+            let mut contact = Contact::new(
+                format!("Person{}", i),
+                "Tester".to_string(),
+                vec![PhoneNumber::new(format!("555000000{}", i)).unwrap()],
+                vec![],
+            );
+            for tag in *tags {
+                contact.add_tag(tag.to_string());
+            }
+            repository.save(contact).unwrap();
+        }
+
+        let response = rename(&repository, "work", "job").unwrap();
+
+        assert_eq!(response.affected, 3);
+        let mut tags: Vec<Vec<String>> = repository
+            .find_all()
+            .unwrap()
+            .iter()
+            .map(|contact| contact.tags().to_vec())
+            .collect();
+        tags.sort();
+        assert_eq!(
+            tags,
+            [vec!["family"], vec!["job"], vec!["job"], vec!["job", "friends"]]
+                .map(|tags| tags.into_iter().map(String::from).collect::<Vec<_>>())
+        );
+    }
+
+    #[test]
+    fn empty_or_unchanged_tag_names_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+
+        assert!(rename(&repository, " ", "job").unwrap_err().is_validation());
+        assert!(rename(&repository, "work", "").unwrap_err().is_validation());
+        assert!(rename(&repository, "work", "work").unwrap_err().is_validation());
+    }
+}
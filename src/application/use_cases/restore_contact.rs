@@ -0,0 +1,126 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
+use crate::domain::value_objects::ContactId;
+use std::sync::Arc;
+
+/// Use case for restoring a contact from the trash
+/// Follows Single Responsibility Principle - only handles contact restoration
+pub struct RestoreContactUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl RestoreContactUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the restore contact use case
+    pub fn execute(&self, request: RestoreContactRequest) -> Result<RestoreContactResponse, ApplicationError> {
+        let mut contact = self.repository
+            .find_by_id(&request.contact_id)?
+            .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+
+        if !contact.is_deleted() {
+            return Err(DomainError::Validation("Contact is not in the trash".to_string()).into());
+        }
+
+        self.repository.transaction(&mut |contacts| {
+            let stored = contacts
+                .get_mut(&request.contact_id)
+                .filter(|contact| contact.is_deleted())
+                .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+            stored.restore();
+            contact = stored.clone();
+            Ok(())
+        })?;
+
+        Ok(RestoreContactResponse {
+            contact,
+            message: "Contact restored successfully".to_string(),
+        })
+    }
+}
+
+/// Request DTO for restoring a contact
+#[derive(Debug)]
+pub struct RestoreContactRequest {
+    pub contact_id: ContactId,
+}
+
+/// Response DTO for restoring a contact
+#[derive(Debug)]
+pub struct RestoreContactResponse {
+    pub contact: Contact,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::use_cases::{DeleteContactRequest, DeleteContactUseCase};
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    fn setup() -> (tempfile::TempDir, Arc<dyn ContactRepositorySync>, ContactId) {
+        let dir = tempfile::tempdir().unwrap();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+        let contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let id = contact.id().clone();
+        repository.save(contact).unwrap();
+        (dir, repository, id)
+    }
+
+    fn delete(repository: &Arc<dyn ContactRepositorySync>, id: &ContactId, purge: bool) {
+        DeleteContactUseCase::new(repository.clone())
+            .execute(DeleteContactRequest {
+                contact_id: id.clone(),
+                purge,
+                ..Default::default()
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn deleted_contact_can_be_restored() {
+        let (_dir, repository, id) = setup();
+        delete(&repository, &id, false);
+        assert_eq!(repository.count().unwrap(), 0);
+
+        let response = RestoreContactUseCase::new(repository.clone())
+            .execute(RestoreContactRequest { contact_id: id.clone() })
+            .unwrap();
+
+        assert!(!response.contact.is_deleted());
+        assert_eq!(repository.count().unwrap(), 1);
+        assert!(repository.find_deleted().unwrap().is_empty());
+    }
+
+    #[test]
+    fn restoring_a_live_contact_is_rejected() {
+        let (_dir, repository, id) = setup();
+
+        let result = RestoreContactUseCase::new(repository).execute(RestoreContactRequest { contact_id: id });
+
+        assert!(result.unwrap_err().is_validation());
+    }
+
+    #[test]
+    fn purged_contact_cannot_be_restored() {
+        let (_dir, repository, id) = setup();
+        delete(&repository, &id, false);
+        delete(&repository, &id, true);
+
+        let result = RestoreContactUseCase::new(repository.clone()).execute(RestoreContactRequest { contact_id: id });
+
+        assert!(result.unwrap_err().is_not_found());
+        assert!(repository.find_deleted().unwrap().is_empty());
+    }
+}
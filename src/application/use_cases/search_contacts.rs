@@ -1,5 +1,12 @@
+use crate::application::use_cases::list_contacts::SortBy;
+use crate::application::validation::{Validator, RECOMMENDED_MAX_PAGE_SIZE};
 use crate::domain::entities::Contact;
-use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
+use crate::domain::errors::{ApplicationError, DomainError};
+use crate::domain::repositories::ContactRepositorySync;
+use crate::domain::value_objects::ContactId;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::sync::Arc;
 
 /// Use case for searching contacts
@@ -14,28 +21,246 @@ impl SearchContactsUseCase {
     }
 
     /// Execute the search contacts use case
-    pub fn execute(&self, request: SearchContactsRequest) -> Result<SearchContactsResponse, RepositoryError> {
+    pub fn execute(&self, request: SearchContactsRequest) -> Result<SearchContactsResponse, ApplicationError> {
         if request.query.trim().is_empty() {
-            return Err(RepositoryError::ValidationError(
+            return Err(DomainError::Validation(
                 "Search query cannot be empty".to_string(),
-            ));
+            ).into());
         }
 
-        let contacts = self.repository.search(&request.query)?;
-        let count = contacts.len();
+        Validator::validate_pagination(request.offset, request.limit, request.max_limit)?;
+
+        let regex = if request.regex {
+            Some(Regex::new(&request.query).map_err(|e| {
+                DomainError::Validation(format!("Invalid regex '{}': {}", request.query, e))
+            })?)
+        } else {
+            None
+        };
+
+        let cursor = request
+            .cursor
+            .as_deref()
+            .map(|cursor| SearchCursor::decode(cursor, request.sort_by.len()))
+            .transpose()?;
+        if cursor.is_some() && request.offset > 0 {
+            return Err(DomainError::Validation("A search cursor can't be combined with an offset".to_string()).into());
+        }
+
+        // Without relevance ranking a contact's place in the order is known before it is
+        // matched, so a cursor page drops everything up to the cursor unmatched
+        let resume_after = cursor.as_ref().filter(|_| !request.rank_by_relevance);
+        let contacts: Vec<Contact> = if let Some(cursor) = resume_after {
+            self.repository
+                .find_all()?
+                .into_iter()
+                .filter(|contact| cursor.precedes(&SearchCursor::of(contact, &request.sort_by, 0), request.reverse, false))
+                .filter(|contact| match &regex {
+                    Some(regex) => contact.matches_regex_with(regex, request.include_metadata),
+                    None => contact.matches_search_with(&request.query, request.include_metadata),
+                })
+                .collect()
+        } else if let Some(regex) = &regex {
+            self.repository
+                .find_all()?
+                .into_iter()
+                .filter(|contact| contact.matches_regex_with(regex, request.include_metadata))
+                .collect()
+        } else if request.include_metadata {
+            self.repository.search(&request.query)?
+        } else {
+            self.repository
+                .find_all()?
+                .into_iter()
+                .filter(|contact| contact.matches_search_with(&request.query, false))
+                .collect()
+        };
+
+        let query_lower = request.query.to_lowercase();
+        let matches = |value: &str| match &regex {
+            Some(regex) => regex.is_match(value),
+            None => value.to_lowercase().contains(&query_lower),
+        };
+
+        let mut positioned: Vec<(SearchCursor, Contact)> = contacts
+            .into_iter()
+            .map(|contact| {
+                let score = if request.rank_by_relevance {
+                    relevance(&contact, request.include_metadata, matches)
+                } else {
+                    0
+                };
+                (SearchCursor::of(&contact, &request.sort_by, score), contact)
+            })
+            .collect();
+        let count = positioned.len();
+        positioned.sort_by(|(a, _), (b, _)| a.compare(b, request.reverse, request.rank_by_relevance));
+
+        // Seek past the cursor in the sorted matches; unless the contacts before it were
+        // already dropped, `count` still covers every match
+        let start = cursor.as_ref().map_or(0, |cursor| {
+            positioned.partition_point(|(position, _)| {
+                !cursor.precedes(position, request.reverse, request.rank_by_relevance)
+            })
+        });
+        let page: Vec<(SearchCursor, Contact)> = positioned
+            .into_iter()
+            .skip(start.saturating_add(request.offset))
+            .take(request.limit)
+            .collect();
+        let end = start.saturating_add(request.offset).saturating_add(page.len());
+        let next_cursor = (end < count)
+            .then(|| page.last().map(|(position, _)| position.encode()))
+            .flatten();
+        let contacts = page.into_iter().map(|(_, contact)| contact).collect();
 
         Ok(SearchContactsResponse {
             contacts,
             query: request.query,
             count,
+            offset: start.saturating_add(request.offset),
+            next_cursor,
+            counted_from_cursor: resume_after.is_some(),
         })
     }
 }
 
+/// Weights of a search hit in each field, used to rank results
+/// A contact scores the sum of the weights of the fields the query hits,
+/// so a name match outranks one buried in the notes
+pub const NAME_WEIGHT: u32 = 10;
+pub const EMAIL_WEIGHT: u32 = 5;
+pub const PHONE_WEIGHT: u32 = 5;
+pub const TAG_WEIGHT: u32 = 3;
+pub const NOTES_WEIGHT: u32 = 1;
+pub const METADATA_WEIGHT: u32 = 1;
+
+/// Combined relevance of a contact, given whether the query hits a single field value
+/// Each field counts once however many of its values match
+pub fn relevance(contact: &Contact, include_metadata: bool, matches: impl Fn(&str) -> bool) -> u32 {
+    let fields = [
+        (
+            NAME_WEIGHT,
+            matches(contact.first_name()) || matches(contact.last_name()) || matches(&contact.full_name()),
+        ),
+        (EMAIL_WEIGHT, contact.emails().iter().any(|e| matches(e.value()))),
+        (PHONE_WEIGHT, contact.phone_numbers().iter().any(|p| matches(p.value()))),
+        (TAG_WEIGHT, contact.tags().iter().any(|t| matches(t))),
+        (NOTES_WEIGHT, contact.notes().is_some_and(&matches)),
+        (
+            METADATA_WEIGHT,
+            include_metadata && contact.metadata().iter().any(|(key, value)| matches(key) || matches(value)),
+        ),
+    ];
+
+    fields.into_iter().filter(|(_, hit)| *hit).map(|(weight, _)| weight).sum()
+}
+
+/// Position of a contact in the search order, handed to clients as an opaque
+/// `next_cursor` so the following page can pick up right after it
+/// Holds the contact's relevance, its `sort_by` key values and its ID, which
+/// together place it exactly since the ID breaks every tie
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SearchCursor {
+    relevance: u32,
+    keys: Vec<String>,
+    id: ContactId,
+}
+
+impl SearchCursor {
+    fn of(contact: &Contact, sort_by: &[SortBy], relevance: u32) -> Self {
+        Self {
+            relevance,
+            keys: sort_by.iter().map(|key| key.value(contact)).collect(),
+            id: contact.id().clone(),
+        }
+    }
+
+    /// Order two positions the way results are listed: most relevant first when
+    /// ranking, then by sort keys and ID, with `reverse` flipping only the latter
+    fn compare(&self, other: &Self, reverse: bool, rank_by_relevance: bool) -> Ordering {
+        let by_relevance = if rank_by_relevance {
+            other.relevance.cmp(&self.relevance)
+        } else {
+            Ordering::Equal
+        };
+        let by_keys = self.keys.cmp(&other.keys).then_with(|| self.id.cmp(&other.id));
+        by_relevance.then(if reverse { by_keys.reverse() } else { by_keys })
+    }
+
+    /// Whether `other` is listed after this position
+    fn precedes(&self, other: &Self, reverse: bool, rank_by_relevance: bool) -> bool {
+        self.compare(other, reverse, rank_by_relevance).is_lt()
+    }
+
+    /// Hex-encoded JSON, so the cursor is URL- and shell-safe
+    fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        json.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    fn decode(value: &str, key_count: usize) -> Result<Self, DomainError> {
+        let invalid = || DomainError::Validation(format!("Invalid search cursor '{}'", value));
+        if !value.len().is_multiple_of(2) || !value.is_ascii() {
+            return Err(invalid());
+        }
+
+        let bytes = (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        let cursor: Self = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+        // A cursor from a search with different sort keys can't be placed in this order
+        if cursor.keys.len() != key_count {
+            return Err(invalid());
+        }
+        Ok(cursor)
+    }
+}
+
 /// Request DTO for searching contacts
 #[derive(Debug)]
 pub struct SearchContactsRequest {
     pub query: String,
+    pub regex: bool,
+    /// Maximum number of matches to return
+    pub limit: usize,
+    /// Largest `limit` accepted
+    pub max_limit: usize,
+    /// Number of matches to skip
+    pub offset: usize,
+    /// `next_cursor` from the previous page; results resume right after it.
+    /// Pass the same query, sort and ranking options as that page, and no `offset`.
+    /// Without `rank_by_relevance`, contacts up to the cursor are skipped before matching;
+    /// with it, every contact is still matched and scored, so the cursor only keeps the
+    /// pages stable and saves no work.
+    pub cursor: Option<String>,
+    /// Sort keys in priority order
+    pub sort_by: Vec<SortBy>,
+    pub reverse: bool,
+    /// Also match metadata keys and values
+    pub include_metadata: bool,
+    /// Order by `relevance` first, leaving `sort_by` to break ties
+    pub rank_by_relevance: bool,
+}
+
+impl Default for SearchContactsRequest {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            regex: false,
+            limit: 20,
+            max_limit: RECOMMENDED_MAX_PAGE_SIZE,
+            offset: 0,
+            cursor: None,
+            sort_by: vec![SortBy::LastName],
+            reverse: false,
+            include_metadata: true,
+            rank_by_relevance: true,
+        }
+    }
 }
 
 /// Response DTO for searching contacts
@@ -43,5 +268,235 @@ pub struct SearchContactsRequest {
 pub struct SearchContactsResponse {
     pub contacts: Vec<Contact>,
     pub query: String,
+    /// Total number of matches, before the cursor, `offset` and `limit` were applied
+    /// (only those after the cursor when `counted_from_cursor` is set)
     pub count: usize,
+    /// Position of the first returned match among those counted in `count`
+    pub offset: usize,
+    /// Pass back as `cursor` to fetch the next page; `None` on the last page
+    pub next_cursor: Option<String>,
+    /// Whether contacts up to the cursor were skipped unmatched, leaving them out of `count`
+    pub counted_from_cursor: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    fn repository_with(dir: &tempfile::TempDir, last_names: &[&str]) -> Arc<dyn ContactRepositorySync> {
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+        for (i, last_name) in last_names.iter().enumerate() {
+            repository
+                .save(Contact::new(
+                    "Ann".to_string(),
+                    last_name.to_string(),
+                    vec![PhoneNumber::new(format!("555000000{}", i)).unwrap()],
+                    vec![],
+                ))
+                .unwrap();
+        }
+        repository
+    }
+
+    fn search(repository: &Arc<dyn ContactRepositorySync>, limit: usize, cursor: Option<String>) -> SearchContactsResponse {
+        SearchContactsUseCase::new(repository.clone())
+            .execute(SearchContactsRequest {
+                query: "ann".to_string(),
+                limit,
+                cursor,
+                ..Default::default()
+            })
+            .unwrap()
+    }
+
+    fn last_names(contacts: &[Contact]) -> Vec<String> {
+        contacts.iter().map(|contact| contact.last_name().to_string()).collect()
+    }
+
+    #[test]
+    fn cursor_pages_list_every_match_once_and_keep_the_total_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, &["Gray", "Adams", "Ford", "Clark", "Evans", "Brown", "Davis"]);
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = search(&repository, 3, cursor);
+            assert_eq!(page.count, 7, "count is the total number of matches on every page");
+            seen.extend(last_names(&page.contacts));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(seen, ["Adams", "Brown", "Clark", "Davis", "Evans", "Ford", "Gray"]);
+    }
+
+    #[test]
+    fn cursor_resumes_after_its_contact_even_once_that_contact_is_gone() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, &["Adams", "Brown", "Clark", "Davis"]);
+
+        let first = search(&repository, 2, None);
+        assert_eq!(last_names(&first.contacts), ["Adams", "Brown"]);
+        repository.delete(first.contacts[1].id()).unwrap();
+
+        let second = search(&repository, 2, first.next_cursor.clone());
+        assert_eq!(last_names(&second.contacts), ["Clark", "Davis"]);
+        assert_eq!(second.count, 3);
+        assert_eq!(second.offset, 1, "Adams is the only match before the cursor now");
+        assert!(second.next_cursor.is_none());
+
+        let with_offset = SearchContactsUseCase::new(repository).execute(SearchContactsRequest {
+            query: "ann".to_string(),
+            offset: 1,
+            cursor: first.next_cursor,
+            ..Default::default()
+        });
+        assert!(with_offset.unwrap_err().is_validation());
+    }
+
+    #[test]
+    fn unranked_cursor_pages_skip_the_contacts_before_the_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, &["Evans", "Adams", "Davis", "Brown", "Clark"]);
+        let page = |reverse: bool, cursor: Option<String>| {
+            SearchContactsUseCase::new(repository.clone())
+                .execute(SearchContactsRequest {
+                    query: "ann".to_string(),
+                    limit: 2,
+                    cursor,
+                    reverse,
+                    rank_by_relevance: false,
+                    ..Default::default()
+                })
+                .unwrap()
+        };
+
+        let first = page(false, None);
+        assert_eq!((first.count, first.counted_from_cursor), (5, false));
+        let second = page(false, first.next_cursor);
+        assert_eq!(last_names(&second.contacts), ["Clark", "Davis"]);
+        assert_eq!((second.count, second.offset, second.counted_from_cursor), (3, 0, true));
+        let third = page(false, second.next_cursor);
+        assert_eq!(last_names(&third.contacts), ["Evans"]);
+        assert!(third.next_cursor.is_none());
+
+        let reversed = page(true, page(true, None).next_cursor);
+        assert_eq!(last_names(&reversed.contacts), ["Clark", "Brown"]);
+        assert_eq!(reversed.count, 3);
+    }
+
+    #[test]
+    fn repeated_searches_return_the_same_order() {
+        let dir = tempfile::tempdir().unwrap();
+        repository_with(&dir, &["Smith", "Jones", "Smith", "Brown", "Smith", "Jones"]);
+        let ids = |contacts: &[Contact]| contacts.iter().map(|c| c.id().clone()).collect::<Vec<_>>();
+
+        // A fresh repository reloads the file, so its hash map order can differ
+        let reload = || -> Arc<dyn ContactRepositorySync> {
+            Arc::new(FileContactRepository::new(dir.path().join("contacts.json").to_string_lossy().into_owned()))
+        };
+        let first = search(&reload(), 20, None);
+        let second = search(&reload(), 20, None);
+
+        assert_eq!(last_names(&first.contacts), ["Brown", "Jones", "Jones", "Smith", "Smith", "Smith"]);
+        assert_eq!(ids(&first.contacts), ids(&second.contacts));
+        let smiths = &ids(&first.contacts)[3..];
+        assert!(smiths.windows(2).all(|pair| pair[0] < pair[1]), "ties are ordered by ID");
+    }
+
+    #[test]
+    fn search_honours_sort_keys_and_reverse() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, &["Adams", "Clark", "Brown"]);
+
+        let response = SearchContactsUseCase::new(repository)
+            .execute(SearchContactsRequest {
+                query: "ann".to_string(),
+                sort_by: vec![SortBy::FullName],
+                reverse: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(last_names(&response.contacts), ["Clark", "Brown", "Adams"]);
+    }
+
+    #[test]
+    fn accented_and_cjk_names_are_found_and_sorted() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, &["Ötzi", "山田", "Émile", "Zoë", "Adams"]);
+        let search_for = |query: &str| {
+            let response = SearchContactsUseCase::new(repository.clone())
+                .execute(SearchContactsRequest {
+                    query: query.to_string(),
+                    ..Default::default()
+                })
+                .unwrap();
+            last_names(&response.contacts)
+        };
+
+        assert_eq!(search_for("émile"), ["Émile"]);
+        assert_eq!(search_for("ÉMILE"), ["Émile"]);
+        assert_eq!(search_for("山"), ["山田"]);
+        assert_eq!(search_for("ZOË"), ["Zoë"]);
+        // Names compare by code point, so accented and CJK names follow plain ASCII ones
+        assert_eq!(search_for("ann"), ["Adams", "Zoë", "Émile", "Ötzi", "山田"]);
+    }
+
+    #[test]
+    fn name_matches_rank_above_note_only_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, &["Adams", "Brown"]);
+        let mut adams = repository.find_all().unwrap().into_iter().find(|c| c.last_name() == "Adams").unwrap();
+        adams.set_notes(Some("Introduced by Zed Brown".to_string()));
+        repository.update(adams.clone()).unwrap();
+
+        let search_for = |rank_by_relevance: bool| {
+            let response = SearchContactsUseCase::new(repository.clone())
+                .execute(SearchContactsRequest {
+                    query: "brown".to_string(),
+                    rank_by_relevance,
+                    ..Default::default()
+                })
+                .unwrap();
+            last_names(&response.contacts)
+        };
+
+        assert_eq!(search_for(true), ["Brown", "Adams"]);
+        assert_eq!(search_for(false), ["Adams", "Brown"]);
+        assert_eq!(relevance(&adams, true, |value| value.to_lowercase().contains("brown")), NOTES_WEIGHT);
+    }
+
+    #[test]
+    fn metadata_keys_and_values_are_searched_unless_turned_off() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_with(&dir, &["Adams", "Brown"]);
+        let mut adams = repository.find_all().unwrap().into_iter().find(|c| c.last_name() == "Adams").unwrap();
+        adams.set_metadata("Company".to_string(), "Acme Widgets".to_string());
+        repository.update(adams).unwrap();
+
+        let search_for = |query: &str, include_metadata: bool| {
+            let response = SearchContactsUseCase::new(repository.clone())
+                .execute(SearchContactsRequest {
+                    query: query.to_string(),
+                    include_metadata,
+                    ..Default::default()
+                })
+                .unwrap();
+            last_names(&response.contacts)
+        };
+
+        assert_eq!(search_for("acme", true), ["Adams"]);
+        assert_eq!(search_for("WIDGETS", true), ["Adams"]);
+        assert_eq!(search_for("company", true), ["Adams"]);
+        assert!(search_for("acme", false).is_empty());
+        assert_eq!(search_for("ann", false), ["Adams", "Brown"]);
+    }
 }
@@ -0,0 +1,124 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::ContactRepositorySync;
+use std::sync::Arc;
+
+/// Distinct tags in use, compared case-insensitively
+/// The first spelling seen for each tag is kept
+#[derive(Debug, Clone, Default)]
+pub struct TagIndex {
+    tags: Vec<String>,
+}
+
+impl TagIndex {
+    /// Collect the tags of the given contacts
+    pub fn from_contacts(contacts: &[Contact]) -> Self {
+        let mut index = Self::default();
+        for tag in contacts.iter().flat_map(|contact| contact.tags()) {
+            if !index.tags.iter().any(|known| known.eq_ignore_ascii_case(tag)) {
+                index.tags.push(tag.clone());
+            }
+        }
+        index.tags.sort_by_key(|tag| tag.to_lowercase());
+        index
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Existing tag that `tag` is probably a typo of: same letters in a different
+    /// case, or one edit (including swapping two adjacent letters) away
+    pub fn suggest(&self, tag: &str) -> Option<&str> {
+        if self.tags.iter().any(|known| known == tag) {
+            return None;
+        }
+
+        let lowered = tag.to_lowercase();
+        self.tags
+            .iter()
+            .find(|known| strsim::osa_distance(&known.to_lowercase(), &lowered) <= 1)
+            .map(String::as_str)
+    }
+}
+
+/// Use case for spotting near-duplicate tags before they are added
+/// Follows Single Responsibility Principle - only handles tag suggestions
+pub struct SuggestTagsUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl SuggestTagsUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the suggest tags use case
+    pub fn execute(&self, request: SuggestTagsRequest) -> Result<SuggestTagsResponse, ApplicationError> {
+        let index = TagIndex::from_contacts(&self.repository.find_all()?);
+
+        let suggestions = request
+            .tags
+            .into_iter()
+            .filter_map(|tag| index.suggest(&tag).map(|existing| (tag.clone(), existing.to_string())))
+            .collect();
+
+        Ok(SuggestTagsResponse { suggestions })
+    }
+}
+
+/// Request DTO for suggesting tags
+#[derive(Debug)]
+pub struct SuggestTagsRequest {
+    /// Tags about to be added
+    pub tags: Vec<String>,
+}
+
+/// Response DTO for suggesting tags
+#[derive(Debug)]
+pub struct SuggestTagsResponse {
+    /// `(new tag, existing tag it resembles)` pairs
+    pub suggestions: Vec<(String, String)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::PhoneNumber;
+
+    fn index(tags: &[&str]) -> TagIndex {
+        let mut contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        for tag in tags {
+            contact.add_tag(tag.to_string());
+        }
+        TagIndex::from_contacts(&[contact])
+    }
+
+    #[test]
+    fn a_transposed_tag_suggests_the_existing_spelling() {
+        let index = index(&["work", "family"]);
+
+        assert_eq!(index.suggest("Wrok"), Some("work"));
+        assert_eq!(index.suggest("Work"), Some("work"));
+        assert_eq!(index.suggest("famly"), Some("family"));
+    }
+
+    #[test]
+    fn exact_and_distant_tags_get_no_suggestion() {
+        let index = index(&["work"]);
+
+        assert_eq!(index.suggest("work"), None);
+        assert_eq!(index.suggest("gym"), None);
+        assert_eq!(index.suggest("wrko"), None, "two edits away");
+    }
+
+    #[test]
+    fn tags_differing_only_in_case_are_indexed_once() {
+        assert_eq!(index(&["Work", "work", "art"]).tags(), ["art", "Work"]);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
+use crate::domain::value_objects::ContactId;
+use std::sync::Arc;
+
+/// Use case for marking a contact as recently reviewed
+/// Follows Single Responsibility Principle - only handles bumping `updated_at`
+pub struct TouchContactUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl TouchContactUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the touch contact use case
+    pub fn execute(&self, request: TouchContactRequest) -> Result<TouchContactResponse, ApplicationError> {
+        let mut touched = None;
+        self.repository.transaction(&mut |contacts| {
+            let contact = contacts
+                .get_mut(&request.contact_id)
+                .filter(|contact| !contact.is_deleted())
+                .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+            contact.touch();
+            touched = Some(contact.clone());
+            Ok(())
+        })?;
+
+        Ok(TouchContactResponse {
+            contact: touched.expect("a successful transaction touches the contact"),
+            message: "Contact marked as updated".to_string(),
+        })
+    }
+}
+
+/// Request DTO for touching a contact
+#[derive(Debug)]
+pub struct TouchContactRequest {
+    pub contact_id: ContactId,
+}
+
+/// Response DTO for touching a contact
+#[derive(Debug)]
+pub struct TouchContactResponse {
+    pub contact: Contact,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{PhoneNumber, Timestamp};
+    use crate::infrastructure::FileContactRepository;
+
+    #[test]
+    fn touch_advances_updated_at_and_changes_nothing_else() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(path.clone()));
+        let mut contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        contact.add_tag("work".to_string());
+        contact.set_notes(Some("Reviewed yearly".to_string()));
+        let mut value = serde_json::to_value(&contact).unwrap();
+        value["updated_at"] = serde_json::to_value(Timestamp::parse("2024-01-01T00:00:00Z").unwrap()).unwrap();
+        let before: Contact = serde_json::from_value(value).unwrap();
+        let id = before.id().clone();
+        repository.save(before.clone()).unwrap();
+
+        let response = TouchContactUseCase::new(repository).execute(TouchContactRequest { contact_id: id.clone() }).unwrap();
+
+        let after = FileContactRepository::new(path).find_by_id(&id).unwrap().unwrap();
+        assert_eq!(after, response.contact);
+        assert!(after.updated_at() > before.updated_at());
+        let without_updated_at = |contact: &Contact| {
+            let mut value = serde_json::to_value(contact).unwrap();
+            value.as_object_mut().unwrap().remove("updated_at");
+            value
+        };
+        assert_eq!(without_updated_at(&after), without_updated_at(&before));
+    }
+
+    #[test]
+    fn touching_an_unknown_contact_is_not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository: Arc<dyn ContactRepositorySync> = Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ));
+
+        let error = TouchContactUseCase::new(repository)
+            .execute(TouchContactRequest { contact_id: ContactId::new() })
+            .unwrap_err();
+
+        assert!(error.is_not_found(), "{}", error);
+    }
+}
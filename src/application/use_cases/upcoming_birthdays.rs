@@ -0,0 +1,107 @@
+use crate::domain::entities::Contact;
+use crate::domain::errors::ApplicationError;
+use crate::domain::repositories::ContactRepositorySync;
+use crate::domain::value_objects::Date;
+use chrono::{Datelike, NaiveDate};
+use std::sync::Arc;
+
+/// Use case for listing contacts with an upcoming birthday
+/// Follows Single Responsibility Principle - only handles birthday reminders
+pub struct UpcomingBirthdaysUseCase {
+    repository: Arc<dyn ContactRepositorySync>,
+}
+
+impl UpcomingBirthdaysUseCase {
+    pub fn new(repository: Arc<dyn ContactRepositorySync>) -> Self {
+        Self { repository }
+    }
+
+    /// Execute the upcoming birthdays use case
+    pub fn execute(&self, request: UpcomingBirthdaysRequest) -> Result<UpcomingBirthdaysResponse, ApplicationError> {
+        let today = request.today.unwrap_or_else(Date::today);
+
+        let mut upcoming: Vec<UpcomingBirthday> = self
+            .repository
+            .find_all()?
+            .into_iter()
+            .filter_map(|contact| {
+                let days_until = days_until_next_birthday(contact.birthday()?, &today);
+                (days_until <= request.days).then_some(UpcomingBirthday { contact, days_until })
+            })
+            .collect();
+
+        upcoming.sort_by(|a, b| {
+            a.days_until
+                .cmp(&b.days_until)
+                .then_with(|| a.contact.full_name().cmp(&b.contact.full_name()))
+        });
+
+        Ok(UpcomingBirthdaysResponse {
+            contacts: upcoming,
+            days: request.days,
+        })
+    }
+}
+
+/// Compute the number of days from `today` until the next occurrence of `birthday`.
+/// Returns 0 when the birthday is today. February 29 birthdays fall on
+/// February 28 in non-leap years.
+pub fn days_until_next_birthday(birthday: &Date, today: &Date) -> u32 {
+    let today = today.naive();
+    let occurrence_in = |year: i32| {
+        NaiveDate::from_ymd_opt(year, birthday.month(), birthday.day())
+            .or_else(|| NaiveDate::from_ymd_opt(year, birthday.month(), birthday.day() - 1))
+            .expect("only February 29 is missing in some years")
+    };
+
+    let mut next = occurrence_in(today.year());
+    if next < today {
+        next = occurrence_in(today.year() + 1);
+    }
+
+    (next - today).num_days() as u32
+}
+
+/// Request DTO for listing upcoming birthdays
+#[derive(Debug)]
+pub struct UpcomingBirthdaysRequest {
+    pub days: u32,
+    /// Reference date; defaults to the current date when `None`
+    pub today: Option<Date>,
+}
+
+/// A contact paired with the days remaining until their birthday
+#[derive(Debug)]
+pub struct UpcomingBirthday {
+    pub contact: Contact,
+    pub days_until: u32,
+}
+
+/// Response DTO for listing upcoming birthdays
+#[derive(Debug)]
+pub struct UpcomingBirthdaysResponse {
+    pub contacts: Vec<UpcomingBirthday>,
+    pub days: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> Date {
+        Date::parse(value).unwrap()
+    }
+
+    #[test]
+    fn counts_days_to_the_next_occurrence() {
+        assert_eq!(days_until_next_birthday(&date("1990-05-17"), &date("2024-05-17")), 0);
+        assert_eq!(days_until_next_birthday(&date("1990-05-17"), &date("2024-05-10")), 7);
+        assert_eq!(days_until_next_birthday(&date("1990-01-02"), &date("2024-12-31")), 2);
+    }
+
+    #[test]
+    fn leap_day_birthdays_fall_on_february_28_otherwise() {
+        assert_eq!(days_until_next_birthday(&date("2000-02-29"), &date("2023-02-27")), 1);
+        assert_eq!(days_until_next_birthday(&date("2000-02-29"), &date("2024-02-27")), 2);
+    }
+}
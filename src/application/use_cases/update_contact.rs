@@ -1,6 +1,9 @@
-use crate::domain::entities::Contact;
+use crate::application::validation::{ValidationErrors, Validator};
+use crate::domain::entities::{Contact, EmailDedupPolicy};
+use crate::domain::errors::{ApplicationError, DomainError};
 use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
-use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
+use crate::domain::value_objects::{AuditEntry, ContactId, Date, Email, PhoneNumber, Timestamp};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 /// Use case for updating an existing contact
@@ -15,76 +18,161 @@ impl UpdateContactUseCase {
     }
 
     /// Execute the update contact use case
-    pub fn execute(&self, request: UpdateContactRequest) -> Result<UpdateContactResponse, RepositoryError> {
-        // Check if contact exists
-        let mut contact = self.repository
-            .find_by_id(&request.contact_id)?
-            .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+    pub fn execute(&self, request: UpdateContactRequest) -> Result<UpdateContactResponse, ApplicationError> {
+        if request.dry_run {
+            let contact = self.repository
+                .find_by_id(&request.contact_id)?
+                .filter(|contact| !contact.is_deleted())
+                .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+            return Ok(UpdateContactResponse {
+                contact: Self::apply(&request, contact)?,
+                message: "Dry run: no changes saved".to_string(),
+            });
+        }
+
+        // The changes are applied inside the transaction, so if another writer saved the
+        // file in the meantime they are re-applied to its copy of the contact
+        let mut updated = None;
+        self.repository.transaction(&mut |contacts| {
+            // Trashed contacts have to be restored first
+            let contact = contacts
+                .get_mut(&request.contact_id)
+                .filter(|contact| !contact.is_deleted())
+                .ok_or_else(|| RepositoryError::ContactNotFound(request.contact_id.to_string()))?;
+            *contact = Self::apply(&request, contact.clone())?;
+            updated = Some(contact.clone());
+            Ok(())
+        })?;
+
+        Ok(UpdateContactResponse {
+            contact: updated.expect("a successful transaction runs the update"),
+            message: "Contact updated successfully".to_string(),
+        })
+    }
+}
+
+impl UpdateContactUseCase {
+    /// Apply the requested changes to a stored contact
+    fn apply(request: &UpdateContactRequest, mut contact: Contact) -> Result<Contact, RepositoryError> {
+        if let Some(expected) = request.expected_version {
+            if contact.version() != expected {
+                return Err(RepositoryError::VersionConflict {
+                    id: request.contact_id.to_string(),
+                    expected,
+                    actual: contact.version(),
+                });
+            }
+        }
+        let original = contact.clone();
+
+        // Collect every field problem so they're reported together
+        let mut errors = ValidationErrors::new();
 
         // Update fields if provided
-        if let Some(first_name) = request.first_name {
+        if let Some(first_name) = &request.first_name {
             if first_name.trim().is_empty() {
-                return Err(RepositoryError::ValidationError(
-                    "First name cannot be empty".to_string(),
-                ));
+                errors.push("First name cannot be empty");
             }
-            contact.set_first_name(first_name);
+            contact.set_first_name(first_name.clone());
         }
 
-        if let Some(last_name) = request.last_name {
+        if let Some(last_name) = &request.last_name {
             if last_name.trim().is_empty() {
-                return Err(RepositoryError::ValidationError(
-                    "Last name cannot be empty".to_string(),
-                ));
+                errors.push("Last name cannot be empty");
             }
-            contact.set_last_name(last_name);
+            contact.set_last_name(last_name.clone());
+        }
+
+        if let Some(notes) = &request.notes {
+            contact.set_notes(if notes.trim().is_empty() { None } else { Some(notes.clone()) });
+        }
+
+        if let Some(birthday) = request.birthday {
+            contact.set_birthday(birthday);
+        }
+
+        if let Some(photo) = &request.photo {
+            errors.check(contact.set_photo_path(photo.clone()));
         }
 
-        if let Some(notes) = request.notes {
-            contact.set_notes(if notes.trim().is_empty() { None } else { Some(notes) });
+        // Handle phone number updates; clearing happens first so the additions replace the old numbers
+        if request.clear_phone_numbers {
+            contact.clear_phone_numbers();
         }
 
-        // Handle phone number updates
-        for phone in request.add_phone_numbers {
-            contact.add_phone_number(phone);
+        for phone in &request.add_phone_numbers {
+            contact.add_phone_number(phone.clone());
         }
 
-        for phone in request.remove_phone_numbers {
-            contact.remove_phone_number(&phone);
+        for phone in &request.remove_phone_numbers {
+            contact.remove_phone_number(phone);
         }
 
         // Handle email updates
-        for email in request.add_emails {
-            contact.add_email(email);
+        if request.clear_emails {
+            contact.clear_emails();
         }
 
-        for email in request.remove_emails {
-            contact.remove_email(&email);
+        for email in &request.add_emails {
+            contact.add_email_with_policy(email.clone(), request.email_policy);
+        }
+
+        for email in &request.remove_emails {
+            contact.remove_email_with_policy(email, request.email_policy);
         }
 
         // Handle tag updates
-        for tag in request.add_tags {
-            contact.add_tag(tag);
+        if request.clear_tags {
+            contact.clear_tags();
+        }
+
+        for tag in &request.add_tags {
+            contact.add_tag(tag.clone());
+        }
+
+        for tag in &request.remove_tags {
+            contact.remove_tag(tag);
+        }
+
+        // Handle metadata updates; setting an existing key overwrites it
+        for (key, value) in &request.set_metadata {
+            if errors.check(Validator::validate_metadata_key(key)) {
+                contact.set_metadata(key.clone(), value.clone());
+            }
         }
 
-        for tag in request.remove_tags {
-            contact.remove_tag(&tag);
+        for key in &request.remove_metadata {
+            contact.remove_metadata(key);
+        }
+
+        // Generic `field=value` assignments are applied last, so they win over typed flags
+        for (field, value) in &request.set_fields {
+            errors.check(contact.set_field(field, value));
         }
 
         // Validate that contact still has at least one phone or email
         if contact.phone_numbers().is_empty() && contact.emails().is_empty() {
-            return Err(RepositoryError::ValidationError(
-                "Contact must have at least one phone number or email".to_string(),
-            ));
+            errors.push("Contact must have at least one phone number or email");
+        }
+        if let Err(DomainError::Validation(message)) = errors.into_result() {
+            return Err(RepositoryError::ValidationError(message));
         }
 
-        // Save updated contact
-        self.repository.update(contact.clone())?;
+        // Record an audit entry for every field that changed
+        for entry in Self::diff(&original, &contact, Timestamp::now()) {
+            contact.record_change(entry);
+        }
+        contact.increment_version();
+        Ok(contact)
+    }
 
-        Ok(UpdateContactResponse {
-            contact,
-            message: "Contact updated successfully".to_string(),
-        })
+    /// Compare two versions of a contact and describe each changed field
+    fn diff(before: &Contact, after: &Contact, timestamp: Timestamp) -> Vec<AuditEntry> {
+        before
+            .changes_to(after)
+            .into_iter()
+            .map(|change| AuditEntry::new(change.field.to_string(), change.old, change.new, timestamp))
+            .collect()
     }
 }
 
@@ -95,12 +183,32 @@ pub struct UpdateContactRequest {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub notes: Option<String>,
+    /// `Some(None)` clears the birthday
+    pub birthday: Option<Option<Date>>,
+    /// `Some(None)` clears the photo
+    pub photo: Option<Option<PathBuf>>,
+    /// Remove every stored phone number before `add_phone_numbers` are added
+    pub clear_phone_numbers: bool,
     pub add_phone_numbers: Vec<PhoneNumber>,
     pub remove_phone_numbers: Vec<PhoneNumber>,
+    /// Remove every stored email before `add_emails` are added
+    pub clear_emails: bool,
     pub add_emails: Vec<Email>,
     pub remove_emails: Vec<Email>,
+    /// Which added emails are dropped as repeats of stored ones
+    pub email_policy: EmailDedupPolicy,
+    /// Remove every stored tag before `add_tags` are added
+    pub clear_tags: bool,
     pub add_tags: Vec<String>,
     pub remove_tags: Vec<String>,
+    pub set_metadata: Vec<(String, String)>,
+    pub remove_metadata: Vec<String>,
+    /// `(field, value)` pairs applied with `Contact::set_field`
+    pub set_fields: Vec<(String, String)>,
+    /// Reject the update unless the stored contact is at this version
+    pub expected_version: Option<u64>,
+    /// Compute the updated contact without saving it
+    pub dry_run: bool,
 }
 
 /// Response DTO for updating a contact
@@ -109,3 +217,249 @@ pub struct UpdateContactResponse {
     pub contact: Contact,
     pub message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::FileContactRepository;
+    use std::thread;
+    use std::time::Duration;
+
+    fn repository_in(dir: &tempfile::TempDir) -> Arc<dyn ContactRepositorySync> {
+        Arc::new(FileContactRepository::new(
+            dir.path().join("contacts.json").to_string_lossy().into_owned(),
+        ))
+    }
+
+    fn add_tag(repository: &Arc<dyn ContactRepositorySync>, id: &ContactId, tag: &str) -> UpdateContactResponse {
+        UpdateContactUseCase::new(repository.clone())
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                add_tags: vec![tag.to_string()],
+                ..Default::default()
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn updates_from_two_writers_are_both_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = repository_in(&dir);
+        let second = repository_in(&dir);
+        let contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let id = contact.id().clone();
+        first.save(contact).unwrap();
+
+        add_tag(&first, &id, "first");
+        thread::sleep(Duration::from_millis(20));
+        add_tag(&second, &id, "second");
+        thread::sleep(Duration::from_millis(20));
+        let response = add_tag(&first, &id, "third");
+
+        assert_eq!(response.contact.tags(), ["first", "second", "third"].map(String::from));
+        assert_eq!(response.contact.version(), 3);
+        assert_eq!(repository_in(&dir).find_by_id(&id).unwrap().unwrap(), response.contact);
+    }
+
+    #[test]
+    fn each_update_records_the_changed_fields_in_the_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let id = contact.id().clone();
+        repository.save(contact).unwrap();
+        let use_case = UpdateContactUseCase::new(repository.clone());
+
+        use_case
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                first_name: Some("Anna".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        use_case
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                first_name: Some("Anne".to_string()),
+                notes: Some("met at the conference".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let stored = repository.find_by_id(&id).unwrap().unwrap();
+        let history: Vec<(&str, Option<&str>, Option<&str>)> = stored
+            .history()
+            .iter()
+            .map(|entry| (entry.field(), entry.old_value(), entry.new_value()))
+            .collect();
+        assert_eq!(
+            history,
+            [
+                ("first_name", Some("Ann"), Some("Anna")),
+                ("first_name", Some("Anna"), Some("Anne")),
+                ("notes", None, Some("met at the conference")),
+            ]
+        );
+
+        // The old values live only in the history, which search doesn't look at
+        assert!(!stored.matches_search("Anna"));
+    }
+
+    #[test]
+    fn stale_expected_version_is_a_conflict_and_dry_run_saves_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let id = contact.id().clone();
+        repository.save(contact).unwrap();
+        add_tag(&repository, &id, "work");
+
+        let use_case = UpdateContactUseCase::new(repository.clone());
+        let stale = use_case.execute(UpdateContactRequest {
+            contact_id: id.clone(),
+            first_name: Some("Anna".to_string()),
+            expected_version: Some(0),
+            ..Default::default()
+        });
+        assert!(stale.unwrap_err().is_conflict());
+
+        let preview = use_case
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                first_name: Some("Anna".to_string()),
+                dry_run: true,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(preview.contact.first_name(), "Anna");
+        assert_eq!(repository.find_by_id(&id).unwrap().unwrap().first_name(), "Ann");
+    }
+
+    fn save_ann(repository: &Arc<dyn ContactRepositorySync>) -> ContactId {
+        let contact = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        );
+        let id = contact.id().clone();
+        repository.save(contact).unwrap();
+        id
+    }
+
+    #[test]
+    fn matching_expected_version_updates_and_bumps_the_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let id = save_ann(&repository);
+        let use_case = UpdateContactUseCase::new(repository.clone());
+        let rename = |name: &str, expected_version: u64| {
+            use_case.execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                first_name: Some(name.to_string()),
+                expected_version: Some(expected_version),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(rename("Anna", 0).unwrap().contact.version(), 1);
+        assert_eq!(rename("Anne", 1).unwrap().contact.version(), 2);
+        assert!(rename("Annie", 1).unwrap_err().is_conflict(), "a writer that read version 1 lost the race");
+
+        let stored = repository.find_by_id(&id).unwrap().unwrap();
+        assert_eq!((stored.first_name(), stored.version()), ("Anne", 2));
+    }
+
+    #[test]
+    fn cleared_collections_are_replaced_by_the_added_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let id = save_ann(&repository);
+        add_tag(&repository, &id, "old");
+
+        let response = UpdateContactUseCase::new(repository.clone())
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                clear_phone_numbers: true,
+                add_phone_numbers: vec![PhoneNumber::new("5550000002".to_string()).unwrap()],
+                clear_emails: true,
+                add_emails: vec![Email::new("ann@example.com".to_string()).unwrap()],
+                clear_tags: true,
+                add_tags: vec!["new".to_string()],
+                ..Default::default()
+            })
+            .unwrap();
+
+        let stored = repository.find_by_id(&id).unwrap().unwrap();
+        assert_eq!(stored, response.contact);
+        let phones: Vec<&str> = stored.phone_numbers().iter().map(|p| p.value()).collect();
+        assert_eq!(phones, ["5550000002"]);
+        assert_eq!(stored.emails()[0].value(), "ann@example.com");
+        assert_eq!(stored.tags(), ["new"]);
+    }
+
+    #[test]
+    fn clearing_every_contact_method_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let id = save_ann(&repository);
+
+        let error = UpdateContactUseCase::new(repository.clone())
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                clear_phone_numbers: true,
+                clear_emails: true,
+                ..Default::default()
+            })
+            .unwrap_err();
+
+        assert!(error.to_string().contains("at least one phone number or email"), "{}", error);
+        assert_eq!(repository.find_by_id(&id).unwrap().unwrap().phone_numbers().len(), 1);
+    }
+
+    #[test]
+    fn every_empty_name_is_reported_in_one_validation_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let id = save_ann(&repository);
+        let use_case = UpdateContactUseCase::new(repository.clone());
+
+        let error = use_case
+            .execute(UpdateContactRequest {
+                contact_id: id.clone(),
+                first_name: Some(" ".to_string()),
+                last_name: Some(String::new()),
+                ..Default::default()
+            })
+            .unwrap_err();
+
+        assert!(error.is_validation(), "{}", error);
+        let message = error.to_string();
+        assert!(message.contains("First name cannot be empty"), "{}", message);
+        assert!(message.contains("Last name cannot be empty"), "{}", message);
+        assert_eq!(repository.find_by_id(&id).unwrap().unwrap().full_name(), "Ann Tester");
+
+        let missing = use_case
+            .execute(UpdateContactRequest {
+                first_name: Some(" ".to_string()),
+                ..Default::default()
+            })
+            .unwrap_err();
+        assert!(missing.is_not_found() && !missing.is_validation(), "{}", missing);
+    }
+}
@@ -4,6 +4,10 @@ use crate::domain::value_objects::{Email, PhoneNumber};
 /// Validation utilities for application layer
 pub struct Validator;
 
+/// Largest page size that's comfortable to read and fast to render; also the
+/// default page size cap
+pub const RECOMMENDED_MAX_PAGE_SIZE: usize = 100;
+
 impl Validator {
     /// Validate that a string is not empty or whitespace-only
     pub fn validate_non_empty_string(value: &str, field_name: &str) -> Result<(), DomainError> {
@@ -17,8 +21,9 @@ impl Validator {
     }
 
     /// Validate that a string meets minimum length requirements
+    /// Lengths count characters, not bytes, so accented and CJK names get the same limits
     pub fn validate_min_length(value: &str, min_length: usize, field_name: &str) -> Result<(), DomainError> {
-        if value.trim().len() < min_length {
+        if value.trim().chars().count() < min_length {
             return Err(DomainError::Validation(format!(
                 "{} must be at least {} characters long",
                 field_name, min_length
@@ -27,9 +32,9 @@ impl Validator {
         Ok(())
     }
 
-    /// Validate that a string doesn't exceed maximum length
+    /// Validate that a string doesn't exceed maximum length, in characters
     pub fn validate_max_length(value: &str, max_length: usize, field_name: &str) -> Result<(), DomainError> {
-        if value.len() > max_length {
+        if value.chars().count() > max_length {
             return Err(DomainError::Validation(format!(
                 "{} cannot exceed {} characters",
                 field_name, max_length
@@ -111,20 +116,98 @@ impl Validator {
         Ok(())
     }
 
-    /// Validate pagination parameters
-    pub fn validate_pagination(_page: usize, page_size: usize) -> Result<(), DomainError> {
+    /// Validate pagination parameters against the given page size cap
+    pub fn validate_pagination(_page: usize, page_size: usize, max_page_size: usize) -> Result<(), DomainError> {
         if page_size == 0 {
             return Err(DomainError::Validation(
                 "Page size must be greater than 0".to_string(),
             ));
         }
         
-        if page_size > 100 {
-            return Err(DomainError::Validation(
-                "Page size cannot exceed 100".to_string(),
-            ));
+        if page_size > max_page_size {
+            return Err(DomainError::Validation(format!(
+                "Page size cannot exceed {}",
+                max_page_size
+            )));
         }
         
         Ok(())
     }
+
+    /// Validate a metadata key; keys can't contain `=` so they round-trip through `--meta key=value`
+    pub fn validate_metadata_key(key: &str) -> Result<(), DomainError> {
+        if key.trim().is_empty() || key.contains('=') {
+            return Err(DomainError::Validation(format!("Invalid metadata key '{}'", key)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects validation failures so they can be reported together
+#[derive(Debug, Default)]
+pub struct ValidationErrors {
+    messages: Vec<String>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a problem
+    pub fn push(&mut self, message: impl Into<String>) {
+        self.messages.push(message.into());
+    }
+
+    /// Record the error from a validation result, returning whether it passed
+    pub fn check(&mut self, result: Result<(), DomainError>) -> bool {
+        match result {
+            Ok(()) => true,
+            Err(DomainError::Validation(message)) => {
+                self.push(message);
+                false
+            }
+            Err(e) => {
+                self.push(e.to_string());
+                false
+            }
+        }
+    }
+
+    /// A single validation error listing every recorded problem, if there were any
+    pub fn into_result(self) -> Result<(), DomainError> {
+        if self.messages.is_empty() {
+            Ok(())
+        } else {
+            Err(DomainError::Validation(self.messages.join("; ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_size_may_reach_the_cap_but_not_pass_it() {
+        assert!(Validator::validate_pagination(0, RECOMMENDED_MAX_PAGE_SIZE, RECOMMENDED_MAX_PAGE_SIZE).is_ok());
+        assert!(Validator::validate_pagination(0, RECOMMENDED_MAX_PAGE_SIZE + 1, RECOMMENDED_MAX_PAGE_SIZE).is_err());
+
+        assert!(Validator::validate_pagination(0, 250, 250).is_ok());
+        assert!(Validator::validate_pagination(0, 251, 250).is_err());
+        assert!(Validator::validate_pagination(0, 0, 250).is_err());
+    }
+
+    #[test]
+    fn name_length_counts_accented_and_cjk_characters() {
+        for letter in ["é", "山", "ő"] {
+            assert!(Validator::validate_name_component(&letter.repeat(100), "First name").is_ok(), "{}", letter);
+            assert!(Validator::validate_name_component(&letter.repeat(101), "First name").is_err(), "{}", letter);
+        }
+
+        assert!(Validator::validate_name_component("李", "Last name").is_ok());
+        assert!(Validator::validate_min_length(" 李 ", 1, "Last name").is_ok());
+        assert!(Validator::validate_min_length("李", 2, "Last name").is_err());
+    }
 }
@@ -1,6 +1,104 @@
-use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
+use crate::domain::errors::DomainError;
+use crate::domain::value_objects::{AuditEntry, ContactId, Date, Email, PhoneNumber, Timestamp};
+use chrono::DateTime;
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Maximum length of a single tag, in characters
+pub const MAX_TAG_LENGTH: usize = 50;
+
+/// Characters of context kept either side of a search hit by `Contact::match_contexts`
+pub const MATCH_CONTEXT_CHARS: usize = 30;
+
+/// Field names accepted by `Contact::set_field`
+pub const SETTABLE_FIELDS: &[&str] = &["first_name", "last_name", "notes", "birthday", "photo"];
+
+/// Order in which first and last names are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameFormat {
+    /// "First Last"
+    #[default]
+    FirstLast,
+    /// "Last, First", for alphabetizing by surname
+    LastFirst,
+}
+
+impl std::str::FromStr for NameFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first-last" | "firstlast" => Ok(NameFormat::FirstLast),
+            "last-first" | "lastfirst" => Ok(NameFormat::LastFirst),
+            _ => Err(format!("Invalid name format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for NameFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NameFormat::FirstLast => write!(f, "first-last"),
+            NameFormat::LastFirst => write!(f, "last-first"),
+        }
+    }
+}
+
+/// How `Contact::add_email_with_policy` decides an address is already on the contact
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmailDedupPolicy {
+    /// Keep every address, even exact repeats
+    AllowAll,
+    /// Skip addresses identical to a stored one
+    #[default]
+    DedupExact,
+    /// Skip addresses sharing a stored one's `Email::canonical` form (e.g. Gmail dot and `+tag` variants)
+    DedupCanonical,
+}
+
+impl EmailDedupPolicy {
+    /// Whether `candidate` counts as a duplicate of `existing`
+    pub fn is_duplicate(&self, existing: &Email, candidate: &Email) -> bool {
+        match self {
+            EmailDedupPolicy::AllowAll => false,
+            EmailDedupPolicy::DedupExact => existing == candidate,
+            EmailDedupPolicy::DedupCanonical => existing.canonical() == candidate.canonical(),
+        }
+    }
+}
+
+impl std::str::FromStr for EmailDedupPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "allow-all" | "all" => Ok(EmailDedupPolicy::AllowAll),
+            "exact" => Ok(EmailDedupPolicy::DedupExact),
+            "canonical" => Ok(EmailDedupPolicy::DedupCanonical),
+            _ => Err(format!("Invalid email dedup policy: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for EmailDedupPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmailDedupPolicy::AllowAll => write!(f, "allow-all"),
+            EmailDedupPolicy::DedupExact => write!(f, "exact"),
+            EmailDedupPolicy::DedupCanonical => write!(f, "canonical"),
+        }
+    }
+}
+
+/// A single field that differs between two versions of a contact
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
 
 /// Contact entity representing a person in the phonebook
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -13,26 +111,75 @@ pub struct Contact {
     notes: Option<String>,
     tags: Vec<String>,
     metadata: HashMap<String, String>,
+    #[serde(default)]
+    birthday: Option<Date>,
+    #[serde(default)]
+    history: Vec<AuditEntry>,
+    #[serde(default)]
+    deleted_at: Option<Timestamp>,
+    #[serde(default)]
+    photo_path: Option<PathBuf>,
+    #[serde(default)]
+    created_at: Option<Timestamp>,
+    #[serde(default)]
+    updated_at: Option<Timestamp>,
+    /// Number of updates saved so far; 0 for contacts stored before this was tracked
+    #[serde(default)]
+    version: u64,
 }
 
 impl Contact {
-    /// Create a new contact
+    /// Create a new contact, dropping phone numbers and emails given more than once
     pub fn new(
         first_name: String,
         last_name: String,
         phone_numbers: Vec<PhoneNumber>,
         emails: Vec<Email>,
     ) -> Self {
-        Self {
-            id: ContactId::new(),
+        Self::new_with_id(ContactId::new(), first_name, last_name, phone_numbers, emails)
+    }
+
+    /// Create a new contact under a given ID, e.g. one carried over by an import
+    /// Unlike `with_id`, this deduplicates like `new` and stamps the creation time
+    pub fn new_with_id(
+        id: ContactId,
+        first_name: String,
+        last_name: String,
+        phone_numbers: Vec<PhoneNumber>,
+        emails: Vec<Email>,
+    ) -> Self {
+        let now = Timestamp::now();
+        let mut contact = Self {
+            id,
             first_name,
             last_name,
-            phone_numbers,
-            emails,
+            phone_numbers: Vec::new(),
+            emails: Vec::new(),
             notes: None,
             tags: Vec::new(),
             metadata: HashMap::new(),
+            birthday: None,
+            history: Vec::new(),
+            deleted_at: None,
+            photo_path: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+            version: 0,
+        };
+
+        for phone in phone_numbers {
+            contact.add_phone_number(phone);
+        }
+        for email in emails {
+            contact.add_email(email);
         }
+
+        contact
+    }
+
+    /// Start building a contact field by field; see `ContactBuilder`
+    pub fn builder() -> ContactBuilder {
+        ContactBuilder::default()
     }
 
     /// Create a contact with existing ID (for loading from storage)
@@ -52,6 +199,13 @@ impl Contact {
             notes: None,
             tags: Vec::new(),
             metadata: HashMap::new(),
+            birthday: None,
+            history: Vec::new(),
+            deleted_at: None,
+            photo_path: None,
+            created_at: None,
+            updated_at: None,
+            version: 0,
         }
     }
 
@@ -72,6 +226,20 @@ impl Contact {
         format!("{} {}", self.first_name, self.last_name)
     }
 
+    /// Name in the requested order; a missing part is left out rather than
+    /// leaving a stray comma or space
+    pub fn display_name(&self, format: NameFormat) -> String {
+        let first = self.first_name.trim();
+        let last = self.last_name.trim();
+
+        match (first.is_empty(), last.is_empty(), format) {
+            (true, _, _) => last.to_string(),
+            (_, true, _) => first.to_string(),
+            (_, _, NameFormat::FirstLast) => format!("{} {}", first, last),
+            (_, _, NameFormat::LastFirst) => format!("{}, {}", last, first),
+        }
+    }
+
     pub fn phone_numbers(&self) -> &[PhoneNumber] {
         &self.phone_numbers
     }
@@ -92,6 +260,44 @@ impl Contact {
         &self.metadata
     }
 
+    pub fn birthday(&self) -> Option<&Date> {
+        self.birthday.as_ref()
+    }
+
+    /// Absolute path of the contact's photo
+    pub fn photo_path(&self) -> Option<&Path> {
+        self.photo_path.as_deref()
+    }
+
+    /// When the contact was moved to the trash, if it was
+    pub fn deleted_at(&self) -> Option<&Timestamp> {
+        self.deleted_at.as_ref()
+    }
+
+    /// When the contact was added; unknown for contacts stored before this was tracked
+    pub fn created_at(&self) -> Option<&Timestamp> {
+        self.created_at.as_ref()
+    }
+
+    /// When the contact last changed (its creation time until it's first updated)
+    pub fn updated_at(&self) -> Option<&Timestamp> {
+        self.updated_at.as_ref()
+    }
+
+    /// How many updates have been saved, for detecting edits made from a stale copy
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// Field changes recorded by updates, oldest first
+    pub fn history(&self) -> &[AuditEntry] {
+        &self.history
+    }
+
     // Setters
     pub fn set_first_name(&mut self, first_name: String) {
         self.first_name = first_name;
@@ -105,24 +311,70 @@ impl Contact {
         self.notes = notes;
     }
 
+    pub fn set_birthday(&mut self, birthday: Option<Date>) {
+        self.birthday = birthday;
+    }
+
+    /// Set the photo path; the file must exist and is stored as an absolute path
+    pub fn set_photo_path(&mut self, photo_path: Option<PathBuf>) -> Result<(), DomainError> {
+        self.photo_path = match photo_path {
+            Some(path) => Some(path.canonicalize().map_err(|_| {
+                DomainError::Validation(format!("Photo file does not exist: {}", path.display()))
+            })?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Add a phone number unless the same number is already stored in another format
     pub fn add_phone_number(&mut self, phone: PhoneNumber) {
-        if !self.phone_numbers.contains(&phone) {
+        let normalized = phone.normalized();
+        if !self.phone_numbers.iter().any(|p| p.normalized() == normalized) {
             self.phone_numbers.push(phone);
         }
     }
 
     pub fn remove_phone_number(&mut self, phone: &PhoneNumber) {
-        self.phone_numbers.retain(|p| p != phone);
+        let normalized = phone.normalized();
+        self.phone_numbers.retain(|p| p.normalized() != normalized);
+    }
+
+    /// Remove every phone number
+    pub fn clear_phone_numbers(&mut self) {
+        self.phone_numbers.clear();
     }
 
+    /// Add an email unless the same address is already stored
     pub fn add_email(&mut self, email: Email) {
-        if !self.emails.contains(&email) {
+        self.add_email_with_policy(email, EmailDedupPolicy::default());
+    }
+
+    /// Add an email unless `policy` considers it a duplicate of a stored one
+    pub fn add_email_with_policy(&mut self, email: Email, policy: EmailDedupPolicy) {
+        if !self.emails.iter().any(|e| policy.is_duplicate(e, &email)) {
             self.emails.push(email);
         }
     }
 
     pub fn remove_email(&mut self, email: &Email) {
-        self.emails.retain(|e| e != email);
+        self.remove_email_with_policy(email, EmailDedupPolicy::default());
+    }
+
+    /// Remove an email, matching stored addresses the same way `policy` spots duplicates:
+    /// only `DedupCanonical` also removes the address's dot and `+tag` variants
+    pub fn remove_email_with_policy(&mut self, email: &Email, policy: EmailDedupPolicy) {
+        match policy {
+            EmailDedupPolicy::DedupCanonical => {
+                let canonical = email.canonical();
+                self.emails.retain(|e| e.canonical() != canonical);
+            }
+            EmailDedupPolicy::AllowAll | EmailDedupPolicy::DedupExact => self.emails.retain(|e| e != email),
+        }
+    }
+
+    /// Remove every email address
+    pub fn clear_emails(&mut self) {
+        self.emails.clear();
     }
 
     pub fn add_tag(&mut self, tag: String) {
@@ -135,6 +387,65 @@ impl Contact {
         self.tags.retain(|t| t != tag);
     }
 
+    /// Remove every tag
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    /// Rename a tag in place, dropping it instead if `to` is already present.
+    /// Returns whether the contact carried the `from` tag.
+    pub fn rename_tag(&mut self, from: &str, to: &str) -> bool {
+        let Some(index) = self.tags.iter().position(|t| t == from) else {
+            return false;
+        };
+
+        if self.tags.iter().any(|t| t == to) {
+            self.tags.remove(index);
+        } else {
+            self.tags[index] = to.to_string();
+        }
+        true
+    }
+
+    /// Set a field by name from its text form, as given to `update --set field=value`
+    /// Names may use `-` for `_`. An empty value clears notes, birthday and photo;
+    /// names can't be emptied.
+    pub fn set_field(&mut self, name: &str, value: &str) -> Result<(), DomainError> {
+        let optional = |value: &str| (!value.trim().is_empty()).then(|| value.to_string());
+
+        match name.trim().replace('-', "_").as_str() {
+            "first_name" | "last_name" if value.trim().is_empty() => {
+                Err(DomainError::Validation(format!("{} cannot be empty", name.trim())))
+            }
+            "first_name" => {
+                self.set_first_name(value.to_string());
+                Ok(())
+            }
+            "last_name" => {
+                self.set_last_name(value.to_string());
+                Ok(())
+            }
+            "notes" => {
+                self.set_notes(optional(value));
+                Ok(())
+            }
+            "birthday" => {
+                let birthday = optional(value)
+                    .map(|value| Date::parse(&value))
+                    .transpose()
+                    .map_err(|e| DomainError::Validation(format!("Invalid birthday '{}': {}", value, e)))?;
+                self.set_birthday(birthday);
+                Ok(())
+            }
+            "photo" => self.set_photo_path(optional(value).map(PathBuf::from)),
+            _ => Err(DomainError::Validation(format!(
+                "Unknown field '{}' (expected one of: {})",
+                name,
+                SETTABLE_FIELDS.join(", ")
+            ))),
+        }
+    }
+
     pub fn set_metadata(&mut self, key: String, value: String) {
         self.metadata.insert(key, value);
     }
@@ -143,15 +454,798 @@ impl Contact {
         self.metadata.remove(key);
     }
 
+    /// Fold another contact's details into this one
+    /// Phones, emails and tags are unioned using the `add_*` dedup rules, and
+    /// metadata keys missing here are copied over. Names and existing values
+    /// are kept; notes are taken from `other` only when this contact has none.
+    pub fn merge_from(&mut self, other: &Contact) {
+        for phone in other.phone_numbers() {
+            self.add_phone_number(phone.clone());
+        }
+
+        for email in other.emails() {
+            self.add_email(email.clone());
+        }
+
+        for tag in other.tags() {
+            self.add_tag(tag.clone());
+        }
+
+        for (key, value) in other.metadata() {
+            self.metadata.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+
+        if self.notes.as_deref().is_none_or(|notes| notes.trim().is_empty()) {
+            if let Some(notes) = other.notes() {
+                self.notes = Some(notes.to_string());
+            }
+        }
+    }
+
+    /// Copy of the contact with personal data scrubbed, for sharing realistic test data
+    /// Names become `Contact <first 8 characters of the ID>`, phone numbers keep only
+    /// their last two digits, email local parts are masked, and notes, metadata, the
+    /// photo and the change history are dropped. The ID, tags, timestamps, the number
+    /// of phones and emails, and whether a birthday is set are preserved.
+    pub fn anonymize(&self) -> Contact {
+        let id = self.id.to_string();
+        Contact {
+            id: self.id.clone(),
+            first_name: "Contact".to_string(),
+            last_name: id.chars().take(8).collect(),
+            phone_numbers: self.phone_numbers.iter().map(PhoneNumber::masked).collect(),
+            emails: self.emails.iter().map(Email::masked).collect(),
+            notes: None,
+            tags: self.tags.clone(),
+            metadata: HashMap::new(),
+            birthday: self.birthday.map(|_| DateTime::UNIX_EPOCH.date_naive().into()),
+            history: Vec::new(),
+            deleted_at: self.deleted_at,
+            photo_path: None,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            version: self.version,
+        }
+    }
+
+    /// Move the contact to the trash
+    pub fn mark_deleted(&mut self, at: Timestamp) {
+        self.deleted_at = Some(at);
+    }
+
+    /// Take the contact back out of the trash
+    pub fn restore(&mut self) {
+        self.deleted_at = None;
+    }
+
+    /// Mark the contact as updated now without changing anything else
+    pub fn touch(&mut self) {
+        self.updated_at = Some(Timestamp::now());
+    }
+
+    /// Count one more saved update
+    pub fn increment_version(&mut self) {
+        self.version += 1;
+    }
+
+    /// Take the place of `stored`, the contact with this ID that this one replaces
+    /// Its creation time, change history and version are kept, and the replacement counts as one more update
+    pub fn replace(&mut self, stored: &Contact) {
+        self.created_at = stored.created_at;
+        self.history = stored.history.clone();
+        self.version = stored.version + 1;
+    }
+
+    /// Append an entry to the contact's change history, bumping `updated_at`
+    pub fn record_change(&mut self, entry: AuditEntry) {
+        let at = *entry.timestamp();
+        if self.updated_at.is_none_or(|updated| updated < at) {
+            self.updated_at = Some(at);
+        }
+        self.history.push(entry);
+    }
+
+    /// Check the contact's invariants: non-empty names, at least one
+    /// contact method, and tags that are non-empty and not too long
+    pub fn validate(&self) -> Result<(), DomainError> {
+        if self.first_name.trim().is_empty() {
+            return Err(DomainError::Validation("First name cannot be empty".to_string()));
+        }
+
+        if self.last_name.trim().is_empty() {
+            return Err(DomainError::Validation("Last name cannot be empty".to_string()));
+        }
+
+        if self.phone_numbers.is_empty() && self.emails.is_empty() {
+            return Err(DomainError::BusinessRule(
+                "At least one phone number or email address is required".to_string(),
+            ));
+        }
+
+        for tag in &self.tags {
+            if tag.trim().is_empty() {
+                return Err(DomainError::Validation("Tags cannot be empty".to_string()));
+            }
+
+            if tag.chars().count() > MAX_TAG_LENGTH {
+                return Err(DomainError::Validation(format!(
+                    "Tag '{}' cannot exceed {} characters",
+                    tag, MAX_TAG_LENGTH
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Describe each user-visible field that differs from `other`
+    /// IDs, history and trash state are not compared
+    pub fn changes_to(&self, other: &Contact) -> Vec<FieldChange> {
+        fn joined<T: ToString>(items: &[T]) -> Option<String> {
+            if items.is_empty() {
+                None
+            } else {
+                Some(items.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(", "))
+            }
+        }
+
+        fn metadata(map: &HashMap<String, String>) -> Option<String> {
+            let mut entries: Vec<String> = map.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+            entries.sort();
+            joined(&entries)
+        }
+
+        let fields = [
+            ("first_name", Some(self.first_name.clone()), Some(other.first_name.clone())),
+            ("last_name", Some(self.last_name.clone()), Some(other.last_name.clone())),
+            ("notes", self.notes.clone(), other.notes.clone()),
+            ("birthday", self.birthday.map(|d| d.to_string()), other.birthday.map(|d| d.to_string())),
+            (
+                "photo_path",
+                self.photo_path.as_ref().map(|p| p.display().to_string()),
+                other.photo_path.as_ref().map(|p| p.display().to_string()),
+            ),
+            ("phone_numbers", joined(&self.phone_numbers), joined(&other.phone_numbers)),
+            ("emails", joined(&self.emails), joined(&other.emails)),
+            ("tags", joined(&self.tags), joined(&other.tags)),
+            ("metadata", metadata(&self.metadata), metadata(&other.metadata)),
+        ];
+
+        fields
+            .into_iter()
+            .filter(|(_, old, new)| old != new)
+            .map(|(field, old, new)| FieldChange { field, old, new })
+            .collect()
+    }
+
+    /// Whether both contacts describe the same person, whatever their IDs
+    /// Names are compared trimmed and case-insensitively, phones by `normalized` form and
+    /// emails by `canonical` form, each as a set; IDs, timestamps, notes, tags,
+    /// metadata and history are ignored
+    pub fn same_person(&self, other: &Contact) -> bool {
+        fn same_name(a: &str, b: &str) -> bool {
+            a.trim().to_lowercase() == b.trim().to_lowercase()
+        }
+
+        let phones = |contact: &Contact| -> HashSet<String> {
+            contact.phone_numbers.iter().map(PhoneNumber::normalized).collect()
+        };
+        let emails = |contact: &Contact| -> HashSet<String> { contact.emails.iter().map(Email::canonical).collect() };
+
+        same_name(&self.first_name, &other.first_name)
+            && same_name(&self.last_name, &other.last_name)
+            && phones(self) == phones(other)
+            && emails(self) == emails(other)
+    }
+
     /// Check if contact matches search query
     pub fn matches_search(&self, query: &str) -> bool {
+        self.matches_search_with(query, true)
+    }
+
+    /// Like `matches_search`; `include_metadata` controls whether metadata keys and values are scanned
+    pub fn matches_search_with(&self, query: &str, include_metadata: bool) -> bool {
         let query_lower = query.to_lowercase();
         
         self.first_name.to_lowercase().contains(&query_lower)
             || self.last_name.to_lowercase().contains(&query_lower)
             || self.phone_numbers.iter().any(|p| p.value().contains(&query_lower))
             || self.emails.iter().any(|e| e.value().contains(&query_lower))
-            || self.notes.as_ref().map_or(false, |n| n.to_lowercase().contains(&query_lower))
+            || self.notes.as_ref().is_some_and(|n| n.to_lowercase().contains(&query_lower))
             || self.tags.iter().any(|t| t.to_lowercase().contains(&query_lower))
+            || (include_metadata
+                && self.metadata.iter().any(|(key, value)| {
+                    key.to_lowercase().contains(&query_lower) || value.to_lowercase().contains(&query_lower)
+                }))
+    }
+
+    /// Describe where a plain-text search query hits, as `(field, snippet)` pairs
+    /// Snippets keep up to `MATCH_CONTEXT_CHARS` characters either side of the
+    /// first hit in each value, marking cut-off text with `…`
+    pub fn match_contexts(&self, query: &str) -> Vec<(String, String)> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+        // Matching case-insensitively on the original text gives offsets that are valid in it;
+        // offsets found in a lowercased copy can point elsewhere, or inside a character
+        let Ok(pattern) = RegexBuilder::new(&regex::escape(query)).case_insensitive(true).build() else {
+            return Vec::new();
+        };
+
+        let mut values: Vec<(&str, &str)> = vec![("first name", &self.first_name), ("last name", &self.last_name)];
+        values.extend(self.phone_numbers.iter().map(|p| ("phone", p.value())));
+        values.extend(self.emails.iter().map(|e| ("email", e.value())));
+        if let Some(notes) = &self.notes {
+            values.push(("notes", notes));
+        }
+        values.extend(self.tags.iter().map(|t| ("tag", t.as_str())));
+
+        // Metadata hits are labelled with their key, in key order
+        let mut metadata: Vec<(&String, &String)> = self.metadata.iter().collect();
+        metadata.sort();
+        let metadata = metadata.into_iter().filter_map(|(key, value)| {
+            Self::snippet(&format!("{}={}", key, value), &pattern).map(|snippet| (format!("metadata {}", key), snippet))
+        });
+
+        values
+            .into_iter()
+            .filter_map(|(field, value)| Self::snippet(value, &pattern).map(|snippet| (field.to_string(), snippet)))
+            .chain(metadata)
+            .collect()
+    }
+
+    /// Cut a window of text around the first match of `pattern`
+    fn snippet(text: &str, pattern: &Regex) -> Option<String> {
+        let hit = pattern.find(text)?;
+        let (start, end) = (hit.start(), hit.end());
+
+        let before = text[..start].char_indices().rev().nth(MATCH_CONTEXT_CHARS - 1).map_or(0, |(i, _)| i);
+        let after = text[end..].char_indices().nth(MATCH_CONTEXT_CHARS).map_or(text.len(), |(i, _)| end + i);
+
+        let mut snippet = String::new();
+        if before > 0 {
+            snippet.push('…');
+        }
+        snippet.push_str(&text[before..after]);
+        if after < text.len() {
+            snippet.push('…');
+        }
+        Some(snippet.replace('\n', " "))
+    }
+
+    /// Check if contact matches a regular expression
+    pub fn matches_regex(&self, regex: &Regex) -> bool {
+        self.matches_regex_with(regex, true)
+    }
+
+    /// Like `matches_regex`; `include_metadata` controls whether metadata keys and values are tried
+    pub fn matches_regex_with(&self, regex: &Regex, include_metadata: bool) -> bool {
+        regex.is_match(&self.first_name)
+            || regex.is_match(&self.last_name)
+            || regex.is_match(&self.full_name())
+            || self.phone_numbers.iter().any(|p| regex.is_match(p.value()))
+            || self.emails.iter().any(|e| regex.is_match(e.value()))
+            || self.notes.as_ref().is_some_and(|n| regex.is_match(n))
+            || self.tags.iter().any(|t| regex.is_match(t))
+            || (include_metadata && self.metadata.iter().any(|(key, value)| regex.is_match(key) || regex.is_match(value)))
+    }
+}
+
+/// Fluent builder for contacts, e.g.
+/// `Contact::builder().first_name("Ada").last_name("Lovelace").phone(phone).build()`
+/// The contact is validated when built
+#[derive(Debug, Default)]
+pub struct ContactBuilder {
+    first_name: String,
+    last_name: String,
+    phone_numbers: Vec<PhoneNumber>,
+    emails: Vec<Email>,
+    notes: Option<String>,
+    tags: Vec<String>,
+    metadata: Vec<(String, String)>,
+}
+
+impl ContactBuilder {
+    pub fn first_name(mut self, first_name: impl Into<String>) -> Self {
+        self.first_name = first_name.into();
+        self
+    }
+
+    pub fn last_name(mut self, last_name: impl Into<String>) -> Self {
+        self.last_name = last_name.into();
+        self
+    }
+
+    pub fn phone(mut self, phone: PhoneNumber) -> Self {
+        self.phone_numbers.push(phone);
+        self
+    }
+
+    pub fn email(mut self, email: Email) -> Self {
+        self.emails.push(email);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    pub fn note(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Build the contact, failing if it doesn't pass `Contact::validate`
+    pub fn build(self) -> Result<Contact, DomainError> {
+        let mut contact = Contact::new(self.first_name, self.last_name, self.phone_numbers, self.emails);
+        contact.set_notes(self.notes);
+
+        for tag in self.tags {
+            contact.add_tag(tag);
+        }
+        for (key, value) in self.metadata {
+            contact.set_metadata(key, value);
+        }
+
+        contact.validate()?;
+        Ok(contact)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_contact() -> Contact {
+        Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        )
+    }
+
+    fn is_invalid(contact: &Contact) -> bool {
+        matches!(
+            contact.validate(),
+            Err(DomainError::Validation(_) | DomainError::BusinessRule(_))
+        )
+    }
+
+    #[test]
+    fn valid_contact_passes() {
+        let mut contact = valid_contact();
+        contact.add_tag("x".repeat(MAX_TAG_LENGTH));
+        assert!(contact.validate().is_ok());
+    }
+
+    #[test]
+    fn names_must_not_be_blank() {
+        let mut contact = valid_contact();
+        contact.set_first_name("  ".to_string());
+        assert!(is_invalid(&contact));
+
+        let mut contact = valid_contact();
+        contact.set_last_name(String::new());
+        assert!(is_invalid(&contact));
+    }
+
+    #[test]
+    fn a_phone_number_or_email_is_required() {
+        let mut contact = valid_contact();
+        contact.clear_phone_numbers();
+        assert!(is_invalid(&contact));
+
+        contact.add_email(Email::new("ann@example.com".to_string()).unwrap());
+        assert!(contact.validate().is_ok());
+    }
+
+    #[test]
+    fn the_same_number_in_three_formats_is_stored_once() {
+        let mut contact = valid_contact();
+        contact.clear_phone_numbers();
+        for phone in ["555-123-4567", "(555) 123-4567", "+1 555 123 4567"] {
+            contact.add_phone_number(PhoneNumber::new(phone.to_string()).unwrap());
+        }
+
+        assert_eq!(contact.phone_numbers().len(), 1);
+        assert_eq!(contact.phone_numbers()[0].normalized(), "+15551234567");
+    }
+
+    #[test]
+    fn emails_differing_only_in_case_are_stored_once() {
+        let mut contact = valid_contact();
+        contact.add_email(Email::new("Ann@Example.COM".to_string()).unwrap());
+        contact.add_email(Email::new("ann@example.com".to_string()).unwrap());
+
+        assert_eq!(contact.emails().len(), 1);
+    }
+
+    #[test]
+    fn a_missing_photo_is_rejected_and_leaves_the_old_one() {
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("ann.jpg");
+        std::fs::write(&photo, b"jpeg").unwrap();
+        let mut contact = valid_contact();
+        contact.set_photo_path(Some(photo.clone())).unwrap();
+        assert_eq!(contact.photo_path(), Some(photo.canonicalize().unwrap().as_path()));
+
+        let result = contact.set_photo_path(Some(dir.path().join("missing.jpg")));
+
+        assert!(matches!(result, Err(DomainError::Validation(message)) if message.contains("missing.jpg")));
+        assert_eq!(contact.photo_path(), Some(photo.canonicalize().unwrap().as_path()));
+    }
+
+    #[test]
+    fn files_without_a_photo_field_load_with_none() {
+        let mut value = serde_json::to_value(valid_contact()).unwrap();
+        value.as_object_mut().unwrap().remove("photo_path");
+
+        let contact: Contact = serde_json::from_value(value).unwrap();
+
+        assert_eq!(contact.photo_path(), None);
+    }
+
+    #[test]
+    fn files_without_a_version_load_at_version_zero() {
+        let mut contact = valid_contact();
+        contact.increment_version();
+        let mut value = serde_json::to_value(contact).unwrap();
+        assert_eq!(value["version"], 1);
+        value.as_object_mut().unwrap().remove("version");
+
+        let contact: Contact = serde_json::from_value(value).unwrap();
+
+        assert_eq!(contact.version(), 0);
+    }
+
+    #[test]
+    fn the_builder_sets_every_field() {
+        let contact = Contact::builder()
+            .first_name("Ada")
+            .last_name("Lovelace")
+            .phone(PhoneNumber::new("5550000001".to_string()).unwrap())
+            .email(Email::new("ada@example.com".to_string()).unwrap())
+            .tag("math")
+            .note("Analyst")
+            .metadata("era", "victorian")
+            .build()
+            .unwrap();
+
+        assert_eq!(contact.full_name(), "Ada Lovelace");
+        assert_eq!(contact.phone_numbers()[0].value(), "5550000001");
+        assert_eq!(contact.emails()[0].value(), "ada@example.com");
+        assert_eq!(contact.tags(), ["math"]);
+        assert_eq!(contact.notes(), Some("Analyst"));
+        assert_eq!(contact.metadata().get("era").map(String::as_str), Some("victorian"));
+    }
+
+    #[test]
+    fn the_builder_validates_the_contact() {
+        let no_contact_method = Contact::builder().first_name("Ada").last_name("Lovelace").build();
+        assert!(matches!(no_contact_method, Err(DomainError::Validation(_) | DomainError::BusinessRule(_))));
+
+        let no_name = Contact::builder()
+            .phone(PhoneNumber::new("5550000001".to_string()).unwrap())
+            .build();
+        assert!(no_name.is_err());
+    }
+
+    #[test]
+    fn tags_must_be_non_empty_and_short_enough() {
+        let mut contact = valid_contact();
+        contact.add_tag(" ".to_string());
+        assert!(is_invalid(&contact));
+
+        let mut contact = valid_contact();
+        contact.add_tag("x".repeat(MAX_TAG_LENGTH + 1));
+        assert!(is_invalid(&contact));
+    }
+
+    fn emails_after_adding(policy: EmailDedupPolicy, addresses: &[&str]) -> Vec<String> {
+        let mut contact = valid_contact();
+        for address in addresses {
+            contact.add_email_with_policy(Email::new(address.to_string()).unwrap(), policy);
+        }
+        contact.emails().iter().map(|email| email.value().to_string()).collect()
+    }
+
+    const GMAIL_VARIANTS: [&str; 4] = ["john@gmail.com", "john+x@gmail.com", "j.o.h.n@gmail.com", "john@gmail.com"];
+
+    #[test]
+    fn exact_dedup_is_the_default_and_keeps_variants() {
+        assert_eq!(EmailDedupPolicy::default(), EmailDedupPolicy::DedupExact);
+
+        let mut contact = valid_contact();
+        for address in GMAIL_VARIANTS {
+            contact.add_email(Email::new(address.to_string()).unwrap());
+        }
+        let emails: Vec<&str> = contact.emails().iter().map(|email| email.value()).collect();
+        assert_eq!(emails, ["john@gmail.com", "john+x@gmail.com", "j.o.h.n@gmail.com"]);
+    }
+
+    #[test]
+    fn canonical_dedup_drops_plus_and_dotted_variants() {
+        assert_eq!(emails_after_adding(EmailDedupPolicy::DedupCanonical, &GMAIL_VARIANTS), ["john@gmail.com"]);
+        assert_eq!(
+            emails_after_adding(EmailDedupPolicy::DedupCanonical, &["john+x@gmail.com", "john@example.com", "john+y@example.com"]),
+            ["john+x@gmail.com", "john@example.com", "john+y@example.com"],
+            "only Gmail addresses have a canonical form"
+        );
+    }
+
+    #[test]
+    fn allow_all_keeps_every_address() {
+        assert_eq!(emails_after_adding(EmailDedupPolicy::AllowAll, &GMAIL_VARIANTS), GMAIL_VARIANTS);
+    }
+
+    #[test]
+    fn removing_an_email_follows_the_policy() {
+        let john = Email::new("john@gmail.com".to_string()).unwrap();
+        let remaining = |policy| {
+            let mut contact = valid_contact();
+            for address in GMAIL_VARIANTS {
+                contact.add_email_with_policy(Email::new(address.to_string()).unwrap(), EmailDedupPolicy::AllowAll);
+            }
+            contact.remove_email_with_policy(&john, policy);
+            contact.emails().iter().map(|email| email.value().to_string()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(remaining(EmailDedupPolicy::DedupExact), ["john+x@gmail.com", "j.o.h.n@gmail.com"]);
+        assert_eq!(remaining(EmailDedupPolicy::AllowAll), ["john+x@gmail.com", "j.o.h.n@gmail.com"]);
+        assert!(remaining(EmailDedupPolicy::DedupCanonical).is_empty());
+    }
+
+    #[test]
+    fn display_name_follows_the_name_format() {
+        let contact = valid_contact();
+        assert_eq!(contact.display_name(NameFormat::FirstLast), "Ann Tester");
+        assert_eq!(contact.display_name(NameFormat::LastFirst), "Tester, Ann");
+        assert_eq!(contact.full_name(), "Ann Tester");
+    }
+
+    #[test]
+    fn one_word_names_have_no_separator_in_either_format() {
+        let mut first_only = valid_contact();
+        first_only.set_last_name(" ".to_string());
+        let mut last_only = valid_contact();
+        last_only.set_first_name(String::new());
+
+        for format in [NameFormat::FirstLast, NameFormat::LastFirst] {
+            assert_eq!(first_only.display_name(format), "Ann");
+            assert_eq!(last_only.display_name(format), "Tester");
+        }
+    }
+
+    #[test]
+    fn match_contexts_name_the_field_and_clamp_the_snippet() {
+        let mut contact = valid_contact();
+        let filler = "x".repeat(MATCH_CONTEXT_CHARS + 10);
+        contact.set_notes(Some(format!("{} met at the Conference in Lisbon {}", filler, filler)));
+
+        let contexts = contact.match_contexts("conference");
+        assert_eq!(contexts.len(), 1);
+        let (field, snippet) = &contexts[0];
+        assert_eq!(field, "notes");
+        assert!(snippet.starts_with('…') && snippet.ends_with('…'), "{}", snippet);
+        assert!(snippet.contains("the Conference in"), "the original case is kept: {}", snippet);
+        assert_eq!(snippet.chars().count(), "Conference".len() + 2 * MATCH_CONTEXT_CHARS + 2);
+    }
+
+    #[test]
+    fn match_contexts_cover_every_matching_field() {
+        let mut contact = valid_contact();
+        contact.add_tag("tester-club".to_string());
+        contact.set_metadata("team".to_string(), "Testers".to_string());
+
+        let fields: Vec<String> = contact.match_contexts("TEST").into_iter().map(|(field, _)| field).collect();
+        assert_eq!(fields, ["last name", "tag", "metadata team"]);
+        assert!(contact.match_contexts("nowhere").is_empty());
+        assert!(contact.match_contexts("").is_empty());
+    }
+
+    #[test]
+    fn match_contexts_handle_text_whose_lowercase_changes_length() {
+        let mut contact = valid_contact();
+        // `İ` and the Kelvin sign lowercase to a different number of bytes
+        contact.set_notes(Some("İstanbul \u{212A}elvin trip with ANN, then Ürümqi".to_string()));
+
+        let contexts = contact.match_contexts("ann");
+        let notes = contexts.iter().find(|(field, _)| field == "notes").expect("notes hit");
+        assert_eq!(notes.1, "İstanbul \u{212A}elvin trip with ANN, then Ürümqi");
+
+        let contexts = contact.match_contexts("ürümqi");
+        assert_eq!(contexts, [("notes".to_string(), "…ul \u{212A}elvin trip with ANN, then Ürümqi".to_string())]);
+    }
+
+    #[test]
+    fn anchored_regex_matches_first_names_starting_with_a() {
+        let pattern = Regex::new("^A.*").unwrap();
+        let named = |first_name: &str| {
+            let mut contact = valid_contact();
+            contact.set_first_name(first_name.to_string());
+            contact.set_last_name("Tester".to_string());
+            contact
+        };
+
+        assert!(named("Ann").matches_regex(&pattern));
+        assert!(named("Alice").matches_regex(&pattern));
+        assert!(!named("Bob").matches_regex(&pattern));
+        assert!(!named("ann").matches_regex(&pattern), "regex matching is case-sensitive");
+        assert!(named("ann").matches_regex(&Regex::new("(?i)^A.*").unwrap()));
+    }
+
+    #[test]
+    fn regex_matches_tags() {
+        let mut contact = valid_contact();
+        assert!(!contact.matches_regex(&Regex::new("^work-").unwrap()));
+        contact.add_tag("work-2024".to_string());
+        assert!(contact.matches_regex(&Regex::new("^work-[0-9]+$").unwrap()));
+    }
+
+    #[test]
+    fn merge_from_unions_details_and_keeps_this_contacts_values() {
+        let mut ann = valid_contact();
+        ann.add_email(Email::new("ann@example.com".to_string()).unwrap());
+        ann.add_tag("work".to_string());
+        ann.set_metadata("team".to_string(), "blue".to_string());
+        let mut other = Contact::new(
+            "Annie".to_string(),
+            "Other".to_string(),
+            vec![
+                PhoneNumber::new("(555) 000-0001".to_string()).unwrap(),
+                PhoneNumber::new("5550000002".to_string()).unwrap(),
+            ],
+            vec![
+                Email::new("ANN@example.com".to_string()).unwrap(),
+                Email::new("ann@home.example.com".to_string()).unwrap(),
+            ],
+        );
+        other.add_tag("work".to_string());
+        other.add_tag("family".to_string());
+        other.set_metadata("team".to_string(), "red".to_string());
+        other.set_metadata("desk".to_string(), "4B".to_string());
+
+        ann.merge_from(&other);
+
+        assert_eq!(ann.full_name(), "Ann Tester");
+        let phones: Vec<&str> = ann.phone_numbers().iter().map(PhoneNumber::value).collect();
+        assert_eq!(phones, ["5550000001", "5550000002"]);
+        let emails: Vec<&str> = ann.emails().iter().map(Email::value).collect();
+        assert_eq!(emails, ["ann@example.com", "ann@home.example.com"]);
+        assert_eq!(ann.tags(), ["work", "family"]);
+        assert_eq!(ann.metadata().get("team").map(String::as_str), Some("blue"));
+        assert_eq!(ann.metadata().get("desk").map(String::as_str), Some("4B"));
+    }
+
+    #[test]
+    fn merge_from_fills_in_missing_notes_only() {
+        let mut other = valid_contact();
+        other.set_notes(Some("From the other record".to_string()));
+
+        let mut without_notes = valid_contact();
+        without_notes.merge_from(&other);
+        assert_eq!(without_notes.notes(), Some("From the other record"));
+
+        let mut with_notes = valid_contact();
+        with_notes.set_notes(Some("Keep me".to_string()));
+        with_notes.merge_from(&other);
+        assert_eq!(with_notes.notes(), Some("Keep me"));
+
+        let mut blank_notes = valid_contact();
+        blank_notes.set_notes(Some("  ".to_string()));
+        blank_notes.merge_from(&other);
+        assert_eq!(blank_notes.notes(), Some("From the other record"));
+    }
+
+    #[test]
+    fn anonymize_masks_personal_data_and_keeps_the_shape() {
+        let mut ann = Contact::new(
+            "Ann".to_string(),
+            "Tester".to_string(),
+            vec![
+                PhoneNumber::new("5551234567".to_string()).unwrap(),
+                PhoneNumber::new("+44 20 7946 0958 x12".to_string()).unwrap(),
+            ],
+            vec![Email::new("ann.tester@example.com".to_string()).unwrap()],
+        );
+        ann.add_tag("work".to_string());
+        ann.set_notes(Some("Private".to_string()));
+        ann.set_metadata("team".to_string(), "blue".to_string());
+        ann.set_birthday(Some(Date::parse("1990-05-17").unwrap()));
+
+        let anonymized = ann.anonymize();
+
+        let id = ann.id().to_string();
+        assert_eq!(anonymized.id(), ann.id());
+        assert_eq!(anonymized.full_name(), format!("Contact {}", &id[..8]));
+        let phones: Vec<String> = anonymized.phone_numbers().iter().map(|p| p.value().to_string()).collect();
+        assert_eq!(phones, ["XXXXXXXX67", "+XXXXXXXXXX58"]);
+        let emails: Vec<&str> = anonymized.emails().iter().map(Email::value).collect();
+        assert_eq!(emails, ["xxxxxxxxxx@example.com"]);
+        assert_eq!(anonymized.tags(), ann.tags());
+        assert_eq!(anonymized.notes(), None);
+        assert!(anonymized.metadata().is_empty());
+        assert!(anonymized.birthday().is_some_and(|birthday| *birthday != Date::parse("1990-05-17").unwrap()));
+        assert_eq!(anonymized.created_at(), ann.created_at());
+    }
+
+    #[test]
+    fn set_field_sets_and_clears_each_supported_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let photo = dir.path().join("ann.png");
+        std::fs::write(&photo, b"png").unwrap();
+        let mut contact = valid_contact();
+
+        contact.set_field("first_name", "Anna").unwrap();
+        contact.set_field("last-name", "Smith").unwrap();
+        contact.set_field("notes", "hi there").unwrap();
+        contact.set_field("birthday", "1990-05-17").unwrap();
+        contact.set_field("photo", &photo.to_string_lossy()).unwrap();
+
+        assert_eq!(contact.full_name(), "Anna Smith");
+        assert_eq!(contact.notes(), Some("hi there"));
+        assert_eq!(contact.birthday(), Some(&Date::parse("1990-05-17").unwrap()));
+        assert_eq!(contact.photo_path(), Some(photo.canonicalize().unwrap().as_path()));
+
+        for field in ["notes", "birthday", "photo"] {
+            contact.set_field(field, " ").unwrap();
+        }
+        assert_eq!(contact.notes(), None);
+        assert_eq!(contact.birthday(), None);
+        assert_eq!(contact.photo_path(), None);
+    }
+
+    #[test]
+    fn set_field_rejects_unknown_fields_and_bad_values() {
+        let mut contact = valid_contact();
+
+        let unknown = contact.set_field("nickname", "Annie").unwrap_err();
+        assert!(matches!(&unknown, DomainError::Validation(message) if message.contains("'nickname'") && message.contains("first_name")));
+
+        assert!(contact.set_field("first_name", "  ").is_err());
+        assert!(contact.set_field("birthday", "1990-13-40").is_err());
+        assert_eq!(contact.full_name(), "Ann Tester");
+        assert_eq!(contact.birthday(), None);
+    }
+
+    fn person(first_name: &str, last_name: &str, phones: &[&str], emails: &[&str]) -> Contact {
+        Contact::new(
+            first_name.to_string(),
+            last_name.to_string(),
+            phones.iter().map(|p| PhoneNumber::new(p.to_string()).unwrap()).collect(),
+            emails.iter().map(|e| Email::new(e.to_string()).unwrap()).collect(),
+        )
+    }
+
+    #[test]
+    fn same_person_ignores_ids_tags_and_formatting() {
+        let mut ann = person("Ann", "Tester", &["5550000001", "5550000002"], &["ann.tester@gmail.com"]);
+        ann.add_tag("work".to_string());
+        ann.set_metadata("team".to_string(), "blue".to_string());
+        let mut copy = person(" ann ", "TESTER", &["+1 (555) 000-0002", "555-000-0001"], &["anntester+news@gmail.com"]);
+        copy.add_tag("family".to_string());
+        copy.set_notes(Some("Imported twice".to_string()));
+
+        assert_ne!(ann.id(), copy.id());
+        assert_ne!(ann, copy);
+        assert!(ann.same_person(&copy));
+        assert!(copy.same_person(&ann));
+    }
+
+    #[test]
+    fn different_names_or_contact_details_are_different_people() {
+        let ann = person("Ann", "Tester", &["5550000001"], &["ann@example.com"]);
+
+        assert!(!ann.same_person(&person("Anna", "Tester", &["5550000001"], &["ann@example.com"])));
+        assert!(!ann.same_person(&person("Ann", "Tester", &["5550000009"], &["ann@example.com"])));
+        assert!(!ann.same_person(&person("Ann", "Tester", &["5550000001"], &[])));
+        assert!(!ann.same_person(&person("Ann", "Tester", &["5550000001", "5550000002"], &["ann@example.com"])));
     }
 }
@@ -0,0 +1,49 @@
+use crate::domain::value_objects::ContactId;
+use serde::{Deserialize, Serialize};
+
+/// Named group of contacts, e.g. "Soccer Team"
+/// Unlike tags, a group exists on its own and tracks its members by ID
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Group {
+    name: String,
+    /// Member IDs, unique and in the order they were added
+    members: Vec<ContactId>,
+}
+
+impl Group {
+    /// Create an empty group
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn members(&self) -> &[ContactId] {
+        &self.members
+    }
+
+    pub fn has_member(&self, id: &ContactId) -> bool {
+        self.members.contains(id)
+    }
+
+    /// Add a member, returning false if it was already in the group
+    pub fn add_member(&mut self, id: ContactId) -> bool {
+        if self.has_member(&id) {
+            return false;
+        }
+        self.members.push(id);
+        true
+    }
+
+    /// Remove a member, returning false if it wasn't in the group
+    pub fn remove_member(&mut self, id: &ContactId) -> bool {
+        let before = self.members.len();
+        self.members.retain(|member| member != id);
+        self.members.len() != before
+    }
+}
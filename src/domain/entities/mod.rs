@@ -1,3 +1,5 @@
 pub mod contact;
+pub mod group;
 
-pub use contact::Contact;
+pub use contact::{Contact, ContactBuilder, EmailDedupPolicy, FieldChange, NameFormat, MATCH_CONTEXT_CHARS, MAX_TAG_LENGTH, SETTABLE_FIELDS};
+pub use group::Group;
@@ -1,3 +1,4 @@
+use crate::domain::repositories::RepositoryError;
 use thiserror::Error;
 
 /// Domain-level errors
@@ -19,11 +20,11 @@ pub enum DomainError {
 /// Application-level errors
 #[derive(Error, Debug)]
 pub enum ApplicationError {
-    #[error("Domain error: {0}")]
+    #[error(transparent)]
     Domain(#[from] DomainError),
     
-    #[error("Repository error: {0}")]
-    Repository(#[from] crate::domain::repositories::RepositoryError),
+    #[error(transparent)]
+    Repository(RepositoryError),
     
     #[error("Use case error: {0}")]
     UseCase(String),
@@ -32,6 +33,56 @@ pub enum ApplicationError {
     Service(String),
 }
 
+impl ApplicationError {
+    /// Whether the error was caused by invalid input
+    pub fn is_validation(&self) -> bool {
+        matches!(
+            self,
+            ApplicationError::Domain(DomainError::Validation(_))
+                | ApplicationError::Domain(DomainError::BusinessRule(_))
+        )
+    }
+
+    /// Whether the error was caused by a missing entity
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            ApplicationError::Domain(DomainError::EntityNotFound(_))
+                | ApplicationError::Repository(RepositoryError::ContactNotFound(_))
+                | ApplicationError::Repository(RepositoryError::GroupNotFound(_))
+        )
+    }
+
+    /// Whether a write lost a race with another writer of the same storage,
+    /// or was based on an out-of-date copy of a contact
+    pub fn is_conflict(&self) -> bool {
+        matches!(
+            self,
+            ApplicationError::Repository(RepositoryError::Conflict(_))
+                | ApplicationError::Repository(RepositoryError::VersionConflict { .. })
+        )
+    }
+}
+
+/// Repository validation failures are surfaced as domain validation errors so
+/// callers can tell bad input apart from storage failures
+impl From<RepositoryError> for ApplicationError {
+    fn from(error: RepositoryError) -> Self {
+        match error {
+            RepositoryError::ValidationError(message) => {
+                ApplicationError::Domain(DomainError::Validation(message))
+            }
+            other => ApplicationError::Repository(other),
+        }
+    }
+}
+
+impl From<RepositoryError> for PhonebookError {
+    fn from(error: RepositoryError) -> Self {
+        PhonebookError::Application(error.into())
+    }
+}
+
 /// Infrastructure-level errors
 #[derive(Error, Debug)]
 pub enum InfrastructureError {
@@ -1,9 +1,16 @@
 use crate::domain::entities::Contact;
 use crate::domain::repositories::RepositoryError;
-use crate::domain::value_objects::ContactId;
+use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
+use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
 
+/// Body of a `ContactRepositorySync::transaction`, given every stored contact by ID
+pub type ContactTransaction<'a> = dyn FnMut(&mut HashMap<ContactId, Contact>) -> Result<(), RepositoryError> + 'a;
+
+/// Body of a `ContactRepositorySync::scan`, given the live contacts in no particular order
+pub type ContactScan<'a> = dyn FnMut(&mut dyn Iterator<Item = &Contact>) -> Result<(), RepositoryError> + 'a;
+
 /// Repository trait for contact persistence operations
 /// Follows the Repository pattern and Dependency Inversion Principle
 pub trait ContactRepository: Send + Sync {
@@ -13,22 +20,31 @@ pub trait ContactRepository: Send + Sync {
     /// Find a contact by ID
     fn find_by_id(&self, id: &ContactId) -> Pin<Box<dyn Future<Output = Result<Option<Contact>, RepositoryError>> + Send + '_>>;
     
-    /// Find all contacts
+    /// Find all contacts, excluding soft-deleted ones
     fn find_all(&self) -> Pin<Box<dyn Future<Output = Result<Vec<Contact>, RepositoryError>> + Send + '_>>;
     
+    /// Find all soft-deleted contacts
+    fn find_deleted(&self) -> Pin<Box<dyn Future<Output = Result<Vec<Contact>, RepositoryError>> + Send + '_>>;
+    
     /// Update an existing contact
     fn update(&self, contact: Contact) -> Pin<Box<dyn Future<Output = Result<(), RepositoryError>> + Send + '_>>;
     
-    /// Delete a contact by ID
+    /// Permanently delete a contact by ID
     fn delete(&self, id: &ContactId) -> Pin<Box<dyn Future<Output = Result<(), RepositoryError>> + Send + '_>>;
     
-    /// Search contacts by query
+    /// Search contacts by query, excluding soft-deleted ones
     fn search(&self, query: &str) -> Pin<Box<dyn Future<Output = Result<Vec<Contact>, RepositoryError>> + Send + '_>>;
     
+    /// Find contacts with this phone number in any format, excluding soft-deleted ones
+    fn find_by_phone(&self, phone: &PhoneNumber) -> Pin<Box<dyn Future<Output = Result<Vec<Contact>, RepositoryError>> + Send + '_>>;
+    
+    /// Find contacts with this email address (compared by `Email::canonical`), excluding soft-deleted ones
+    fn find_by_email(&self, email: &Email) -> Pin<Box<dyn Future<Output = Result<Vec<Contact>, RepositoryError>> + Send + '_>>;
+    
     /// Check if a contact exists
     fn exists(&self, id: &ContactId) -> Pin<Box<dyn Future<Output = Result<bool, RepositoryError>> + Send + '_>>;
     
-    /// Get total count of contacts
+    /// Get total count of contacts, excluding soft-deleted ones
     fn count(&self) -> Pin<Box<dyn Future<Output = Result<usize, RepositoryError>> + Send + '_>>;
 }
 
@@ -40,21 +56,47 @@ pub trait ContactRepositorySync: Send + Sync {
     /// Find a contact by ID
     fn find_by_id(&self, id: &ContactId) -> Result<Option<Contact>, RepositoryError>;
     
-    /// Find all contacts
+    /// Find all contacts, excluding soft-deleted ones
     fn find_all(&self) -> Result<Vec<Contact>, RepositoryError>;
     
-    /// Update an existing contact
+    /// Find all soft-deleted contacts
+    fn find_deleted(&self) -> Result<Vec<Contact>, RepositoryError>;
+    
+    /// Update an existing contact, replacing the stored copy
+    /// Changes saved by another writer since `contact` was read are overwritten; use
+    /// `transaction` to change a contact based on its stored state
     fn update(&self, contact: Contact) -> Result<(), RepositoryError>;
+
+    /// Insert a contact, or replace the stored one with the same ID
+    fn upsert(&self, contact: Contact) -> Result<(), RepositoryError>;
     
-    /// Delete a contact by ID
+    /// Permanently delete a contact by ID
     fn delete(&self, id: &ContactId) -> Result<(), RepositoryError>;
     
-    /// Search contacts by query
+    /// Search contacts by query, excluding soft-deleted ones
     fn search(&self, query: &str) -> Result<Vec<Contact>, RepositoryError>;
     
+    /// Find contacts with this phone number in any format, excluding soft-deleted ones
+    fn find_by_phone(&self, phone: &PhoneNumber) -> Result<Vec<Contact>, RepositoryError>;
+    
+    /// Find contacts with this email address (compared by `Email::canonical`), excluding soft-deleted ones
+    fn find_by_email(&self, email: &Email) -> Result<Vec<Contact>, RepositoryError>;
+    
     /// Check if a contact exists
     fn exists(&self, id: &ContactId) -> Result<bool, RepositoryError>;
     
-    /// Get total count of contacts
+    /// Get total count of contacts, excluding soft-deleted ones
     fn count(&self) -> Result<usize, RepositoryError>;
+
+    /// Apply several changes to the stored contacts (including soft-deleted ones) and
+    /// persist them together. If `f` returns an error nothing is saved and the store
+    /// is left as it was. `f` may be called more than once if the store has to retry,
+    /// so it should not carry state over from a previous call. Contacts that `f` adds or
+    /// changes must pass the same validation as `save` and `update`, or nothing is saved.
+    fn transaction(&self, f: &mut ContactTransaction) -> Result<(), RepositoryError>;
+
+    /// Pass the stored contacts, excluding soft-deleted ones, to `f` without copying
+    /// them out of the store. The store stays locked while `f` runs, so `f` must not
+    /// call back into the repository.
+    fn scan(&self, f: &mut ContactScan) -> Result<(), RepositoryError>;
 }
@@ -5,9 +5,27 @@ pub enum RepositoryError {
     #[error("Contact not found with ID: {0}")]
     ContactNotFound(String),
     
+    #[error("Contact ID prefix '{0}' matches more than one contact")]
+    AmbiguousId(String),
+    
     #[error("Contact already exists with ID: {0}")]
     ContactAlreadyExists(String),
     
+    #[error("Group not found: {0}")]
+    GroupNotFound(String),
+    
+    #[error("Group already exists: {0}")]
+    GroupAlreadyExists(String),
+    
+    #[error("Write conflict: {0}")]
+    Conflict(String),
+    
+    #[error("Version conflict for contact {id}: expected version {expected}, but it is at version {actual}")]
+    VersionConflict { id: String, expected: u64, actual: u64 },
+    
+    #[error("Wrong passphrase for {0} (or the file is damaged)")]
+    WrongPassphrase(String),
+    
     #[error("Storage error: {0}")]
     StorageError(String),
     
@@ -0,0 +1,21 @@
+use crate::domain::entities::Group;
+use crate::domain::repositories::RepositoryError;
+
+/// Repository trait for group persistence operations
+/// Groups are identified by their name
+pub trait GroupRepositorySync: Send + Sync {
+    /// Save a new group
+    fn save(&self, group: Group) -> Result<(), RepositoryError>;
+
+    /// Find a group by name
+    fn find_by_name(&self, name: &str) -> Result<Option<Group>, RepositoryError>;
+
+    /// Find all groups
+    fn find_all(&self) -> Result<Vec<Group>, RepositoryError>;
+
+    /// Update an existing group
+    fn update(&self, group: Group) -> Result<(), RepositoryError>;
+
+    /// Delete a group by name
+    fn delete(&self, name: &str) -> Result<(), RepositoryError>;
+}
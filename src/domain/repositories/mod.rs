@@ -1,5 +1,7 @@
 pub mod contact_repository;
 pub mod errors;
+pub mod group_repository;
 
-pub use contact_repository::{ContactRepository, ContactRepositorySync};
+pub use contact_repository::{ContactRepository, ContactRepositorySync, ContactScan, ContactTransaction};
 pub use errors::RepositoryError;
+pub use group_repository::GroupRepositorySync;
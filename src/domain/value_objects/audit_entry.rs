@@ -0,0 +1,43 @@
+use crate::domain::value_objects::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// Value object recording a single field change on a contact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    field: String,
+    old_value: Option<String>,
+    new_value: Option<String>,
+    timestamp: Timestamp,
+}
+
+impl AuditEntry {
+    pub fn new(
+        field: String,
+        old_value: Option<String>,
+        new_value: Option<String>,
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            field,
+            old_value,
+            new_value,
+            timestamp,
+        }
+    }
+
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn old_value(&self) -> Option<&str> {
+        self.old_value.as_deref()
+    }
+
+    pub fn new_value(&self) -> Option<&str> {
+        self.new_value.as_deref()
+    }
+
+    pub fn timestamp(&self) -> &Timestamp {
+        &self.timestamp
+    }
+}
@@ -1,9 +1,11 @@
+use crate::domain::errors::DomainError;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
 
 /// Unique identifier for a contact
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// Ordered so it can serve as a stable final sort key
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct ContactId(Uuid);
 
 impl ContactId {
@@ -17,6 +19,18 @@ impl ContactId {
         Self(uuid)
     }
 
+    /// Parse a full contact ID, naming the offending input on failure
+    pub fn parse(input: &str) -> Result<Self, DomainError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(DomainError::Validation("Contact ID cannot be empty".to_string()));
+        }
+
+        Uuid::parse_str(input)
+            .map(Self)
+            .map_err(|e| DomainError::Validation(format!("Invalid contact ID '{}': {}", input, e)))
+    }
+
     /// Get the inner UUID
     pub fn as_uuid(&self) -> &Uuid {
         &self.0
@@ -46,3 +60,28 @@ impl From<ContactId> for Uuid {
         id.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_a_valid_uuid() {
+        let id = ContactId::parse(" abcd1234-0000-4000-8000-000000000001 ").unwrap();
+        assert_eq!(id.to_string(), "abcd1234-0000-4000-8000-000000000001");
+    }
+
+    #[test]
+    fn parse_names_a_malformed_input() {
+        let error = ContactId::parse("abcd-not-a-uuid").unwrap_err();
+        assert!(matches!(&error, DomainError::Validation(message) if message.contains("'abcd-not-a-uuid'")), "{}", error);
+    }
+
+    #[test]
+    fn parse_rejects_an_empty_string() {
+        for input in ["", "   "] {
+            let error = ContactId::parse(input).unwrap_err();
+            assert!(matches!(&error, DomainError::Validation(message) if message.contains("empty")), "{}", error);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use chrono::format::ParseErrorKind;
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DateError {
+    #[error("Invalid date format (expected YYYY-MM-DD): {0}")]
+    InvalidFormat(String),
+    #[error("Date out of range: {0}")]
+    OutOfRange(String),
+}
+
+/// Value object representing a calendar date without time or timezone
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Date(NaiveDate);
+
+impl Date {
+    /// Create a new date with validation
+    pub fn new(year: i32, month: u32, day: u32) -> Result<Self, DateError> {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .map(Self)
+            .ok_or_else(|| DateError::OutOfRange(format!("{:04}-{:02}-{:02}", year, month, day)))
+    }
+
+    /// Parse a date in YYYY-MM-DD format
+    pub fn parse(value: &str) -> Result<Self, DateError> {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map(Self)
+            .map_err(|e| match e.kind() {
+                ParseErrorKind::OutOfRange | ParseErrorKind::Impossible => DateError::OutOfRange(value.to_string()),
+                _ => DateError::InvalidFormat(value.to_string()),
+            })
+    }
+
+    /// Get the current date (UTC)
+    pub fn today() -> Self {
+        Self(Utc::now().date_naive())
+    }
+
+    /// The underlying chrono date, for calendar arithmetic
+    pub fn naive(&self) -> NaiveDate {
+        self.0
+    }
+
+    pub fn year(&self) -> i32 {
+        self.0.year()
+    }
+
+    pub fn month(&self) -> u32 {
+        self.0.month()
+    }
+
+    pub fn day(&self) -> u32 {
+        self.0.day()
+    }
+}
+
+impl From<NaiveDate> for Date {
+    fn from(date: NaiveDate) -> Self {
+        Self(date)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.format("%Y-%m-%d"))
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = DateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Date::parse(&value)
+    }
+}
+
+impl TryFrom<&str> for Date {
+    type Error = DateError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Date::parse(value)
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> Self {
+        date.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_prints_iso_dates() {
+        let date = Date::parse(" 2024-02-29 ").unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+        assert_eq!(date.to_string(), "2024-02-29");
+        assert_eq!(serde_json::to_string(&date).unwrap(), "\"2024-02-29\"");
+    }
+
+    #[test]
+    fn rejects_malformed_and_impossible_dates() {
+        assert!(matches!(Date::parse("29/02/2024"), Err(DateError::InvalidFormat(_))));
+        assert!(matches!(Date::parse("2024-02"), Err(DateError::InvalidFormat(_))));
+        assert!(matches!(Date::parse("2023-02-29"), Err(DateError::OutOfRange(_))));
+        assert!(matches!(Date::parse("2024-13-01"), Err(DateError::OutOfRange(_))));
+        assert!(Date::new(2024, 4, 31).is_err());
+    }
+}
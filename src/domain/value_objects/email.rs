@@ -1,20 +1,37 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use thiserror::Error;
 
+/// Known throwaway email domains, one per line; `#` starts a comment line
+const DISPOSABLE_DOMAINS: &str = include_str!("disposable_domains.txt");
+
+/// Popular email provider domains, one per line; `#` starts a comment line
+const PROVIDER_DOMAINS: &str = include_str!("email_providers.txt");
+
+/// Provider domains shorter than this only match typos one edit away,
+/// so short real domains aren't mistaken for misspelt providers
+const TWO_EDIT_MIN_LEN: usize = 9;
+
 #[derive(Error, Debug)]
 pub enum EmailError {
     #[error("Invalid email format: {0}")]
     InvalidFormat(String),
     #[error("Email cannot be empty")]
     Empty,
+    #[error("Invalid email label: {0}")]
+    InvalidLabel(String),
 }
 
 /// Value object representing an email address
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+/// An optional label (e.g. `work`, `personal`) describes the address but
+/// doesn't take part in equality
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Email {
     value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
 }
 
 impl Email {
@@ -30,7 +47,34 @@ impl Email {
             return Err(EmailError::InvalidFormat(value));
         }
 
-        Ok(Self { value: trimmed })
+        Ok(Self {
+            value: trimmed,
+            label: None,
+        })
+    }
+
+    /// Parse an email with an optional `label:` prefix, e.g. `work:jane@acme.com`
+    pub fn parse_labeled(value: &str) -> Result<Self, EmailError> {
+        match value.split_once(':') {
+            Some((label, address)) => Self::new(address.to_string())?.with_label(label),
+            None => Self::new(value.to_string()),
+        }
+    }
+
+    /// Attach a label to the email
+    pub fn with_label(mut self, label: &str) -> Result<Self, EmailError> {
+        let label = label.trim().to_lowercase();
+        if label.is_empty() || !label.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            return Err(EmailError::InvalidLabel(label));
+        }
+
+        self.label = Some(label);
+        Ok(self)
+    }
+
+    /// Get the email label, if any
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
     }
 
     /// Get the email value
@@ -51,10 +95,86 @@ impl Email {
         self.value.split('@').nth(1).unwrap_or("")
     }
 
+    /// Whether the address belongs to a known disposable (throwaway) email service
+    pub fn is_disposable(&self) -> bool {
+        let domain = self.domain();
+        DISPOSABLE_DOMAINS
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .any(|known| known == domain)
+    }
+
+    /// The same address at a popular provider's domain when this domain looks like a typo of it,
+    /// e.g. `ann@gmial.com` -> `ann@gmail.com`; `None` when the domain is a known provider
+    /// or isn't close to one. The label is kept
+    pub fn suggest_correction(&self) -> Option<Self> {
+        let domain = self.domain();
+        let providers = PROVIDER_DOMAINS
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+        let mut best: Option<(&str, usize)> = None;
+        for provider in providers {
+            let distance = strsim::osa_distance(domain, provider);
+            if distance == 0 {
+                return None;
+            }
+            let max_distance = if provider.len() >= TWO_EDIT_MIN_LEN { 2 } else { 1 };
+            if distance <= max_distance && best.is_none_or(|(_, closest)| distance < closest) {
+                best = Some((provider, distance));
+            }
+        }
+
+        best.map(|(provider, _)| Self {
+            value: format!("{}@{}", self.local_part(), provider),
+            label: self.label.clone(),
+        })
+    }
+
     /// Get the local part of the email
     pub fn local_part(&self) -> &str {
         self.value.split('@').next().unwrap_or("")
     }
+
+    /// Copy of the address with every character of the local part replaced by `x`
+    /// The domain and label are kept, so anonymized data keeps its shape
+    pub fn masked(&self) -> Self {
+        Self {
+            value: format!("{}@{}", "x".repeat(self.local_part().chars().count()), self.domain()),
+            label: self.label.clone(),
+        }
+    }
+
+    /// Canonical form of the address, used only to spot duplicates
+    /// Gmail ignores dots and `+tag` suffixes in the local part, so
+    /// `john.doe+news@gmail.com` and `johndoe@gmail.com` share a canonical form;
+    /// other domains are returned unchanged
+    pub fn canonical(&self) -> String {
+        match self.domain() {
+            "gmail.com" | "googlemail.com" => {
+                let local = self.local_part();
+                let local = local.split('+').next().unwrap_or(local);
+                format!("{}@gmail.com", local.replace('.', ""))
+            }
+            _ => self.value.clone(),
+        }
+    }
+}
+
+impl PartialEq for Email {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Email {}
+
+impl Hash for Email {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
 }
 
 impl fmt::Display for Email {
@@ -79,4 +199,71 @@ impl TryFrom<&str> for Email {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonical(address: &str) -> String {
+        Email::new(address.to_string()).unwrap().canonical()
+    }
+
+    #[test]
+    fn gmail_dots_and_plus_tags_share_a_canonical_form() {
+        assert_eq!(canonical("john.doe@gmail.com"), "johndoe@gmail.com");
+        assert_eq!(canonical("johndoe+news@gmail.com"), "johndoe@gmail.com");
+        assert_eq!(canonical("J.O.H.N.Doe+a+b@Gmail.com"), "johndoe@gmail.com");
+        assert_eq!(canonical("johndoe@googlemail.com"), "johndoe@gmail.com");
+    }
+
+    #[test]
+    fn canonical_form_leaves_the_stored_value_alone() {
+        let email = Email::new("john.doe+news@gmail.com".to_string()).unwrap();
+        assert_eq!(email.canonical(), "johndoe@gmail.com");
+        assert_eq!(email.value(), "john.doe+news@gmail.com");
+    }
+
+    #[test]
+    fn other_domains_are_unchanged() {
+        assert_eq!(canonical("john.doe+news@example.com"), "john.doe+news@example.com");
+        assert_eq!(canonical("john.doe@gmail.co"), "john.doe@gmail.co");
+    }
+
+    fn email(address: &str) -> Email {
+        Email::new(address.to_string()).unwrap()
+    }
+
+    #[test]
+    fn known_throwaway_domains_are_disposable() {
+        assert!(email("someone@10minutemail.com").is_disposable());
+        assert!(email("Someone@GetNada.com").is_disposable());
+    }
 
+    #[test]
+    fn ordinary_and_lookalike_domains_are_not_disposable() {
+        assert!(!email("ann@example.com").is_disposable());
+        assert!(!email("ann@gmail.com").is_disposable());
+        assert!(!email("ann@mail.10minutemail.com").is_disposable(), "only exact domains match");
+    }
+
+    fn suggestion(address: &str) -> Option<String> {
+        email(address).suggest_correction().map(|corrected| corrected.value().to_string())
+    }
+
+    #[test]
+    fn misspelt_provider_domains_get_a_correction() {
+        assert_eq!(suggestion("ann@gmial.com").as_deref(), Some("ann@gmail.com"));
+        assert_eq!(suggestion("ann@yaho.com").as_deref(), Some("ann@yahoo.com"));
+        assert_eq!(suggestion("ann@hotmial.com").as_deref(), Some("ann@hotmail.com"));
+
+        let labeled = Email::parse_labeled("work:ann@gmial.com").unwrap().suggest_correction().unwrap();
+        assert_eq!(labeled.label(), Some("work"));
+    }
+
+    #[test]
+    fn correct_and_unrelated_domains_get_no_correction() {
+        assert_eq!(suggestion("ann@gmail.com"), None);
+        assert_eq!(suggestion("ann@Yahoo.com"), None);
+        assert_eq!(suggestion("ann@example.com"), None);
+        assert_eq!(suggestion("ann@gmx.de"), None, "a provider close to another is not a typo");
+    }
+}
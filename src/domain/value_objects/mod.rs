@@ -1,7 +1,13 @@
 pub mod phone_number;
 pub mod email;
 pub mod contact_id;
+pub mod date;
+pub mod timestamp;
+pub mod audit_entry;
 
-pub use phone_number::PhoneNumber;
+pub use phone_number::{PhoneNumber, PhoneValidationPolicy};
 pub use email::Email;
 pub use contact_id::ContactId;
+pub use date::Date;
+pub use timestamp::Timestamp;
+pub use audit_entry::AuditEntry;
@@ -3,40 +3,256 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use thiserror::Error;
 
+/// Country calling codes of common regions, mapped to ISO 3166-1 alpha-2 codes
+/// Codes shared by several countries map to the largest one (e.g. `+1` to `US`, `+7` to `RU`)
+const CALLING_CODES: &[(&str, &str)] = &[
+    ("1", "US"),
+    ("7", "RU"),
+    ("20", "EG"),
+    ("27", "ZA"),
+    ("30", "GR"),
+    ("31", "NL"),
+    ("32", "BE"),
+    ("33", "FR"),
+    ("34", "ES"),
+    ("36", "HU"),
+    ("39", "IT"),
+    ("40", "RO"),
+    ("41", "CH"),
+    ("43", "AT"),
+    ("44", "GB"),
+    ("45", "DK"),
+    ("46", "SE"),
+    ("47", "NO"),
+    ("48", "PL"),
+    ("49", "DE"),
+    ("51", "PE"),
+    ("52", "MX"),
+    ("54", "AR"),
+    ("55", "BR"),
+    ("56", "CL"),
+    ("57", "CO"),
+    ("60", "MY"),
+    ("61", "AU"),
+    ("62", "ID"),
+    ("63", "PH"),
+    ("64", "NZ"),
+    ("65", "SG"),
+    ("66", "TH"),
+    ("81", "JP"),
+    ("82", "KR"),
+    ("84", "VN"),
+    ("86", "CN"),
+    ("90", "TR"),
+    ("91", "IN"),
+    ("92", "PK"),
+    ("94", "LK"),
+    ("98", "IR"),
+    ("212", "MA"),
+    ("234", "NG"),
+    ("254", "KE"),
+    ("351", "PT"),
+    ("353", "IE"),
+    ("358", "FI"),
+    ("380", "UA"),
+    ("420", "CZ"),
+    ("852", "HK"),
+    ("880", "BD"),
+    ("886", "TW"),
+    ("966", "SA"),
+    ("971", "AE"),
+    ("972", "IL"),
+    ("977", "NP"),
+];
+
 #[derive(Error, Debug)]
 pub enum PhoneNumberError {
     #[error("Invalid phone number format: {0}")]
     InvalidFormat(String),
     #[error("Phone number cannot be empty")]
     Empty,
+    #[error("Invalid phone validation policy: {0}")]
+    InvalidPolicy(String),
+}
+
+/// Rules used to decide whether a phone number is acceptable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhoneValidationPolicy {
+    /// Minimum number of digits, excluding an optional `+` country code
+    pub min_digits: usize,
+    /// Maximum number of digits, excluding an optional `+` country code
+    pub max_digits: usize,
+    /// Accept short service codes (3 to 6 digits, no country code)
+    pub allow_short_codes: bool,
+}
+
+impl PhoneValidationPolicy {
+    /// Create a policy, checking that the digit bounds are consistent
+    pub fn new(min_digits: usize, max_digits: usize, allow_short_codes: bool) -> Result<Self, PhoneNumberError> {
+        if min_digits == 0 || min_digits > max_digits {
+            return Err(PhoneNumberError::InvalidPolicy(format!(
+                "digit range {}..={} is invalid",
+                min_digits, max_digits
+            )));
+        }
+
+        Ok(Self {
+            min_digits,
+            max_digits,
+            allow_short_codes,
+        })
+    }
+}
+
+impl Default for PhoneValidationPolicy {
+    fn default() -> Self {
+        Self {
+            min_digits: 10,
+            max_digits: 15,
+            allow_short_codes: false,
+        }
+    }
 }
 
-/// Value object representing a phone number
+/// Value object representing a phone number, with an optional extension
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PhoneNumber {
     value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extension: Option<String>,
 }
 
 impl PhoneNumber {
     /// Create a new phone number with validation
     pub fn new(value: String) -> Result<Self, PhoneNumberError> {
+        Self::new_with_policy(value, &PhoneValidationPolicy::default())
+    }
+
+    /// Create a new phone number validated against the given policy
+    /// A trailing extension written as `x890`, `ext 890`, `ext. 890` or `,890`
+    /// is kept separately; the rest must be a valid number on its own
+    pub fn new_with_policy(value: String, policy: &PhoneValidationPolicy) -> Result<Self, PhoneNumberError> {
         if value.trim().is_empty() {
             return Err(PhoneNumberError::Empty);
         }
 
-        let cleaned = Self::clean_phone_number(&value);
-        if !Self::is_valid_format(&cleaned) {
+        let (number, extension) = Self::split_extension(&value);
+        let cleaned = Self::clean_phone_number(number);
+        if !Self::is_valid_format(&cleaned, policy) {
             return Err(PhoneNumberError::InvalidFormat(value));
         }
 
-        Ok(Self { value: cleaned })
+        Ok(Self { value: cleaned, extension })
+    }
+
+    /// Parse a field holding several numbers, e.g. `555-1111 / 555-2222, 555-3333`
+    /// Returns the valid numbers and the fragments that failed to parse
+    pub fn parse_many(value: &str) -> (Vec<PhoneNumber>, Vec<String>) {
+        Self::parse_many_with_policy(value, &PhoneValidationPolicy::default())
+    }
+
+    /// Parse a field holding several numbers, each validated against the given policy
+    pub fn parse_many_with_policy(value: &str, policy: &PhoneValidationPolicy) -> (Vec<PhoneNumber>, Vec<String>) {
+        let mut numbers = Vec::new();
+        let mut invalid = Vec::new();
+
+        for part in value.split([',', '/', ';']).map(str::trim).filter(|p| !p.is_empty()) {
+            // Whitespace may be part of one number ("555 123 4567") or separate several
+            if let Ok(number) = Self::new_with_policy(part.to_string(), policy) {
+                numbers.push(number);
+                continue;
+            }
+
+            for fragment in part.split_whitespace() {
+                match Self::new_with_policy(fragment.to_string(), policy) {
+                    Ok(number) => numbers.push(number),
+                    Err(_) => invalid.push(fragment.to_string()),
+                }
+            }
+        }
+
+        (numbers, invalid)
     }
 
-    /// Get the phone number value
+    /// Get the phone number value, without any extension
     pub fn value(&self) -> &str {
         &self.value
     }
 
+    /// Get the extension dialled after connecting, if any
+    pub fn extension(&self) -> Option<&str> {
+        self.extension.as_deref()
+    }
+
+    /// ISO country code guessed from the calling code of a `+` number, e.g. `GB` for `+44`
+    /// Returns `None` for numbers without a `+` or with a calling code not in the offline table
+    pub fn region(&self) -> Option<&'static str> {
+        let digits = self.value.strip_prefix('+')?;
+
+        // Calling codes are prefix-free, so at most one length matches
+        (1..=3)
+            .filter_map(|len| digits.get(..len))
+            .find_map(|code| CALLING_CODES.iter().find(|(known, _)| *known == code))
+            .map(|(_, region)| *region)
+    }
+
+    /// Copy of the number with every digit but the last two replaced by `X`
+    /// A leading `+` and the length are kept; an extension is masked entirely
+    pub fn masked(&self) -> Self {
+        let digits = self.value.chars().filter(char::is_ascii_digit).count();
+        let mut seen = 0;
+        let value = self
+            .value
+            .chars()
+            .map(|c| {
+                if !c.is_ascii_digit() {
+                    return c;
+                }
+                seen += 1;
+                if seen + 2 > digits { c } else { 'X' }
+            })
+            .collect();
+
+        Self {
+            value,
+            extension: self.extension.as_ref().map(|extension| "X".repeat(extension.len())),
+        }
+    }
+
+    /// Canonical E.164-style form used to compare numbers entered in different formats
+    /// Numbers without a country code are assumed to be North American, as in `formatted`;
+    /// an extension is appended as `x<digits>`, so different extensions stay distinct
+    pub fn normalized(&self) -> String {
+        match &self.extension {
+            Some(extension) => format!("{}x{}", self.normalized_number(), extension),
+            None => self.normalized_number(),
+        }
+    }
+
+    fn normalized_number(&self) -> String {
+        if self.value.starts_with('+') {
+            self.value.clone()
+        } else if self.value.len() == 11 && self.value.starts_with('1') {
+            format!("+{}", self.value)
+        } else if self.value.len() == 10 {
+            format!("+1{}", self.value)
+        } else {
+            self.value.clone()
+        }
+    }
+
+    /// Split off a trailing extension (`x`, `ext`, `ext.` or `,` followed by digits)
+    fn split_extension(phone: &str) -> (&str, Option<String>) {
+        let regex = Regex::new(r"(?i)^(.*?\d.*?)\s*(?:ext\.?|x|,)\s*(\d+)\s*$").unwrap();
+        match regex.captures(phone) {
+            Some(captures) => (
+                captures.get(1).map_or(phone, |m| m.as_str()),
+                captures.get(2).map(|m| m.as_str().to_string()),
+            ),
+            None => (phone, None),
+        }
+    }
+
     /// Clean phone number by removing non-digit characters except +
     fn clean_phone_number(phone: &str) -> String {
         phone
@@ -46,13 +262,28 @@ impl PhoneNumber {
     }
 
     /// Validate phone number format
-    fn is_valid_format(phone: &str) -> bool {
-        let regex = Regex::new(r"^(\+\d{1,3})?\d{10,15}$").unwrap();
+    fn is_valid_format(phone: &str, policy: &PhoneValidationPolicy) -> bool {
+        if policy.allow_short_codes && !phone.starts_with('+') && (3..=6).contains(&phone.len()) {
+            return true;
+        }
+
+        let regex = Regex::new(&format!(
+            r"^(\+\d{{1,3}})?\d{{{},{}}}$",
+            policy.min_digits, policy.max_digits
+        ))
+        .unwrap();
         regex.is_match(phone)
     }
 
-    /// Format phone number for display
+    /// Format phone number for display, followed by ` x<extension>` if there is one
     pub fn formatted(&self) -> String {
+        match &self.extension {
+            Some(extension) => format!("{} x{}", self.formatted_number(), extension),
+            None => self.formatted_number(),
+        }
+    }
+
+    fn formatted_number(&self) -> String {
         if self.value.starts_with('+') {
             self.value.clone()
         } else if self.value.len() == 10 {
@@ -91,3 +322,111 @@ impl TryFrom<&str> for PhoneNumber {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(numbers: &[PhoneNumber]) -> Vec<&str> {
+        numbers.iter().map(PhoneNumber::value).collect()
+    }
+
+    #[test]
+    fn parse_many_splits_on_every_separator() {
+        let (numbers, invalid) = PhoneNumber::parse_many("5551111111 / 5552222222, 5553333333; 5554444444 5555555555");
+
+        assert_eq!(
+            values(&numbers),
+            ["5551111111", "5552222222", "5553333333", "5554444444", "5555555555"]
+        );
+        assert!(invalid.is_empty());
+    }
+
+    #[test]
+    fn parse_many_keeps_spaced_numbers_whole() {
+        let (numbers, invalid) = PhoneNumber::parse_many("555 111 1111, (555) 222-2222");
+
+        assert_eq!(values(&numbers), ["5551111111", "5552222222"]);
+        assert!(invalid.is_empty());
+    }
+
+    #[test]
+    fn parse_many_reports_the_fragments_that_failed() {
+        let (numbers, invalid) = PhoneNumber::parse_many("5551111111 / call-me, 12;; 5552222222, 555 abc");
+
+        assert_eq!(values(&numbers), ["5551111111", "5552222222"]);
+        assert_eq!(invalid, ["call-me", "12", "555", "abc"]);
+    }
+
+    #[test]
+    fn parse_many_of_an_empty_field_is_empty() {
+        let (numbers, invalid) = PhoneNumber::parse_many(" , / ");
+
+        assert!(numbers.is_empty());
+        assert!(invalid.is_empty());
+    }
+
+    #[test]
+    fn parse_many_with_policy_accepts_what_the_policy_allows() {
+        let policy = PhoneValidationPolicy::new(10, 15, true).unwrap();
+        let (numbers, invalid) = PhoneNumber::parse_many_with_policy("911, 5551111111", &policy);
+
+        assert_eq!(values(&numbers), ["911", "5551111111"]);
+        assert!(invalid.is_empty());
+    }
+
+    #[test]
+    fn extensions_are_parsed_from_every_notation() {
+        for input in [
+            "+1-555-123-4567 x890",
+            "+1-555-123-4567x890",
+            "+1 555 123 4567 ext 890",
+            "+1 555 123 4567 EXT. 890",
+            "+1 555 123 4567,890",
+        ] {
+            let phone = PhoneNumber::new(input.to_string()).unwrap();
+            assert_eq!(phone.value(), "+15551234567", "{}", input);
+            assert_eq!(phone.extension(), Some("890"), "{}", input);
+            assert_eq!(phone.to_string(), "+15551234567 x890", "{}", input);
+        }
+
+        let local = PhoneNumber::new("555-123-4567 ext. 12".to_string()).unwrap();
+        assert_eq!(local.formatted(), "(555) 123-4567 x12");
+        assert_eq!(local.normalized(), "+15551234567x12");
+    }
+
+    #[test]
+    fn the_number_before_an_extension_must_still_be_valid() {
+        assert!(PhoneNumber::new("555-1234 x890".to_string()).is_err());
+        assert!(PhoneNumber::new("x890".to_string()).is_err());
+    }
+
+    #[test]
+    fn extensions_round_trip_and_default_to_none() {
+        let phone = PhoneNumber::new("5551234567 x890".to_string()).unwrap();
+        let json = serde_json::to_string(&phone).unwrap();
+        assert_eq!(serde_json::from_str::<PhoneNumber>(&json).unwrap(), phone);
+
+        let old: PhoneNumber = serde_json::from_str(r#"{"value":"5551234567"}"#).unwrap();
+        assert_eq!(old.extension(), None);
+        assert!(!serde_json::to_string(&old).unwrap().contains("extension"));
+    }
+
+    fn region_of(value: &str) -> Option<&'static str> {
+        PhoneNumber::new(value.to_string()).unwrap().region()
+    }
+
+    #[test]
+    fn region_comes_from_the_calling_code() {
+        assert_eq!(region_of("+1 555 123 4567"), Some("US"));
+        assert_eq!(region_of("+44 20 7946 0958"), Some("GB"));
+        assert_eq!(region_of("+91 98765 43210"), Some("IN"));
+    }
+
+    #[test]
+    fn numbers_without_a_plus_or_a_known_code_have_no_region() {
+        assert_eq!(region_of("5551234567"), None);
+        assert_eq!(region_of("15551234567"), None);
+        assert_eq!(region_of("+999 1234567890"), None);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::domain::value_objects::date::Date;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TimestampError {
+    #[error("Invalid timestamp (expected RFC 3339, e.g. 2024-01-31T09:30:00Z): {0}")]
+    InvalidFormat(String),
+}
+
+/// Value object representing a point in time in UTC, to whole seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    /// Get the current time
+    pub fn now() -> Self {
+        Self::truncated(Utc::now())
+    }
+
+    /// Create a timestamp from seconds since the Unix epoch
+    /// Returns `None` for times chrono can't represent
+    pub fn from_unix_seconds(seconds: i64) -> Option<Self> {
+        DateTime::from_timestamp(seconds, 0).map(Self)
+    }
+
+    /// Seconds since the Unix epoch
+    pub fn unix_seconds(&self) -> i64 {
+        self.0.timestamp()
+    }
+
+    /// Midnight UTC at the start of the given date
+    pub fn start_of_day(date: &Date) -> Self {
+        Self(date.naive().and_time(Default::default()).and_utc())
+    }
+
+    /// The UTC calendar date of this timestamp
+    pub fn date(&self) -> Date {
+        self.0.date_naive().into()
+    }
+
+    /// Parse an RFC 3339 timestamp such as `2024-01-31T09:30:00Z` or
+    /// `2024-01-31T09:30:00.123+02:00`. Fractional seconds are truncated.
+    pub fn parse(value: &str) -> Result<Self, TimestampError> {
+        DateTime::parse_from_rfc3339(value.trim())
+            .map(|time| Self::truncated(time.to_utc()))
+            .map_err(|_| TimestampError::InvalidFormat(value.to_string()))
+    }
+
+    /// Parse either an RFC 3339 timestamp or a plain `YYYY-MM-DD` date,
+    /// which is taken as midnight UTC at the start of that day
+    pub fn parse_date_or_time(value: &str) -> Result<Self, TimestampError> {
+        match Date::parse(value) {
+            Ok(date) => Ok(Self::start_of_day(&date)),
+            Err(_) => Self::parse(value),
+        }
+    }
+
+    /// Drop fractional seconds, which the stored form doesn't keep
+    fn truncated(time: DateTime<Utc>) -> Self {
+        Self(DateTime::from_timestamp(time.timestamp(), 0).unwrap_or(time))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+}
+
+impl TryFrom<String> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Timestamp::parse(&value)
+    }
+}
+
+impl TryFrom<&str> for Timestamp {
+    type Error = TimestampError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Timestamp::parse(value)
+    }
+}
+
+impl From<Timestamp> for String {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rfc3339_with_offsets_into_utc() {
+        let timestamp = Timestamp::parse("2024-01-31T09:30:00.987+02:00").unwrap();
+        assert_eq!(timestamp.to_string(), "2024-01-31T07:30:00Z");
+        assert_eq!(Timestamp::parse(&timestamp.to_string()).unwrap(), timestamp);
+    }
+
+    #[test]
+    fn plain_dates_are_midnight_utc() {
+        let timestamp = Timestamp::parse_date_or_time("2024-03-01").unwrap();
+        assert_eq!(timestamp.to_string(), "2024-03-01T00:00:00Z");
+        assert_eq!(timestamp.date(), Date::new(2024, 3, 1).unwrap());
+        assert_eq!(timestamp.unix_seconds(), 1_709_251_200);
+    }
+
+    #[test]
+    fn rejects_times_without_a_zone() {
+        assert!(Timestamp::parse("2024-01-31T09:30:00").is_err());
+        assert!(Timestamp::parse("yesterday").is_err());
+    }
+}
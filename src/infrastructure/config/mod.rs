@@ -0,0 +1,3 @@
+pub mod profiles;
+
+pub use profiles::ProfileConfig;
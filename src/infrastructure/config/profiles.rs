@@ -0,0 +1,149 @@
+use crate::domain::repositories::RepositoryError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+/// Named phonebooks, stored as a `[profiles]` table of `name = "path"` entries
+/// in `profiles.toml`, e.g.
+///
+/// ```toml
+/// [profiles]
+/// work = "~/phonebooks/work.json"
+/// personal = "~/phonebooks/personal.json"
+/// ```
+///
+/// Other tables and keys are ignored, so the file can hold more settings later
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileConfig {
+    #[serde(default)]
+    profiles: BTreeMap<String, String>,
+}
+
+impl ProfileConfig {
+    /// `$XDG_CONFIG_HOME/phonebook/profiles.toml`, falling back to `~/.config/phonebook/profiles.toml`
+    pub fn default_path() -> Option<PathBuf> {
+        let config_dir = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(config_dir.join("phonebook").join("profiles.toml"))
+    }
+
+    /// Load the config at `path`; a missing file is an empty config
+    pub fn load(path: &Path) -> Result<Self, RepositoryError> {
+        match fs::read_to_string(path) {
+            Ok(text) => toml::from_str(&text).map_err(|e| {
+                RepositoryError::SerializationError(format!("Failed to parse {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(RepositoryError::IoError(format!(
+                "Failed to read {}: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+
+    /// Write the config to `path`, creating its directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), RepositoryError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                RepositoryError::IoError(format!("Failed to create directory {}: {}", parent.display(), e))
+            })?;
+        }
+
+        let text = toml::to_string(self)
+            .map_err(|e| RepositoryError::SerializationError(format!("Failed to serialize profiles: {}", e)))?;
+        fs::write(path, text)
+            .map_err(|e| RepositoryError::IoError(format!("Failed to write {}: {}", path.display(), e)))
+    }
+
+    /// Profiles by name, in name order
+    pub fn profiles(&self) -> &BTreeMap<String, String> {
+        &self.profiles
+    }
+
+    /// Contacts file of a profile, as written in the config
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.profiles.get(name).map(String::as_str)
+    }
+
+    /// Add or repoint a profile; returns whether it already existed
+    pub fn add(&mut self, name: &str, path: &str) -> Result<bool, RepositoryError> {
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            return Err(RepositoryError::ValidationError(format!(
+                "Invalid profile name '{}': use letters, digits, '-' and '_'",
+                name
+            )));
+        }
+        if path.trim().is_empty() {
+            return Err(RepositoryError::ValidationError("Profile path cannot be empty".to_string()));
+        }
+
+        Ok(self.profiles.insert(name.to_string(), path.to_string()).is_some())
+    }
+
+    /// Remove a profile; returns whether it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.profiles.remove(name).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles_round_trip_through_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("phonebook").join("profiles.toml");
+        let mut config = ProfileConfig::default();
+        config.add("work", "~/phonebooks/work.json").unwrap();
+        config.add("odd", r#"C:\phone "books"\odd.json"#).unwrap();
+
+        config.save(&path).unwrap();
+
+        assert_eq!(ProfileConfig::load(&path).unwrap(), config);
+    }
+
+    #[test]
+    fn hand_written_config_is_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profiles.toml");
+        fs::write(
+            &path,
+            "# my phonebooks\n[settings]\ncolor = true\n\n[profiles]\nwork = 'work.json' # literal\n\"home-2\" = \"home.json\"\n",
+        )
+        .unwrap();
+
+        let config = ProfileConfig::load(&path).unwrap();
+
+        assert_eq!(config.get("work"), Some("work.json"));
+        assert_eq!(config.get("home-2"), Some("home.json"));
+        assert_eq!(config.profiles().len(), 2);
+    }
+
+    #[test]
+    fn missing_file_is_empty_and_broken_file_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profiles.toml");
+        assert_eq!(ProfileConfig::load(&path).unwrap(), ProfileConfig::default());
+
+        fs::write(&path, "[profiles\nwork = ").unwrap();
+        assert!(matches!(ProfileConfig::load(&path), Err(RepositoryError::SerializationError(_))));
+    }
+
+    #[test]
+    fn invalid_names_and_empty_paths_are_rejected() {
+        let mut config = ProfileConfig::default();
+        assert!(config.add("my work", "work.json").is_err());
+        assert!(config.add("work", " ").is_err());
+        assert!(!config.add("work", "work.json").unwrap());
+        assert!(config.add("work", "other.json").unwrap());
+        assert!(config.remove("work"));
+        assert!(!config.remove("work"));
+    }
+}
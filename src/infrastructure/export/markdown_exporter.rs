@@ -0,0 +1,145 @@
+use crate::domain::entities::Contact;
+
+/// Exporter producing a GitHub-flavoured Markdown table, for pasting into docs
+/// Multiple phones or emails share a cell, separated by `<br>`
+pub struct MarkdownExporter;
+
+impl MarkdownExporter {
+    /// Export contacts as a table with ID, Name, Phones, Emails and Tags columns
+    pub fn export(contacts: &[Contact]) -> String {
+        let mut output = table(&["ID", "Name", "Phones", "Emails", "Tags"], contacts.iter().map(Self::cells));
+        output.push('\n');
+        output
+    }
+
+    /// Export a single contact as a table row
+    pub fn export_row(contact: &Contact) -> String {
+        row(&Self::cells(contact))
+    }
+
+    fn cells(contact: &Contact) -> Vec<String> {
+        let phones: Vec<String> = contact
+            .phone_numbers()
+            .iter()
+            .map(|p| match p.extension() {
+                Some(extension) => format!("{} x{}", escape(p.value()), extension),
+                None => escape(p.value()),
+            })
+            .collect();
+        let emails: Vec<String> = contact.emails().iter().map(|e| escape(e.value())).collect();
+        let tags: Vec<String> = contact.tags().iter().map(|t| escape(t)).collect();
+
+        vec![
+            contact.id().to_string(),
+            escape(&contact.full_name()),
+            phones.join("<br>"),
+            emails.join("<br>"),
+            tags.join(", "),
+        ]
+    }
+}
+
+/// Build a GitHub-flavoured Markdown table from a header and rows of already escaped cells
+/// Lines are joined with newlines, without a trailing one
+pub fn table(headers: &[&str], rows: impl IntoIterator<Item = Vec<String>>) -> String {
+    let separators: Vec<String> = headers.iter().map(|header| "-".repeat(header.len() + 2)).collect();
+    let mut lines = vec![
+        row(&headers.iter().map(|header| header.to_string()).collect::<Vec<_>>()),
+        format!("|{}|", separators.join("|")),
+    ];
+    lines.extend(rows.into_iter().map(|cells| row(&cells)));
+    lines.join("\n")
+}
+
+fn row(cells: &[String]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+/// Escape characters that Markdown would otherwise interpret inside table cells and text,
+/// so a value can't end a cell or row early or render as emphasis, code, links or HTML
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\r' | '\n' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
+
+    const ID: &str = "abcd1234-0000-4000-8000-000000000001";
+
+    fn contact(first_name: &str, last_name: &str) -> Contact {
+        Contact::with_id(
+            ContactId::parse(ID).unwrap(),
+            first_name.to_string(),
+            last_name.to_string(),
+            vec![
+                PhoneNumber::new("5551234567".to_string()).unwrap(),
+                PhoneNumber::new("5559876543 x12".to_string()).unwrap(),
+            ],
+            vec![
+                Email::new("ann@example.com".to_string()).unwrap(),
+                Email::new("ann@work.example.com".to_string()).unwrap(),
+            ],
+        )
+    }
+
+    #[test]
+    fn export_starts_with_the_header_rows() {
+        let output = MarkdownExporter::export(&[]);
+
+        assert_eq!(output, "| ID | Name | Phones | Emails | Tags |\n|----|------|--------|--------|------|\n");
+    }
+
+    #[test]
+    fn rows_join_multiple_values_and_escape_pipes() {
+        let mut ann = contact("Ann", "Pipe|Smith");
+        ann.add_tag("a|b".to_string());
+        ann.add_tag("work".to_string());
+
+        let output = MarkdownExporter::export(&[ann]);
+
+        assert_eq!(
+            output.lines().nth(2),
+            Some(
+                format!(
+                    "| {} | Ann Pipe\\|Smith | 5551234567<br>5559876543 x12 | ann@example.com<br>ann@work.example.com | a\\|b, work |",
+                    ID
+                )
+                .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn backslashes_and_newlines_cannot_break_a_cell() {
+        let mut ann = contact("Ann", "Two\nLines");
+        ann.add_tag("back\\|slash".to_string());
+
+        let row = MarkdownExporter::export_row(&ann);
+
+        assert!(row.contains("| Ann Two Lines |"), "{}", row);
+        assert!(row.ends_with("| back\\\\\\|slash |"), "{}", row);
+        assert_eq!(row.lines().count(), 1);
+    }
+
+    #[test]
+    fn markup_in_names_is_escaped() {
+        let ann = contact("<b>Ann</b>", "*Tester*");
+
+        let row = MarkdownExporter::export_row(&ann);
+
+        assert!(row.contains("| \\<b\\>Ann\\</b\\> \\*Tester\\* |"), "{}", row);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod markdown_exporter;
+pub mod vcard_exporter;
+
+pub use markdown_exporter::MarkdownExporter;
+pub use vcard_exporter::VCardExporter;
@@ -0,0 +1,199 @@
+use crate::domain::entities::Contact;
+use crate::domain::repositories::RepositoryError;
+use std::fs;
+use std::path::Path;
+
+/// Exporter producing vCard 3.0 documents
+/// Each contact becomes one BEGIN:VCARD ... END:VCARD block
+pub struct VCardExporter;
+
+impl VCardExporter {
+    /// Export contacts as a single vCard document
+    pub fn export(contacts: &[Contact]) -> String {
+        contacts.iter().map(Self::export_contact).collect()
+    }
+
+    /// Write one `<first>_<last>_<id8>.vcf` file per contact into `dir`, creating it if needed
+    /// Unless `overwrite` is set, nothing is written when any of the files already exists
+    /// Returns the number of files written
+    pub fn export_to_dir(contacts: &[Contact], dir: &Path, overwrite: bool) -> Result<usize, RepositoryError> {
+        let files: Vec<_> = contacts.iter().map(|contact| (dir.join(Self::file_name(contact)), contact)).collect();
+        if !overwrite {
+            if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+                return Err(RepositoryError::StorageError(format!(
+                    "{} already exists; pass --force to replace existing files",
+                    path.display()
+                )));
+            }
+        }
+
+        fs::create_dir_all(dir)
+            .map_err(|e| RepositoryError::IoError(format!("Failed to create directory {}: {}", dir.display(), e)))?;
+
+        for (path, contact) in &files {
+            fs::write(path, Self::export_contact(contact))
+                .map_err(|e| RepositoryError::IoError(format!("Failed to write {}: {}", path.display(), e)))?;
+        }
+
+        Ok(files.len())
+    }
+
+    /// File name used by `export_to_dir`; the ID prefix keeps namesakes apart
+    /// Name parts left empty by sanitizing are skipped rather than leaving stray `_`s
+    pub fn file_name(contact: &Contact) -> String {
+        let id: String = contact.id().to_string().chars().take(8).collect();
+        let parts: Vec<String> = [contact.first_name(), contact.last_name()]
+            .into_iter()
+            .map(Self::sanitize_file_component)
+            .filter(|part| !part.is_empty())
+            .chain(std::iter::once(id))
+            .collect();
+        format!("{}.vcf", parts.join("_"))
+    }
+
+    /// Drop path separators, characters reserved on common filesystems and control
+    /// characters, turn whitespace into `-`, and strip leading dots
+    fn sanitize_file_component(value: &str) -> String {
+        let kept: String = value
+            .chars()
+            .filter(|c| !matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') && !c.is_control())
+            .collect();
+
+        kept.trim()
+            .trim_start_matches('.')
+            .chars()
+            .map(|c| if c.is_whitespace() { '-' } else { c })
+            .collect()
+    }
+
+    /// Export a single contact as a vCard block
+    pub fn export_contact(contact: &Contact) -> String {
+        let mut lines = vec![
+            "BEGIN:VCARD".to_string(),
+            "VERSION:3.0".to_string(),
+            format!(
+                "N:{};{};;;",
+                Self::escape(contact.last_name()),
+                Self::escape(contact.first_name())
+            ),
+            format!("FN:{}", Self::escape(&contact.full_name())),
+        ];
+
+        for phone in contact.phone_numbers() {
+            match phone.extension() {
+                Some(extension) => lines.push(format!("TEL:{} x{}", phone.value(), extension)),
+                None => lines.push(format!("TEL:{}", phone.value())),
+            }
+        }
+
+        for email in contact.emails() {
+            match email.label() {
+                Some(label) => lines.push(format!("EMAIL;TYPE={}:{}", label, email.value())),
+                None => lines.push(format!("EMAIL:{}", email.value())),
+            }
+        }
+
+        if let Some(birthday) = contact.birthday() {
+            lines.push(format!("BDAY:{}", birthday));
+        }
+
+        if let Some(photo_path) = contact.photo_path() {
+            lines.push(format!("PHOTO;VALUE=URI:file://{}", photo_path.display()));
+        }
+
+        if let Some(notes) = contact.notes() {
+            lines.push(format!("NOTE:{}", Self::escape(notes)));
+        }
+
+        if !contact.tags().is_empty() {
+            let tags: Vec<String> = contact.tags().iter().map(|t| Self::escape(t)).collect();
+            lines.push(format!("CATEGORIES:{}", tags.join(",")));
+        }
+
+        lines.push(format!("UID:{}", contact.id()));
+        lines.push("END:VCARD".to_string());
+
+        let mut output = lines.join("\r\n");
+        output.push_str("\r\n");
+        output
+    }
+
+    /// Escape text values according to RFC 2426
+    fn escape(value: &str) -> String {
+        value
+            .replace('\\', "\\\\")
+            .replace(',', "\\,")
+            .replace(';', "\\;")
+            .replace('\n', "\\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
+
+    fn contact(first_name: &str, last_name: &str) -> Contact {
+        Contact::with_id(
+            ContactId::parse("abcd1234-0000-4000-8000-000000000001").unwrap(),
+            first_name.to_string(),
+            last_name.to_string(),
+            vec![
+                PhoneNumber::new("5551234567".to_string()).unwrap(),
+                PhoneNumber::new("5559876543 x12".to_string()).unwrap(),
+            ],
+            vec![Email::parse_labeled("work:ann@example.com").unwrap()],
+        )
+    }
+
+    #[test]
+    fn contact_with_two_phones_and_an_escaped_name_exports_exactly() {
+        let mut ann = contact("Ann", "Smith, Jr.; Esq");
+        ann.set_notes(Some("Line one\nback\\slash".to_string()));
+        ann.add_tag("work".to_string());
+        ann.add_tag("a,b".to_string());
+
+        assert_eq!(
+            VCardExporter::export_contact(&ann),
+            "BEGIN:VCARD\r\n\
+             VERSION:3.0\r\n\
+             N:Smith\\, Jr.\\; Esq;Ann;;;\r\n\
+             FN:Ann Smith\\, Jr.\\; Esq\r\n\
+             TEL:5551234567\r\n\
+             TEL:5559876543 x12\r\n\
+             EMAIL;TYPE=work:ann@example.com\r\n\
+             NOTE:Line one\\nback\\\\slash\r\n\
+             CATEGORIES:work,a\\,b\r\n\
+             UID:abcd1234-0000-4000-8000-000000000001\r\n\
+             END:VCARD\r\n"
+        );
+    }
+
+    #[test]
+    fn file_names_are_sanitized_and_skip_empty_name_parts() {
+        assert_eq!(VCardExporter::file_name(&contact("Ann", "Smith")), "Ann_Smith_abcd1234.vcf");
+        assert_eq!(VCardExporter::file_name(&contact("Mary Ann", "../O'Neil")), "Mary-Ann_O'Neil_abcd1234.vcf");
+        assert_eq!(VCardExporter::file_name(&contact("a/b\\c", "x:y*z?")), "abc_xyz_abcd1234.vcf");
+        assert_eq!(VCardExporter::file_name(&contact("Cher", "/")), "Cher_abcd1234.vcf");
+        assert_eq!(VCardExporter::file_name(&contact("..", "\"<>|\"")), "abcd1234.vcf");
+    }
+
+    #[test]
+    fn export_to_dir_refuses_to_overwrite_unless_asked() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("vcf");
+        let ann = contact("Ann", "Smith");
+        let path = out.join("Ann_Smith_abcd1234.vcf");
+
+        assert_eq!(VCardExporter::export_to_dir(std::slice::from_ref(&ann), &out, false).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), VCardExporter::export_contact(&ann));
+
+        fs::write(&path, "edited").unwrap();
+        let refused = VCardExporter::export_to_dir(std::slice::from_ref(&ann), &out, false);
+        assert!(matches!(refused, Err(RepositoryError::StorageError(message)) if message.contains("--force")));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited");
+
+        assert_eq!(VCardExporter::export_to_dir(std::slice::from_ref(&ann), &out, true).unwrap(), 1);
+        assert_eq!(fs::read_to_string(&path).unwrap(), VCardExporter::export_contact(&ann));
+    }
+}
@@ -0,0 +1,228 @@
+use crate::domain::repositories::RepositoryError;
+use std::io::Read;
+
+/// One data row of an imported CSV file, with cells still unparsed
+/// Columns missing from the file come through as empty strings
+#[derive(Debug, Default, Clone)]
+pub struct CsvRecord {
+    /// 1-based line number the row starts on, for error messages
+    pub line: usize,
+    pub id: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub phones: String,
+    pub emails: String,
+    pub notes: String,
+    pub tags: String,
+    pub birthday: String,
+}
+
+/// Importer reading contacts from CSV with a header row
+/// Recognised columns are id, first_name, last_name, phone(s), email(s), notes,
+/// tags and birthday (case-insensitive; spaces and dashes count as underscores);
+/// other columns are ignored
+pub struct CsvImporter;
+
+impl CsvImporter {
+    /// Read all records from `reader`; empty input yields no records
+    pub fn import<R: Read>(mut reader: R) -> Result<Vec<CsvRecord>, RepositoryError> {
+        let mut input = String::new();
+        reader
+            .read_to_string(&mut input)
+            .map_err(|e| RepositoryError::IoError(format!("Failed to read CSV: {}", e)))?;
+
+        let mut rows = Self::parse_rows(&input)?.into_iter();
+        let Some((_, header)) = rows.next() else {
+            return Ok(Vec::new());
+        };
+
+        let columns: Vec<Option<Column>> = header.iter().map(|name| Column::from_header(name)).collect();
+        for required in [Column::FirstName, Column::LastName] {
+            if !columns.contains(&Some(required)) {
+                return Err(RepositoryError::SerializationError(format!(
+                    "CSV header is missing the {} column",
+                    required.name()
+                )));
+            }
+        }
+
+        let mut records = Vec::new();
+        for (line, row) in rows {
+            if row.iter().all(|cell| cell.trim().is_empty()) {
+                continue;
+            }
+
+            let mut record = CsvRecord {
+                line,
+                ..Default::default()
+            };
+            for (column, cell) in columns.iter().zip(row) {
+                let Some(column) = column else {
+                    continue;
+                };
+                let target = match column {
+                    Column::Id => &mut record.id,
+                    Column::FirstName => &mut record.first_name,
+                    Column::LastName => &mut record.last_name,
+                    Column::Phones => &mut record.phones,
+                    Column::Emails => &mut record.emails,
+                    Column::Notes => &mut record.notes,
+                    Column::Tags => &mut record.tags,
+                    Column::Birthday => &mut record.birthday,
+                };
+                *target = cell.trim().to_string();
+            }
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    /// Split CSV text into rows of cells, tagged with their starting line
+    /// Handles quoted cells with embedded commas, newlines and doubled quotes
+    fn parse_rows(input: &str) -> Result<Vec<(usize, Vec<String>)>, RepositoryError> {
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        let mut cell = String::new();
+        let mut in_quotes = false;
+        let mut line = 1;
+        let mut row_start = 1;
+        let mut chars = input.strip_prefix('\u{feff}').unwrap_or(input).chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match (in_quotes, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    cell.push('"');
+                }
+                (true, '"') => in_quotes = false,
+                (true, c) => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    cell.push(c);
+                }
+                (false, '"') if cell.is_empty() => in_quotes = true,
+                (false, ',') => row.push(std::mem::take(&mut cell)),
+                (false, '\r') if chars.peek() == Some(&'\n') => {}
+                (false, '\n') => {
+                    row.push(std::mem::take(&mut cell));
+                    rows.push((row_start, std::mem::take(&mut row)));
+                    line += 1;
+                    row_start = line;
+                }
+                (false, c) => cell.push(c),
+            }
+        }
+
+        if in_quotes {
+            return Err(RepositoryError::SerializationError(format!(
+                "Unterminated quoted field starting on line {}",
+                row_start
+            )));
+        }
+
+        if !cell.is_empty() || !row.is_empty() {
+            row.push(cell);
+            rows.push((row_start, row));
+        }
+
+        Ok(rows)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Column {
+    Id,
+    FirstName,
+    LastName,
+    Phones,
+    Emails,
+    Notes,
+    Tags,
+    Birthday,
+}
+
+impl Column {
+    fn from_header(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace([' ', '-'], "_").as_str() {
+            "id" => Some(Column::Id),
+            "first_name" | "first" => Some(Column::FirstName),
+            "last_name" | "last" => Some(Column::LastName),
+            "phone" | "phones" | "phone_numbers" => Some(Column::Phones),
+            "email" | "emails" => Some(Column::Emails),
+            "notes" => Some(Column::Notes),
+            "tags" => Some(Column::Tags),
+            "birthday" => Some(Column::Birthday),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::FirstName => "first_name",
+            Column::LastName => "last_name",
+            Column::Phones => "phones",
+            Column::Emails => "emails",
+            Column::Notes => "notes",
+            Column::Tags => "tags",
+            Column::Birthday => "birthday",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn import(input: &str) -> Result<Vec<CsvRecord>, RepositoryError> {
+        CsvImporter::import(Cursor::new(input.as_bytes()))
+    }
+
+    #[test]
+    fn empty_input_yields_no_records() {
+        assert!(import("").unwrap().is_empty());
+        assert!(import("first_name,last_name\n").unwrap().is_empty());
+        assert!(import("first_name,last_name\n,\n\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn columns_are_matched_by_header_name() {
+        let input = "\u{feff}Last Name,first-name,Phone,colour,email\r\nLovelace,Ada,5551234567,red,ada@example.com\r\n";
+        let records = import(input).unwrap();
+
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.line, 2);
+        assert_eq!(record.first_name, "Ada");
+        assert_eq!(record.last_name, "Lovelace");
+        assert_eq!(record.phones, "5551234567");
+        assert_eq!(record.emails, "ada@example.com");
+        assert!(record.notes.is_empty() && record.tags.is_empty());
+    }
+
+    #[test]
+    fn quoted_cells_keep_commas_quotes_and_newlines() {
+        let input = "first_name,last_name,notes\nAda,Lovelace,\"Wrote \"\"Notes\"\",\nfirst program\"\nCharles,Babbage,\n";
+        let records = import(input).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].notes, "Wrote \"Notes\",\nfirst program");
+        assert_eq!(records[1].first_name, "Charles");
+        assert_eq!(records[1].line, 4, "line numbers count the newline inside the quoted cell");
+    }
+
+    #[test]
+    fn malformed_input_is_an_error() {
+        assert!(matches!(
+            import("first_name,notes\nAda,x\n"),
+            Err(RepositoryError::SerializationError(message)) if message.contains("last_name")
+        ));
+        assert!(matches!(
+            import("first_name,last_name\nAda,\"Lovelace\n"),
+            Err(RepositoryError::SerializationError(_))
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod csv_importer;
+
+pub use csv_importer::{CsvImporter, CsvRecord};
@@ -1,5 +1,13 @@
 pub mod repositories;
 pub mod persistence;
+pub mod export;
+pub mod import;
+pub mod config;
+#[cfg(feature = "watch")]
+pub mod watch;
 
 pub use repositories::*;
 pub use persistence::*;
+pub use export::*;
+pub use import::*;
+pub use config::*;
@@ -0,0 +1,61 @@
+use crate::domain::repositories::RepositoryError;
+use argon2::Argon2;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+
+use super::file_storage::ENCRYPTED_MAGIC;
+
+/// Length of the random Argon2 salt stored after the magic header
+const SALT_LEN: usize = 16;
+
+/// Length of the ChaCha20-Poly1305 nonce stored after the salt
+const NONCE_LEN: usize = 12;
+
+/// Encrypt a serialized contacts file with a key derived from `passphrase`
+/// The result is the magic header, a fresh salt and nonce, then the ciphertext.
+pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, RepositoryError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = cipher(passphrase, &salt)?;
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| RepositoryError::StorageError("Failed to encrypt the contacts file".to_string()))?;
+
+    let mut output = Vec::with_capacity(ENCRYPTED_MAGIC.len() + SALT_LEN + NONCE_LEN + ciphertext.len());
+    output.extend_from_slice(ENCRYPTED_MAGIC);
+    output.extend_from_slice(&salt);
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Decrypt a file written by `encrypt`
+/// A wrong passphrase (or a tampered file) fails authentication and is
+/// reported as `RepositoryError::WrongPassphrase` rather than returning garbage
+pub fn decrypt(data: &[u8], passphrase: &str, file_path: &str) -> Result<Vec<u8>, RepositoryError> {
+    let header_len = ENCRYPTED_MAGIC.len() + SALT_LEN + NONCE_LEN;
+    if data.len() < header_len || !data.starts_with(ENCRYPTED_MAGIC) {
+        return Err(RepositoryError::SerializationError(format!(
+            "{} is not a valid encrypted contacts file",
+            file_path
+        )));
+    }
+
+    let salt = &data[ENCRYPTED_MAGIC.len()..ENCRYPTED_MAGIC.len() + SALT_LEN];
+    let nonce = Nonce::from_slice(&data[ENCRYPTED_MAGIC.len() + SALT_LEN..header_len]);
+    cipher(passphrase, salt)?
+        .decrypt(nonce, &data[header_len..])
+        .map_err(|_| RepositoryError::WrongPassphrase(file_path.to_string()))
+}
+
+/// Derive the file key from the passphrase with Argon2id
+fn cipher(passphrase: &str, salt: &[u8]) -> Result<ChaCha20Poly1305, RepositoryError> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| RepositoryError::StorageError(format!("Failed to derive the encryption key: {}", e)))?;
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&key)))
+}
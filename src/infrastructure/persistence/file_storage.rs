@@ -1,43 +1,173 @@
-use crate::domain::entities::Contact;
+use crate::domain::entities::{Contact, Group};
 use crate::domain::repositories::RepositoryError;
 use crate::domain::value_objects::ContactId;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fs;
+use serde::de::{MapAccess, Visitor};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Cursor, ErrorKind, Read, Write};
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// First bytes of a contacts file encrypted with the `encrypt` feature,
+/// followed by the key-derivation salt, the nonce and the ciphertext
+pub const ENCRYPTED_MAGIC: &[u8] = b"DPBKENC1";
+
+/// On-disk format of a contacts file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StorageFormat {
+    #[default]
+    Json,
+    /// Gzip-compressed JSON, for files ending in `.json.gz`
+    JsonGzip,
+    /// Human-editable TOML, for files ending in `.toml`
+    Toml,
+}
+
+impl StorageFormat {
+    /// Pick the format from the file extension; anything but `.json.gz` or `.toml` is JSON
+    pub fn from_path(path: &str) -> Self {
+        let name = Path::new(path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if name.ends_with(".json.gz") {
+            StorageFormat::JsonGzip
+        } else if name.ends_with(".toml") {
+            StorageFormat::Toml
+        } else {
+            StorageFormat::Json
+        }
+    }
+}
 
 /// File-based storage implementation
-/// Handles serialization and persistence of contacts to JSON file
+/// Handles serialization and persistence of contacts to a JSON, gzipped JSON or TOML file
 #[derive(Debug)]
 pub struct FileStorage {
     file_path: String,
+    format: StorageFormat,
+    recover: bool,
+    #[cfg(feature = "encrypt")]
+    passphrase: Option<String>,
+    /// Groups as last read or written, so saving contacts doesn't re-read
+    /// (and re-decrypt) the file just for its groups
+    groups: Mutex<Option<KnownGroups>>,
+}
+
+/// The groups of the file when it had the modification time `modified`
+#[derive(Debug)]
+struct KnownGroups {
+    modified: Option<SystemTime>,
+    groups: BTreeMap<String, Group>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 struct ContactsData {
     contacts: HashMap<String, Contact>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    groups: BTreeMap<String, Group>,
+}
+
+/// A snapshot of contacts: either a whole contacts file or an `export --format json` array
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SnapshotData {
+    Export(Vec<Contact>),
+    File(ContactsData),
+}
+
+/// The file's entries as untyped JSON, so each one can be deserialized (or skipped) on its own
+#[derive(Debug, Deserialize)]
+struct LenientContactsData {
+    #[serde(default)]
+    contacts: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    groups: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Every `contacts` entry exactly as written in the file, including repeated keys
+#[derive(Debug, Deserialize)]
+struct RawContactsData {
+    contacts: RawEntries,
+}
+
+#[derive(Debug)]
+struct RawEntries(Vec<(String, serde_json::Value)>);
+
+/// A stored `(key, contact)` entry; the contact is the deserialization error when it doesn't parse
+pub type RawEntry = (String, Result<Contact, String>);
+
+impl<'de> Deserialize<'de> for RawEntries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> Visitor<'de> for EntriesVisitor {
+            type Value = RawEntries;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map of contact IDs to contacts")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(RawEntries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
 }
 
 impl FileStorage {
     pub fn new(file_path: String) -> Self {
-        Self { file_path }
+        Self {
+            format: StorageFormat::from_path(&file_path),
+            file_path,
+            recover: false,
+            #[cfg(feature = "encrypt")]
+            passphrase: None,
+            groups: Mutex::new(None),
+        }
     }
 
-    /// Load all contacts from file
-    pub fn load_contacts(&self) -> Result<HashMap<ContactId, Contact>, RepositoryError> {
-        if !Path::new(&self.file_path).exists() {
-            return Ok(HashMap::new());
-        }
+    /// Skip entries that fail to deserialize instead of failing the whole load
+    pub fn with_recovery(mut self, recover: bool) -> Self {
+        self.recover = recover;
+        self
+    }
 
-        let content = fs::read_to_string(&self.file_path)
-            .map_err(|e| RepositoryError::IoError(format!("Failed to read file: {}", e)))?;
+    /// Decrypt the file with this passphrase, and encrypt it on every save
+    /// A plain file is still read as is, and becomes encrypted the next time it is saved
+    #[cfg(feature = "encrypt")]
+    pub fn with_passphrase(mut self, passphrase: Option<String>) -> Self {
+        self.passphrase = passphrase;
+        self
+    }
 
-        if content.trim().is_empty() {
-            return Ok(HashMap::new());
+    /// Load all contacts from file
+    pub fn load_contacts(&self) -> Result<HashMap<ContactId, Contact>, RepositoryError> {
+        if self.recover {
+            let (contacts, warnings) = self.load_contacts_lenient()?;
+            for warning in warnings {
+                log::warn!("{}", warning);
+            }
+            return Ok(contacts);
         }
 
-        let data: ContactsData = serde_json::from_str(&content)
-            .map_err(|e| RepositoryError::SerializationError(format!("Failed to deserialize: {}", e)))?;
+        let data = self.load_data()?;
 
         let mut contacts = HashMap::new();
         for (id_str, contact) in data.contacts {
@@ -49,33 +179,616 @@ impl FileStorage {
         Ok(contacts)
     }
 
-    /// Save all contacts to file
-    pub fn save_contacts(&self, contacts: &HashMap<ContactId, Contact>) -> Result<(), RepositoryError> {
-        // Create directory if it doesn't exist
-        if let Some(parent) = Path::new(&self.file_path).parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| RepositoryError::IoError(format!("Failed to create directory: {}", e)))?;
+    /// Load the contacts of a snapshot, which may be a contacts file or a JSON export
+    pub fn load_snapshot(&self) -> Result<Vec<Contact>, RepositoryError> {
+        Ok(match self.read_document::<SnapshotData>()? {
+            Some(SnapshotData::Export(contacts)) => contacts,
+            Some(SnapshotData::File(data)) => data.contacts.into_values().collect(),
+            None => Vec::new(),
+        })
+    }
+
+    /// Load all groups from file, keyed by name
+    pub fn load_groups(&self) -> Result<BTreeMap<String, Group>, RepositoryError> {
+        Ok(self.load_data()?.groups)
+    }
+
+    /// Load the contacts that can be read, skipping malformed entries
+    /// Returns the salvaged contacts and one warning per skipped contact or group
+    pub fn load_contacts_lenient(&self) -> Result<(HashMap<ContactId, Contact>, Vec<String>), RepositoryError> {
+        let (data, warnings) = self.load_data_lenient()?;
+
+        let contacts = data
+            .contacts
+            .into_iter()
+            .map(|(id_str, contact)| {
+                // Keys were checked while parsing leniently
+                let id = uuid::Uuid::parse_str(&id_str).expect("validated contact ID");
+                (ContactId::from_uuid(id), contact)
+            })
+            .collect();
+
+        Ok((contacts, warnings))
+    }
+
+    /// Read and parse the whole file
+    fn load_data(&self) -> Result<ContactsData, RepositoryError> {
+        let modified = self.modified_time()?;
+        let data = if self.recover {
+            self.load_data_lenient()?.0
+        } else {
+            match self.read_document()? {
+                Some(data) => data,
+                None => {
+                    log::debug!("{} is missing or empty; starting fresh", self.file_path);
+                    ContactsData::default()
+                }
+            }
+        };
+        *self.groups.lock().unwrap() = Some(KnownGroups {
+            modified,
+            groups: data.groups.clone(),
+        });
+
+        log::debug!(
+            "Loaded {} contact(s) and {} group(s) from {}",
+            data.contacts.len(),
+            data.groups.len(),
+            self.file_path
+        );
+        Ok(data)
+    }
+
+    /// Parse the file entry by entry, dropping contacts and groups that don't deserialize
+    /// The document itself must still be valid JSON; only individual entries are skipped
+    fn load_data_lenient(&self) -> Result<(ContactsData, Vec<String>), RepositoryError> {
+        let Some(raw) = self.read_document::<LenientContactsData>()? else {
+            return Ok((ContactsData::default(), Vec::new()));
+        };
+
+        let mut data = ContactsData::default();
+        let mut warnings = Vec::new();
+
+        for (id_str, value) in raw.contacts {
+            if let Err(e) = uuid::Uuid::parse_str(&id_str) {
+                warnings.push(format!("Skipping contact '{}': invalid ID: {}", id_str, e));
+                continue;
+            }
+            match serde_json::from_value::<Contact>(value) {
+                Ok(contact) => {
+                    data.contacts.insert(id_str, contact);
+                }
+                Err(e) => warnings.push(format!("Skipping contact '{}': {}", id_str, e)),
+            }
+        }
+
+        for (name, value) in raw.groups {
+            match serde_json::from_value::<Group>(value) {
+                Ok(group) => {
+                    data.groups.insert(name, group);
+                }
+                Err(e) => warnings.push(format!("Skipping group '{}': {}", name, e)),
+            }
         }
 
+        Ok((data, warnings))
+    }
+
+    /// Read and deserialize the whole file in its format
+    /// Returns `None` when the file is missing or holds only whitespace
+    fn read_document<T: DeserializeOwned>(&self) -> Result<Option<T>, RepositoryError> {
+        let deserialize_error = |e: String| RepositoryError::SerializationError(format!("Failed to deserialize: {}", e));
+
+        let Some(mut reader) = self.open_reader()? else {
+            return Ok(None);
+        };
+
+        match self.format {
+            StorageFormat::Json | StorageFormat::JsonGzip => {
+                serde_json::from_reader(reader).map(Some).map_err(|e| deserialize_error(e.to_string()))
+            }
+            StorageFormat::Toml => {
+                let mut text = String::new();
+                reader
+                    .read_to_string(&mut text)
+                    .map_err(|e| RepositoryError::IoError(format!("Failed to read {}: {}", self.file_path, e)))?;
+
+                toml::from_str(&text).map(Some).map_err(|e| deserialize_error(e.to_string()))
+            }
+        }
+    }
+
+    /// Open the file for streaming deserialization, so its text is never held in memory whole
+    /// Gzipped files are decompressed on the fly. Encrypted files are the exception:
+    /// they are read and decrypted in full first, since the whole file is authenticated at once.
+    /// Returns `None` when the file is missing or holds only whitespace
+    fn open_reader(&self) -> Result<Option<Box<dyn BufRead>>, RepositoryError> {
+        let read_error =
+            |e: std::io::Error| RepositoryError::IoError(format!("Failed to read {}: {}", self.file_path, e));
+
+        let file = match File::open(&self.file_path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(read_error(e)),
+        };
+
+        let mut source: Box<dyn BufRead> = Box::new(BufReader::new(file));
+        if source.fill_buf().map_err(read_error)?.starts_with(ENCRYPTED_MAGIC) {
+            let mut data = Vec::new();
+            source.read_to_end(&mut data).map_err(read_error)?;
+            source = Box::new(Cursor::new(self.decrypt(&data)?));
+        }
+
+        let mut reader: Box<dyn BufRead> = match self.format {
+            // An empty file is an empty phonebook, not a truncated gzip stream
+            StorageFormat::JsonGzip if source.fill_buf().map_err(read_error)?.is_empty() => return Ok(None),
+            StorageFormat::JsonGzip => Box::new(BufReader::new(GzDecoder::new(source))),
+            _ => source,
+        };
+
+        // Skip leading whitespace; if that's all there is, treat the file as empty
+        loop {
+            let buffer = reader.fill_buf().map_err(read_error)?;
+            if buffer.is_empty() {
+                return Ok(None);
+            }
+
+            let whitespace = buffer.iter().take_while(|b| b.is_ascii_whitespace()).count();
+            let done = whitespace < buffer.len();
+            reader.consume(whitespace);
+            if done {
+                return Ok(Some(reader));
+            }
+        }
+    }
+
+    /// Load every entry of the file as `(key, contact)` pairs without any checks
+    /// Unlike `load_contacts`, repeated keys, invalid UUIDs and entries that don't
+    /// deserialize are kept so they can be reported; the latter carry the error message
+    pub fn load_raw_entries(&self) -> Result<Vec<RawEntry>, RepositoryError> {
+        let Some(data) = self.read_document::<RawContactsData>()? else {
+            return Ok(Vec::new());
+        };
+
+        Ok(data
+            .contacts
+            .0
+            .into_iter()
+            .map(|(key, value)| (key, serde_json::from_value(value).map_err(|e| e.to_string())))
+            .collect())
+    }
+
+    /// Save all contacts to file, keeping the stored groups
+    /// The groups last read or written are reused unless the file changed since
+    pub fn save_contacts(&self, contacts: &HashMap<ContactId, Contact>) -> Result<(), RepositoryError> {
+        let modified = self.modified_time()?;
+        let known = match &*self.groups.lock().unwrap() {
+            Some(known) if known.modified == modified => Some(known.groups.clone()),
+            _ => None,
+        };
+        let groups = match known {
+            Some(groups) => {
+                log::debug!("Keeping the {} known group(s) of {}", groups.len(), self.file_path);
+                groups
+            }
+            None => self.load_groups()?,
+        };
+
         let mut data = ContactsData {
             contacts: HashMap::new(),
+            groups,
         };
 
         for (id, contact) in contacts {
             data.contacts.insert(id.to_string(), contact.clone());
         }
 
-        let json = serde_json::to_string_pretty(&data)
-            .map_err(|e| RepositoryError::SerializationError(format!("Failed to serialize: {}", e)))?;
+        self.save_data(&data)
+    }
+
+    /// Write an empty phonebook, replacing whatever the file held
+    pub fn create_empty(&self) -> Result<(), RepositoryError> {
+        self.save_data(&ContactsData::default())
+    }
+
+    /// Save all groups to file, keeping the stored contacts
+    pub fn save_groups(&self, groups: &BTreeMap<String, Group>) -> Result<(), RepositoryError> {
+        let mut data = self.load_data()?;
+        data.groups = groups.clone();
+        self.save_data(&data)
+    }
+
+    /// Serialize and write the whole file
+    fn save_data(&self, data: &ContactsData) -> Result<(), RepositoryError> {
+        // Create directory if it doesn't exist
+        if let Some(parent) = Path::new(&self.file_path).parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                RepositoryError::IoError(format!("Failed to create directory {}: {}", parent.display(), e))
+            })?;
+        }
+
+        let serialize_error = |e: String| RepositoryError::SerializationError(format!("Failed to serialize: {}", e));
+        let bytes = match self.format {
+            StorageFormat::Json => serde_json::to_vec_pretty(data).map_err(|e| serialize_error(e.to_string()))?,
+            StorageFormat::JsonGzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                serde_json::to_writer(&mut encoder, data).map_err(|e| serialize_error(e.to_string()))?;
+                encoder
+                    .finish()
+                    .map_err(|e| RepositoryError::IoError(format!("Failed to compress {}: {}", self.file_path, e)))?
+            }
+            StorageFormat::Toml => toml::to_string_pretty(data)
+                .map_err(|e| serialize_error(e.to_string()))?
+                .into_bytes(),
+        };
+        #[cfg(feature = "encrypt")]
+        let bytes = match &self.passphrase {
+            Some(passphrase) => super::encryption::encrypt(&bytes, passphrase)?,
+            None => bytes,
+        };
+
+        // Saving a recovered file drops the entries that were skipped, so keep the original first
+        if self.recover && self.strict().load_data().is_err() {
+            let backup_path = format!("{}.corrupt", self.file_path);
+            fs::copy(&self.file_path, &backup_path).map_err(|e| {
+                RepositoryError::IoError(format!("Failed to back up {} to {}: {}", self.file_path, backup_path, e))
+            })?;
+            log::warn!("Kept the original file with unreadable entries at {}", backup_path);
+        }
+
+        // Write a sibling file and rename it over the original, so a crash
+        // mid-write never leaves a truncated contacts file behind
+        let temp_path = format!("{}.tmp", self.file_path);
+        fs::write(&temp_path, bytes)
+            .map_err(|e| RepositoryError::IoError(format!("Failed to write {}: {}", temp_path, e)))?;
+        fs::rename(&temp_path, &self.file_path)
+            .map_err(|e| RepositoryError::IoError(format!("Failed to replace {}: {}", self.file_path, e)))?;
 
-        fs::write(&self.file_path, json)
-            .map_err(|e| RepositoryError::IoError(format!("Failed to write file: {}", e)))?;
+        *self.groups.lock().unwrap() = Some(KnownGroups {
+            modified: self.modified_time()?,
+            groups: data.groups.clone(),
+        });
 
+        log::debug!(
+            "Saved {} contact(s) and {} group(s) to {}",
+            data.contacts.len(),
+            data.groups.len(),
+            self.file_path
+        );
         Ok(())
     }
 
+    /// Decrypt the contents of an encrypted file
+    #[cfg(feature = "encrypt")]
+    fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, RepositoryError> {
+        match &self.passphrase {
+            Some(passphrase) => super::encryption::decrypt(data, passphrase, &self.file_path),
+            None => Err(RepositoryError::StorageError(format!(
+                "{} is encrypted; pass --passphrase or set PHONEBOOK_PASSPHRASE",
+                self.file_path
+            ))),
+        }
+    }
+
+    /// Without the `encrypt` feature an encrypted file can only be reported, not read
+    #[cfg(not(feature = "encrypt"))]
+    fn decrypt(&self, _data: &[u8]) -> Result<Vec<u8>, RepositoryError> {
+        Err(RepositoryError::StorageError(format!(
+            "{} is encrypted; build with the `encrypt` feature to open it",
+            self.file_path
+        )))
+    }
+
+    /// The same file opened without `--recover`
+    fn strict(&self) -> FileStorage {
+        let storage = FileStorage::new(self.file_path.clone());
+        #[cfg(feature = "encrypt")]
+        let storage = storage.with_passphrase(self.passphrase.clone());
+        storage
+    }
+
+    /// Write contacts as JSON Lines (one contact object per line)
+    /// Each contact is serialized straight into the writer, so the whole
+    /// document is never held in memory at once
+    pub fn export_jsonl<W, I>(contacts: I, mut writer: W) -> Result<usize, RepositoryError>
+    where
+        W: Write,
+        I: IntoIterator,
+        I::Item: Borrow<Contact>,
+    {
+        let mut count = 0;
+
+        for contact in contacts {
+            serde_json::to_writer(&mut writer, contact.borrow())
+                .map_err(|e| RepositoryError::SerializationError(format!("Failed to serialize: {}", e)))?;
+            writer
+                .write_all(b"\n")
+                .map_err(|e| RepositoryError::IoError(format!("Failed to write: {}", e)))?;
+            count += 1;
+        }
+
+        writer
+            .flush()
+            .map_err(|e| RepositoryError::IoError(format!("Failed to write: {}", e)))?;
+
+        Ok(count)
+    }
+
+    /// Last modification time of the file, or `None` if it doesn't exist yet
+    pub fn modified_time(&self) -> Result<Option<SystemTime>, RepositoryError> {
+        match fs::metadata(&self.file_path) {
+            Ok(metadata) => metadata
+                .modified()
+                .map(Some)
+                .map_err(|e| {
+                    RepositoryError::IoError(format!("Failed to read modification time of {}: {}", self.file_path, e))
+                }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(RepositoryError::IoError(format!("Failed to read metadata of {}: {}", self.file_path, e))),
+        }
+    }
+
     /// Get the file path
     pub fn file_path(&self) -> &str {
         &self.file_path
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Email, PhoneNumber};
+
+    fn sample_contacts(count: usize) -> HashMap<ContactId, Contact> {
+        (0..count)
+            .map(|i| {
+                let mut contact = Contact::new(
+                    format!("First{}", i),
+                    "Lastname".to_string(),
+                    vec![PhoneNumber::new(format!("555{:07}", i)).unwrap()],
+                    vec![Email::new(format!("person{}@example.com", i)).unwrap()],
+                );
+                contact.add_tag("friends".to_string());
+                (contact.id().clone(), contact)
+            })
+            .collect()
+    }
+
+    fn path_in(dir: &tempfile::TempDir, name: &str) -> String {
+        dir.path().join(name).to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn jsonl_export_writes_one_contact_per_line() {
+        let contacts = sample_contacts(3);
+        let mut output = Vec::new();
+
+        let written = FileStorage::export_jsonl(contacts.values(), &mut output).unwrap();
+
+        assert_eq!(written, 3);
+        let lines: Vec<&str> = std::str::from_utf8(&output).unwrap().lines().collect();
+        assert_eq!(lines.len(), 3);
+        for line in lines {
+            let contact: Contact = serde_json::from_str(line).unwrap();
+            assert_eq!(contacts.get(contact.id()), Some(&contact));
+        }
+    }
+
+    /// A contacts file holding `good` plus one malformed entry and one entry with a bad ID
+    fn damaged_file(dir: &tempfile::TempDir, good: &HashMap<ContactId, Contact>) -> String {
+        let path = path_in(dir, "contacts.json");
+        FileStorage::new(path.clone()).save_contacts(good).unwrap();
+
+        let mut document: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        let entries = document["contacts"].as_object_mut().unwrap();
+        entries.insert(ContactId::new().to_string(), serde_json::json!({ "first_name": 42 }));
+        entries.insert("not-a-uuid".to_string(), entries.values().next().unwrap().clone());
+        fs::write(&path, serde_json::to_vec(&document).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn recovery_skips_broken_entries_and_keeps_the_rest() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = sample_contacts(1);
+        let path = damaged_file(&dir, &good);
+
+        assert!(FileStorage::new(path.clone()).load_contacts().is_err());
+
+        let storage = FileStorage::new(path).with_recovery(true);
+        let (contacts, warnings) = storage.load_contacts_lenient().unwrap();
+        assert_eq!(contacts, good);
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings.iter().any(|warning| warning.contains("not-a-uuid")));
+        assert_eq!(storage.load_contacts().unwrap(), good);
+    }
+
+    #[test]
+    fn saving_a_recovered_file_keeps_the_original() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = sample_contacts(1);
+        let path = damaged_file(&dir, &good);
+        let original = fs::read(&path).unwrap();
+
+        let storage = FileStorage::new(path.clone()).with_recovery(true);
+        storage.save_contacts(&storage.load_contacts().unwrap()).unwrap();
+
+        assert_eq!(fs::read(format!("{}.corrupt", path)).unwrap(), original);
+        assert_eq!(FileStorage::new(path).load_contacts().unwrap(), good);
+    }
+
+    #[test]
+    fn format_is_picked_from_the_extension() {
+        assert_eq!(StorageFormat::from_path("contacts.json"), StorageFormat::Json);
+        assert_eq!(StorageFormat::from_path("contacts.json.gz"), StorageFormat::JsonGzip);
+        assert_eq!(StorageFormat::from_path("dir/Contacts.JSON.GZ"), StorageFormat::JsonGzip);
+        assert_eq!(StorageFormat::from_path("contacts.toml"), StorageFormat::Toml);
+        assert_eq!(StorageFormat::from_path("contacts.gz"), StorageFormat::Json);
+    }
+
+    #[test]
+    fn gzip_file_round_trips_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::new(path_in(&dir, "contacts.json.gz"));
+        let contacts = sample_contacts(5);
+
+        storage.save_contacts(&contacts).unwrap();
+
+        let bytes = fs::read(storage.file_path()).unwrap();
+        assert_eq!(&bytes[..2], &[0x1f, 0x8b], "file should start with the gzip magic bytes");
+        assert_eq!(storage.load_contacts().unwrap(), contacts);
+    }
+
+    #[test]
+    fn gzip_file_is_smaller_than_plain_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = FileStorage::new(path_in(&dir, "contacts.json"));
+        let compressed = FileStorage::new(path_in(&dir, "contacts.json.gz"));
+        let contacts = sample_contacts(200);
+
+        plain.save_contacts(&contacts).unwrap();
+        compressed.save_contacts(&contacts).unwrap();
+
+        let plain_size = fs::metadata(plain.file_path()).unwrap().len();
+        let compressed_size = fs::metadata(compressed.file_path()).unwrap().len();
+        assert!(
+            compressed_size * 4 < plain_size,
+            "expected {} compressed bytes to be well under {} plain bytes",
+            compressed_size,
+            plain_size
+        );
+    }
+
+    #[test]
+    fn toml_file_round_trips_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::new(path_in(&dir, "contacts.toml"));
+        let mut contacts = sample_contacts(3);
+        for contact in contacts.values_mut() {
+            contact.set_notes(Some("Line one\nSays \"hi\"".to_string()));
+        }
+
+        storage.save_contacts(&contacts).unwrap();
+
+        let text = fs::read_to_string(storage.file_path()).unwrap();
+        assert!(text.contains("first_name = \"First0\""), "not TOML:\n{}", text);
+        assert_eq!(storage.load_contacts().unwrap(), contacts);
+    }
+
+    #[test]
+    fn hand_edited_toml_file_is_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::new(path_in(&dir, "contacts.toml"));
+        let contacts = sample_contacts(1);
+        storage.save_contacts(&contacts).unwrap();
+
+        let text = fs::read_to_string(storage.file_path()).unwrap();
+        let edited = format!("# My phonebook\n{}", text.replace("First0", "Edited"));
+        fs::write(storage.file_path(), edited).unwrap();
+
+        let loaded = storage.load_contacts().unwrap();
+        assert_eq!(loaded.values().next().unwrap().first_name(), "Edited");
+    }
+
+    #[test]
+    fn invalid_toml_file_is_a_serialization_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::new(path_in(&dir, "contacts.toml"));
+        fs::write(storage.file_path(), "[contacts\nbroken = ").unwrap();
+
+        assert!(matches!(storage.load_contacts(), Err(RepositoryError::SerializationError(_))));
+    }
+
+    #[test]
+    fn missing_or_empty_gzip_file_is_an_empty_phonebook() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::new(path_in(&dir, "contacts.json.gz"));
+        assert!(storage.load_contacts().unwrap().is_empty());
+
+        fs::write(storage.file_path(), b"").unwrap();
+        assert!(storage.load_contacts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn large_file_loads_every_contact() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::new(path_in(&dir, "contacts.json"));
+        // Parsing is slow in debug builds, so every contact shares one number and email
+        let phone = PhoneNumber::new("5550000001".to_string()).unwrap();
+        let email = Email::new("person@example.com".to_string()).unwrap();
+        let contacts: HashMap<ContactId, Contact> = (0..20_000)
+            .map(|i| {
+                let contact = Contact::new(format!("First{}", i), "Lastname".to_string(), vec![phone.clone()], vec![email.clone()]);
+                (contact.id().clone(), contact)
+            })
+            .collect();
+
+        storage.save_contacts(&contacts).unwrap();
+
+        assert!(fs::metadata(storage.file_path()).unwrap().len() > 5_000_000);
+        assert_eq!(FileStorage::new(storage.file_path().to_string()).load_contacts().unwrap(), contacts);
+    }
+
+    #[test]
+    fn missing_or_empty_json_file_is_an_empty_phonebook() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = FileStorage::new(path_in(&dir, "contacts.json"));
+        assert!(storage.load_contacts().unwrap().is_empty());
+
+        fs::write(storage.file_path(), b"").unwrap();
+        assert!(storage.load_contacts().unwrap().is_empty());
+    }
+
+    #[test]
+    fn unwritable_paths_are_named_in_the_error() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // A directory where the file should be can't be replaced, even by root
+        let occupied = path_in(&dir, "contacts.json");
+        fs::create_dir(&occupied).unwrap();
+        let error = FileStorage::new(occupied.clone()).save_contacts(&sample_contacts(1)).unwrap_err();
+        assert!(matches!(&error, RepositoryError::IoError(message) if message.contains(&occupied)), "{}", error);
+
+        // Neither can a directory be created under a regular file
+        let blocker = path_in(&dir, "blocker");
+        fs::write(&blocker, b"").unwrap();
+        let nested = format!("{}/contacts.json", blocker);
+        let error = FileStorage::new(nested).save_contacts(&sample_contacts(1)).unwrap_err();
+        assert!(matches!(&error, RepositoryError::IoError(message) if message.contains(&blocker)), "{}", error);
+    }
+
+    #[cfg(feature = "encrypt")]
+    #[test]
+    fn encrypted_file_round_trips_with_the_same_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = path_in(&dir, "contacts.json");
+        let storage = FileStorage::new(path.clone()).with_passphrase(Some("correct horse".to_string()));
+        let contacts = sample_contacts(3);
+
+        storage.save_contacts(&contacts).unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        assert!(bytes.starts_with(ENCRYPTED_MAGIC));
+        assert!(!String::from_utf8_lossy(&bytes).contains("First0"), "names must not be stored in plain text");
+
+        let reopened = FileStorage::new(path).with_passphrase(Some("correct horse".to_string()));
+        assert_eq!(reopened.load_contacts().unwrap(), contacts);
+    }
+
+    #[cfg(feature = "encrypt")]
+    #[test]
+    fn encrypted_file_rejects_a_wrong_or_missing_passphrase() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = path_in(&dir, "contacts.json.gz");
+        FileStorage::new(path.clone())
+            .with_passphrase(Some("correct horse".to_string()))
+            .save_contacts(&sample_contacts(2))
+            .unwrap();
+
+        let wrong = FileStorage::new(path.clone()).with_passphrase(Some("battery staple".to_string()));
+        assert!(matches!(wrong.load_contacts(), Err(RepositoryError::WrongPassphrase(_))));
+
+        let missing = FileStorage::new(path);
+        assert!(matches!(missing.load_contacts(), Err(RepositoryError::StorageError(_))));
+    }
+}
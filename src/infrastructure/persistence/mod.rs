@@ -1,3 +1,5 @@
 pub mod file_storage;
+#[cfg(feature = "encrypt")]
+pub mod encryption;
 
-pub use file_storage::FileStorage;
+pub use file_storage::{FileStorage, StorageFormat};
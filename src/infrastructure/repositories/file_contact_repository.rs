@@ -1,31 +1,80 @@
 use crate::domain::entities::Contact;
-use crate::domain::repositories::{ContactRepositorySync, RepositoryError};
-use crate::domain::value_objects::ContactId;
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::{ContactRepositorySync, ContactScan, ContactTransaction, RepositoryError};
+use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
 use crate::infrastructure::persistence::FileStorage;
+use lru::LruCache;
 use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// How many times a write is retried after the file changed underneath the cache
+const MAX_WRITE_ATTEMPTS: usize = 3;
+
+/// How many distinct search queries keep their results cached
+const SEARCH_CACHE_CAPACITY: NonZeroUsize = NonZeroUsize::new(64).unwrap();
 
 /// File-based implementation of ContactRepository
 /// Uses JSON file for persistence with in-memory caching
 pub struct FileContactRepository {
     storage: FileStorage,
-    cache: Mutex<Option<HashMap<ContactId, Contact>>>,
+    cache: Mutex<Option<CachedContacts>>,
+    search_cache: Mutex<SearchCache>,
 }
 
+/// Contacts loaded from the file, with the file's modification time at load
+struct CachedContacts {
+    contacts: HashMap<ContactId, Contact>,
+    modified: Option<SystemTime>,
+}
+
+/// Least-recently-used cache of search results, keyed by lowercased query
+/// Holds IDs only; they are resolved against the contact cache on every hit
+type SearchCache = LruCache<String, Vec<ContactId>>;
+
 impl FileContactRepository {
     pub fn new(file_path: String) -> Self {
         Self {
             storage: FileStorage::new(file_path),
             cache: Mutex::new(None),
+            search_cache: Mutex::new(SearchCache::new(SEARCH_CACHE_CAPACITY)),
         }
     }
 
-    /// Load contacts into cache if not already loaded
+    /// Skip entries of a damaged file that fail to deserialize instead of failing the load
+    pub fn with_recovery(mut self, recover: bool) -> Self {
+        self.storage = self.storage.with_recovery(recover);
+        self
+    }
+
+    /// Read and write the file encrypted with this passphrase
+    #[cfg(feature = "encrypt")]
+    pub fn with_passphrase(mut self, passphrase: Option<String>) -> Self {
+        self.storage = self.storage.with_passphrase(passphrase);
+        self
+    }
+
+    /// Read the file into a fresh cache entry
+    /// Cached search results are dropped, since they may not match the file
+    fn load(&self) -> Result<CachedContacts, RepositoryError> {
+        self.search_cache.lock().unwrap().clear();
+        let modified = self.storage.modified_time()?;
+        let contacts = self.storage.load_contacts()?;
+        Ok(CachedContacts { contacts, modified })
+    }
+
+    /// Load contacts into cache if not already loaded, or reload them if the file changed since
     fn ensure_cache_loaded(&self) -> Result<(), RepositoryError> {
         let mut cache = self.cache.lock().unwrap();
         if cache.is_none() {
-            let contacts = self.storage.load_contacts()?;
-            *cache = Some(contacts);
+            log::debug!("Contact cache miss; loading {}", self.storage.file_path());
+            *cache = Some(self.load()?);
+        } else if cache.as_ref().is_some_and(|cached| cached.modified != self.storage.modified_time().ok().flatten()) {
+            log::debug!("{} changed since it was cached; reloading", self.storage.file_path());
+            *cache = Some(self.load()?);
+        } else {
+            log::debug!("Contact cache hit");
         }
         Ok(())
     }
@@ -37,32 +86,77 @@ impl FileContactRepository {
     {
         self.ensure_cache_loaded()?;
         let cache = self.cache.lock().unwrap();
-        let contacts = cache.as_ref().unwrap();
-        Ok(f(contacts))
+        let cached = cache.as_ref().unwrap();
+        Ok(f(&cached.contacts))
     }
 
     /// Modify the cached contacts and save to file
-    fn modify_cache<F>(&self, f: F) -> Result<(), RepositoryError>
+    /// If another process changed the file since it was loaded, the cache is
+    /// reloaded and `f` re-applied to the fresh contacts before writing.
+    /// If `f` fails, the cache is dropped so its partial changes are discarded
+    fn modify_cache<F>(&self, mut f: F) -> Result<(), RepositoryError>
     where
-        F: FnOnce(&mut HashMap<ContactId, Contact>) -> Result<(), RepositoryError>,
+        F: FnMut(&mut HashMap<ContactId, Contact>) -> Result<(), RepositoryError>,
     {
-        self.ensure_cache_loaded()?;
         let mut cache = self.cache.lock().unwrap();
-        let contacts = cache.as_mut().unwrap();
-        f(contacts)?;
-        self.storage.save_contacts(contacts)?;
-        Ok(())
+        self.search_cache.lock().unwrap().clear();
+
+        for _ in 0..MAX_WRITE_ATTEMPTS {
+            let stale = match cache.as_ref() {
+                Some(cached) => self.storage.modified_time()? != cached.modified,
+                None => true,
+            };
+            if stale {
+                log::debug!("{} changed since it was cached; reloading", self.storage.file_path());
+                *cache = Some(self.load()?);
+            }
+
+            let cached = cache.as_mut().unwrap();
+            if let Err(e) = f(&mut cached.contacts) {
+                *cache = None;
+                return Err(e);
+            }
+
+            // The file may have changed while the closure ran
+            if self.storage.modified_time()? != cached.modified {
+                log::info!("{} changed while saving; retrying", self.storage.file_path());
+                *cache = None;
+                continue;
+            }
+
+            self.storage.save_contacts(&cached.contacts)?;
+            cached.modified = self.storage.modified_time()?;
+            return Ok(());
+        }
+
+        *cache = None;
+        Err(RepositoryError::Conflict(format!(
+            "{} kept changing while saving; gave up after {} attempts",
+            self.storage.file_path(),
+            MAX_WRITE_ATTEMPTS
+        )))
+    }
+
+    /// Reject contacts that break the entity's invariants
+    fn validate(contact: &Contact) -> Result<(), RepositoryError> {
+        contact.validate().map_err(|e| match e {
+            DomainError::Validation(message) | DomainError::BusinessRule(message) => {
+                RepositoryError::ValidationError(message)
+            }
+            other => RepositoryError::ValidationError(other.to_string()),
+        })
     }
 }
 
 impl ContactRepositorySync for FileContactRepository {
     fn save(&self, contact: Contact) -> Result<(), RepositoryError> {
+        Self::validate(&contact)?;
         let contact_id = contact.id().clone();
         self.modify_cache(|contacts| {
             if contacts.contains_key(&contact_id) {
                 return Err(RepositoryError::ContactAlreadyExists(contact_id.to_string()));
             }
-            contacts.insert(contact_id, contact);
+            contacts.insert(contact_id.clone(), contact.clone());
             Ok(())
         })
     }
@@ -72,16 +166,42 @@ impl ContactRepositorySync for FileContactRepository {
     }
 
     fn find_all(&self) -> Result<Vec<Contact>, RepositoryError> {
-        self.with_cache(|contacts| contacts.values().cloned().collect())
+        self.with_cache(|contacts| {
+            contacts
+                .values()
+                .filter(|contact| !contact.is_deleted())
+                .cloned()
+                .collect()
+        })
+    }
+
+    fn find_deleted(&self) -> Result<Vec<Contact>, RepositoryError> {
+        self.with_cache(|contacts| {
+            contacts
+                .values()
+                .filter(|contact| contact.is_deleted())
+                .cloned()
+                .collect()
+        })
     }
 
     fn update(&self, contact: Contact) -> Result<(), RepositoryError> {
+        Self::validate(&contact)?;
         let contact_id = contact.id().clone();
         self.modify_cache(|contacts| {
             if !contacts.contains_key(&contact_id) {
                 return Err(RepositoryError::ContactNotFound(contact_id.to_string()));
             }
-            contacts.insert(contact_id, contact);
+            contacts.insert(contact_id.clone(), contact.clone());
+            Ok(())
+        })
+    }
+
+    fn upsert(&self, contact: Contact) -> Result<(), RepositoryError> {
+        Self::validate(&contact)?;
+        let contact_id = contact.id().clone();
+        self.modify_cache(|contacts| {
+            contacts.insert(contact_id.clone(), contact.clone());
             Ok(())
         })
     }
@@ -96,10 +216,47 @@ impl ContactRepositorySync for FileContactRepository {
     }
 
     fn search(&self, query: &str) -> Result<Vec<Contact>, RepositoryError> {
+        let key = query.to_lowercase();
+        self.with_cache(|contacts| {
+            let mut search_cache = self.search_cache.lock().unwrap();
+            if let Some(ids) = search_cache.get(&key) {
+                log::debug!("Search cache hit for '{}'", key);
+                return ids.iter().filter_map(|id| contacts.get(id)).cloned().collect();
+            }
+
+            let matches: Vec<Contact> = contacts
+                .values()
+                .filter(|contact| !contact.is_deleted() && contact.matches_search(query))
+                .cloned()
+                .collect();
+            search_cache.put(key, matches.iter().map(|contact| contact.id().clone()).collect());
+            matches
+        })
+    }
+
+    fn find_by_phone(&self, phone: &PhoneNumber) -> Result<Vec<Contact>, RepositoryError> {
+        let normalized = phone.normalized();
+        self.with_cache(|contacts| {
+            contacts
+                .values()
+                .filter(|contact| {
+                    !contact.is_deleted()
+                        && contact.phone_numbers().iter().any(|p| p.normalized() == normalized)
+                })
+                .cloned()
+                .collect()
+        })
+    }
+
+    fn find_by_email(&self, email: &Email) -> Result<Vec<Contact>, RepositoryError> {
+        let canonical = email.canonical();
         self.with_cache(|contacts| {
             contacts
                 .values()
-                .filter(|contact| contact.matches_search(query))
+                .filter(|contact| {
+                    !contact.is_deleted()
+                        && contact.emails().iter().any(|e| e.canonical() == canonical)
+                })
                 .cloned()
                 .collect()
         })
@@ -110,6 +267,297 @@ impl ContactRepositorySync for FileContactRepository {
     }
 
     fn count(&self) -> Result<usize, RepositoryError> {
-        self.with_cache(|contacts| contacts.len())
+        self.with_cache(|contacts| contacts.values().filter(|c| !c.is_deleted()).count())
+    }
+
+    fn transaction(&self, f: &mut ContactTransaction) -> Result<(), RepositoryError> {
+        // A failure drops the cache, so the contacts are read back from disk
+        // rather than restored from a copy taken up front
+        self.modify_cache(|contacts| {
+            // Invalid entries already stored (e.g. kept by --recover) mustn't block
+            // unrelated changes, so only those few are copied to tell if `f` touched them
+            let invalid_before: HashMap<ContactId, Contact> = contacts
+                .iter()
+                .filter(|(_, contact)| Self::validate(contact).is_err())
+                .map(|(id, contact)| (id.clone(), contact.clone()))
+                .collect();
+            f(contacts)?;
+            contacts
+                .iter()
+                .filter(|(id, contact)| invalid_before.get(*id) != Some(*contact))
+                .try_for_each(|(_, contact)| Self::validate(contact))
+        })
+    }
+
+    fn scan(&self, f: &mut ContactScan) -> Result<(), RepositoryError> {
+        self.with_cache(|contacts| f(&mut contacts.values().filter(|contact| !contact.is_deleted())))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::MAX_TAG_LENGTH;
+    use crate::domain::value_objects::Timestamp;
+
+    fn repository_in(dir: &tempfile::TempDir) -> FileContactRepository {
+        FileContactRepository::new(dir.path().join("contacts.json").to_string_lossy().into_owned())
+    }
+
+    fn contact(first_name: &str, phone: &str) -> Contact {
+        Contact::new(
+            first_name.to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new(phone.to_string()).unwrap()],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn save_update_and_upsert_reject_invalid_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let ann = contact("Ann", "5550000001");
+        let id = ann.id().clone();
+        repository.save(ann.clone()).unwrap();
+
+        let mut nameless = contact("Bob", "5550000002");
+        nameless.set_first_name(String::new());
+        assert!(matches!(repository.save(nameless.clone()), Err(RepositoryError::ValidationError(_))));
+        assert!(matches!(repository.upsert(nameless), Err(RepositoryError::ValidationError(_))));
+
+        let mut unreachable = ann.clone();
+        unreachable.clear_phone_numbers();
+        assert!(matches!(repository.update(unreachable.clone()), Err(RepositoryError::ValidationError(_))));
+        assert!(matches!(repository.upsert(unreachable), Err(RepositoryError::ValidationError(_))));
+
+        assert_eq!(repository.count().unwrap(), 1);
+        assert_eq!(repository_in(&dir).find_by_id(&id).unwrap(), Some(ann));
+    }
+
+    #[test]
+    fn upsert_inserts_a_contact_with_a_new_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        repository.save(contact("Ann", "5550000001")).unwrap();
+        let bob = contact("Bob", "5550000002");
+
+        repository.upsert(bob.clone()).unwrap();
+
+        assert_eq!(repository.count().unwrap(), 2);
+        assert_eq!(repository_in(&dir).find_by_id(bob.id()).unwrap(), Some(bob));
+    }
+
+    #[test]
+    fn upsert_replaces_the_contact_with_the_same_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let mut ann = contact("Ann", "5550000001");
+        ann.add_tag("work".to_string());
+        repository.save(ann.clone()).unwrap();
+
+        let replacement = Contact::new_with_id(
+            ann.id().clone(),
+            "Annie".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000009".to_string()).unwrap()],
+            vec![],
+        );
+        repository.upsert(replacement.clone()).unwrap();
+
+        assert_eq!(repository.count().unwrap(), 1);
+        let stored = repository_in(&dir).find_by_id(ann.id()).unwrap().unwrap();
+        assert_eq!(stored, replacement);
+        assert!(stored.tags().is_empty());
+    }
+
+    #[test]
+    fn transaction_rejects_invalid_changes_and_saves_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let mut tagged = contact("Ann", "5550000001");
+        tagged.add_tag("work".to_string());
+        let id = tagged.id().clone();
+        repository.save(tagged).unwrap();
+        repository.save(contact("Bob", "5550000002")).unwrap();
+
+        let long_tag = "x".repeat(MAX_TAG_LENGTH + 30);
+        let result = repository.transaction(&mut |contacts| {
+            for contact in contacts.values_mut() {
+                contact.rename_tag("work", &long_tag);
+                contact.set_notes(Some("changed".to_string()));
+            }
+            Ok(())
+        });
+
+        assert!(matches!(result, Err(RepositoryError::ValidationError(_))));
+        let reloaded = repository_in(&dir);
+        assert_eq!(reloaded.find_by_id(&id).unwrap().unwrap().tags(), ["work".to_string()]);
+        assert!(reloaded.find_all().unwrap().iter().all(|c| c.notes().is_none()));
+        assert!(repository.find_all().unwrap().iter().all(|c| c.notes().is_none()));
+    }
+
+    #[test]
+    fn transaction_leaves_stored_invalid_contacts_alone_unless_touched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json");
+        let ann = contact("Ann", "5550000001");
+        let ann_id = ann.id().clone();
+        let mut nameless = contact("Bob", "5550000002");
+        nameless.set_first_name(String::new());
+        let nameless_id = nameless.id().clone();
+        let stored: HashMap<ContactId, Contact> = [(ann_id.clone(), ann), (nameless_id.clone(), nameless)].into();
+        FileStorage::new(path.to_string_lossy().into_owned()).save_contacts(&stored).unwrap();
+        let repository = repository_in(&dir);
+
+        repository
+            .transaction(&mut |contacts| {
+                contacts.get_mut(&ann_id).unwrap().add_tag("friends".to_string());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(repository.find_by_id(&ann_id).unwrap().unwrap().tags(), ["friends".to_string()]);
+
+        let touched = repository.transaction(&mut |contacts| {
+            contacts.get_mut(&nameless_id).unwrap().add_tag("friends".to_string());
+            Ok(())
+        });
+        assert!(matches!(touched, Err(RepositoryError::ValidationError(_))));
+        assert!(repository_in(&dir).find_by_id(&nameless_id).unwrap().unwrap().tags().is_empty());
+    }
+
+    #[test]
+    fn transaction_rejects_invalid_new_contacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+
+        let mut nameless = contact("Ann", "5550000001");
+        nameless.set_first_name(String::new());
+        let result = repository.transaction(&mut |contacts| {
+            contacts.insert(nameless.id().clone(), nameless.clone());
+            Ok(())
+        });
+
+        assert!(matches!(result, Err(RepositoryError::ValidationError(_))));
+        assert_eq!(repository.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn transaction_saves_valid_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let ann = contact("Ann", "5550000001");
+        let id = ann.id().clone();
+        repository.save(ann).unwrap();
+
+        repository
+            .transaction(&mut |contacts| {
+                contacts.get_mut(&id).unwrap().add_tag("friends".to_string());
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(repository_in(&dir).find_by_id(&id).unwrap().unwrap().tags(), ["friends".to_string()]);
+    }
+
+    #[test]
+    fn transaction_reapplies_changes_when_another_writer_saves_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let ours = repository_in(&dir);
+        let theirs = repository_in(&dir);
+        let ann = contact("Ann", "5550000001");
+        let id = ann.id().clone();
+        ours.save(ann).unwrap();
+
+        let mut runs = 0;
+        ours.transaction(&mut |contacts| {
+            runs += 1;
+            if runs == 1 {
+                // Let the file's modification time move on, then save from the other writer
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                let mut contact = theirs.find_by_id(&id).unwrap().unwrap();
+                contact.add_tag("theirs".to_string());
+                theirs.update(contact).unwrap();
+            }
+            contacts.get_mut(&id).unwrap().add_tag("ours".to_string());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(runs, 2, "the change was not re-applied after the conflict");
+        let tags = repository_in(&dir).find_by_id(&id).unwrap().unwrap().tags().to_vec();
+        assert!(tags.contains(&"theirs".to_string()), "the other writer's change was lost: {:?}", tags);
+        assert!(tags.contains(&"ours".to_string()));
+    }
+
+    #[test]
+    fn cached_search_results_follow_later_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        repository.save(contact("Ann", "5550000001")).unwrap();
+
+        assert_eq!(repository.search("ann").unwrap().len(), 1);
+        assert_eq!(repository.search("ANN").unwrap().len(), 1, "cached under the lowercased query");
+
+        repository.save(contact("Annabel", "5550000002")).unwrap();
+        assert_eq!(repository.search("ann").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn search_cache_evicts_the_least_recently_used_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        repository.save(contact("Ann", "5550000001")).unwrap();
+
+        repository.search("query 0").unwrap();
+        for i in 1..=SEARCH_CACHE_CAPACITY.get() {
+            repository.search("query 0").unwrap();
+            repository.search(&format!("query {}", i)).unwrap();
+        }
+
+        let search_cache = repository.search_cache.lock().unwrap();
+        assert_eq!(search_cache.len(), SEARCH_CACHE_CAPACITY.get());
+        assert!(search_cache.contains("query 0"), "recently used query was evicted");
+        assert!(!search_cache.contains("query 1"));
+    }
+
+    fn first_names(mut contacts: Vec<Contact>) -> Vec<String> {
+        contacts.sort_by(|a, b| a.first_name().cmp(b.first_name()));
+        contacts.iter().map(|contact| contact.first_name().to_string()).collect()
+    }
+
+    #[test]
+    fn find_by_phone_matches_any_format_and_every_sharer() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        repository.save(contact("Ann", "5550000001")).unwrap();
+        repository.save(contact("Bob", "+1 (555) 000-0001")).unwrap();
+        repository.save(contact("Cal", "5550000002")).unwrap();
+        let mut gone = contact("Dee", "5550000001");
+        gone.mark_deleted(Timestamp::now());
+        repository.save(gone).unwrap();
+
+        let phone = |value: &str| PhoneNumber::new(value.to_string()).unwrap();
+        assert_eq!(first_names(repository.find_by_phone(&phone("555-000-0001")).unwrap()), ["Ann", "Bob"]);
+        assert_eq!(first_names(repository.find_by_phone(&phone("5550000002")).unwrap()), ["Cal"]);
+        assert!(repository.find_by_phone(&phone("5550000009")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn find_by_email_matches_regardless_of_case() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = repository_in(&dir);
+        let email = |value: &str| Email::new(value.to_string()).unwrap();
+        let mut ann = contact("Ann", "5550000001");
+        ann.add_email(email("ann@example.com"));
+        repository.save(ann).unwrap();
+        let mut bob = contact("Bob", "5550000002");
+        bob.add_email(email("ann@example.com"));
+        bob.add_email(email("bob@example.com"));
+        repository.save(bob).unwrap();
+
+        assert_eq!(first_names(repository.find_by_email(&email("ANN@example.com")).unwrap()), ["Ann", "Bob"]);
+        assert_eq!(first_names(repository.find_by_email(&email("bob@example.com")).unwrap()), ["Bob"]);
+        assert!(repository.find_by_email(&email("cal@example.com")).unwrap().is_empty());
     }
 }
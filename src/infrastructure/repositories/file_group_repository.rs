@@ -0,0 +1,178 @@
+use crate::domain::entities::Group;
+use crate::domain::repositories::{GroupRepositorySync, RepositoryError};
+use crate::infrastructure::persistence::FileStorage;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// File-based implementation of GroupRepositorySync
+/// Groups live in the same JSON file as contacts, under the `groups` key
+pub struct FileGroupRepository {
+    storage: FileStorage,
+    cache: Mutex<Option<BTreeMap<String, Group>>>,
+}
+
+impl FileGroupRepository {
+    pub fn new(file_path: String) -> Self {
+        Self {
+            storage: FileStorage::new(file_path),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Skip entries of a damaged file that fail to deserialize instead of failing the load
+    pub fn with_recovery(mut self, recover: bool) -> Self {
+        self.storage = self.storage.with_recovery(recover);
+        self
+    }
+
+    /// Read and write the file encrypted with this passphrase
+    #[cfg(feature = "encrypt")]
+    pub fn with_passphrase(mut self, passphrase: Option<String>) -> Self {
+        self.storage = self.storage.with_passphrase(passphrase);
+        self
+    }
+
+    /// Load groups into cache if not already loaded
+    fn ensure_cache_loaded(&self) -> Result<(), RepositoryError> {
+        let mut cache = self.cache.lock().unwrap();
+        if cache.is_none() {
+            log::debug!("Group cache miss; loading {}", self.storage.file_path());
+            *cache = Some(self.storage.load_groups()?);
+        } else {
+            log::debug!("Group cache hit");
+        }
+        Ok(())
+    }
+
+    /// Get a reference to the cached groups
+    fn with_cache<F, R>(&self, f: F) -> Result<R, RepositoryError>
+    where
+        F: FnOnce(&BTreeMap<String, Group>) -> R,
+    {
+        self.ensure_cache_loaded()?;
+        let cache = self.cache.lock().unwrap();
+        Ok(f(cache.as_ref().unwrap()))
+    }
+
+    /// Modify the cached groups and save to file
+    fn modify_cache<F>(&self, f: F) -> Result<(), RepositoryError>
+    where
+        F: FnOnce(&mut BTreeMap<String, Group>) -> Result<(), RepositoryError>,
+    {
+        self.ensure_cache_loaded()?;
+        let mut cache = self.cache.lock().unwrap();
+        let groups = cache.as_mut().unwrap();
+        f(groups)?;
+        self.storage.save_groups(groups)?;
+        Ok(())
+    }
+}
+
+impl GroupRepositorySync for FileGroupRepository {
+    fn save(&self, group: Group) -> Result<(), RepositoryError> {
+        self.modify_cache(|groups| {
+            if groups.contains_key(group.name()) {
+                return Err(RepositoryError::GroupAlreadyExists(group.name().to_string()));
+            }
+            groups.insert(group.name().to_string(), group);
+            Ok(())
+        })
+    }
+
+    fn find_by_name(&self, name: &str) -> Result<Option<Group>, RepositoryError> {
+        self.with_cache(|groups| groups.get(name).cloned())
+    }
+
+    fn find_all(&self) -> Result<Vec<Group>, RepositoryError> {
+        self.with_cache(|groups| groups.values().cloned().collect())
+    }
+
+    fn update(&self, group: Group) -> Result<(), RepositoryError> {
+        self.modify_cache(|groups| {
+            if !groups.contains_key(group.name()) {
+                return Err(RepositoryError::GroupNotFound(group.name().to_string()));
+            }
+            groups.insert(group.name().to_string(), group);
+            Ok(())
+        })
+    }
+
+    fn delete(&self, name: &str) -> Result<(), RepositoryError> {
+        self.modify_cache(|groups| {
+            if groups.remove(name).is_none() {
+                return Err(RepositoryError::GroupNotFound(name.to_string()));
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::Contact;
+    use crate::domain::repositories::ContactRepositorySync;
+    use crate::domain::value_objects::PhoneNumber;
+    use crate::infrastructure::FileContactRepository;
+
+    fn path_in(dir: &tempfile::TempDir) -> String {
+        dir.path().join("contacts.json").to_string_lossy().into_owned()
+    }
+
+    fn contact(first_name: &str, phone: &str) -> Contact {
+        Contact::new(
+            first_name.to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new(phone.to_string()).unwrap()],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn groups_are_created_and_members_added_and_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let repository = FileGroupRepository::new(path_in(&dir));
+        let ann = contact("Ann", "5550000001");
+
+        repository.save(Group::new("family".to_string())).unwrap();
+        assert!(matches!(
+            repository.save(Group::new("family".to_string())),
+            Err(RepositoryError::GroupAlreadyExists(_))
+        ));
+
+        let mut family = repository.find_by_name("family").unwrap().unwrap();
+        assert!(family.add_member(ann.id().clone()));
+        repository.update(family).unwrap();
+        let reopened = FileGroupRepository::new(path_in(&dir));
+        assert!(reopened.find_by_name("family").unwrap().unwrap().has_member(ann.id()));
+
+        let mut family = reopened.find_by_name("family").unwrap().unwrap();
+        assert!(family.remove_member(ann.id()));
+        reopened.update(family).unwrap();
+        let reopened = FileGroupRepository::new(path_in(&dir));
+        assert!(reopened.find_by_name("family").unwrap().unwrap().members().is_empty());
+
+        reopened.delete("family").unwrap();
+        assert!(matches!(reopened.delete("family"), Err(RepositoryError::GroupNotFound(_))));
+        assert!(FileGroupRepository::new(path_in(&dir)).find_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn saving_contacts_keeps_groups_written_since_the_contacts_were_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let contacts = FileContactRepository::new(path_in(&dir));
+        let groups = FileGroupRepository::new(path_in(&dir));
+
+        let ann = contact("Ann", "5550000001");
+        contacts.save(ann.clone()).unwrap();
+        let mut family = Group::new("family".to_string());
+        family.add_member(ann.id().clone());
+        groups.save(family).unwrap();
+        contacts.save(contact("Bob", "5550000002")).unwrap();
+        contacts.save(contact("Cid", "5550000003")).unwrap();
+
+        let reopened = FileGroupRepository::new(path_in(&dir));
+        assert!(reopened.find_by_name("family").unwrap().unwrap().has_member(ann.id()));
+        assert_eq!(FileContactRepository::new(path_in(&dir)).count().unwrap(), 3);
+    }
+}
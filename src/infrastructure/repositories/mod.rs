@@ -1,3 +1,5 @@
 pub mod file_contact_repository;
+pub mod file_group_repository;
 
 pub use file_contact_repository::FileContactRepository;
+pub use file_group_repository::FileGroupRepository;
@@ -0,0 +1,115 @@
+use crate::domain::repositories::RepositoryError;
+use crate::infrastructure::persistence::FileStorage;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long to wait for a burst of file system events to settle before re-reading
+pub const DEFAULT_SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Watches a contacts file for changes using the platform's file notifications
+/// The parent directory is watched rather than the file, since saves replace the
+/// file atomically with a rename; events for other files are ignored
+pub struct FileWatcher {
+    storage: FileStorage,
+    settle_time: Duration,
+}
+
+impl FileWatcher {
+    pub fn new(file_path: String) -> Self {
+        Self {
+            storage: FileStorage::new(file_path),
+            settle_time: DEFAULT_SETTLE_TIME,
+        }
+    }
+
+    /// Wait this long after an event for more before treating the file as changed
+    pub fn with_settle_time(mut self, settle_time: Duration) -> Self {
+        self.settle_time = settle_time;
+        self
+    }
+
+    /// Call `on_change` once, then again after every change to the file
+    /// Runs until `on_change`, the watcher or reading the file's metadata fails
+    pub fn run<E, F>(&self, mut on_change: F) -> Result<(), E>
+    where
+        E: From<RepositoryError>,
+        F: FnMut() -> Result<(), E>,
+    {
+        let path = absolute(Path::new(self.storage.file_path()));
+        let directory = path.parent().unwrap_or(Path::new(".")).to_path_buf();
+
+        let (sender, events) = mpsc::channel();
+        let mut watcher = RecommendedWatcher::new(sender, notify::Config::default()).map_err(watch_error)?;
+        watcher
+            .watch(&directory, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+
+        let mut last_modified = self.storage.modified_time()?;
+        on_change()?;
+
+        loop {
+            let event = events.recv().map_err(|_| watch_error("watcher stopped"))?;
+            if !concerns(&event.map_err(watch_error)?, &path) {
+                continue;
+            }
+
+            // Saves touch the file several times; let them finish before reading
+            while events.recv_timeout(self.settle_time).is_ok() {}
+
+            // Skip events that didn't change the file, such as reads or metadata changes
+            let modified = self.storage.modified_time()?;
+            if modified != last_modified {
+                last_modified = modified;
+                on_change()?;
+            }
+        }
+    }
+}
+
+/// Whether an event touched the watched file
+fn concerns(event: &Event, path: &Path) -> bool {
+    event.paths.iter().any(|changed| absolute(changed) == path)
+}
+
+/// An absolute form of `path`, so event paths compare equal to the watched one
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn watch_error(error: impl ToString) -> RepositoryError {
+    RepositoryError::IoError(format!("Failed to watch the contacts file: {}", error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::thread;
+
+    #[test]
+    fn change_to_the_file_triggers_a_rerun_and_other_files_do_not() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+        let storage = FileStorage::new(path.clone());
+        storage.save_contacts(&HashMap::new()).unwrap();
+
+        let (calls, runs) = mpsc::channel();
+        let watcher = FileWatcher::new(path);
+        thread::spawn(move || {
+            watcher.run(|| {
+                calls.send(()).map_err(|_| RepositoryError::IoError("test finished".to_string()))
+            })
+        });
+
+        let timeout = Duration::from_secs(5);
+        runs.recv_timeout(timeout).expect("first run happens straight away");
+
+        std::fs::write(dir.path().join("unrelated.txt"), "noise").unwrap();
+        assert!(runs.recv_timeout(Duration::from_millis(500)).is_err(), "other files must be ignored");
+
+        storage.save_contacts(&HashMap::new()).unwrap();
+        runs.recv_timeout(timeout).expect("saving the file triggers a rerun");
+    }
+}
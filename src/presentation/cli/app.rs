@@ -1,388 +1,1862 @@
-use crate::application::services::ContactService;
+use crate::application::diff::{changes_since, diff_contacts};
+use crate::application::integrity::IntegrityChecker;
+use crate::application::query::FilterExpr;
+use crate::application::services::{ContactService, GroupService};
+use crate::application::validation::{ValidationErrors, RECOMMENDED_MAX_PAGE_SIZE};
 use crate::application::use_cases::{
-    AddContactRequest, DeleteContactRequest, FindContactRequest,
-    ListContactsRequest, SearchContactsRequest, UpdateContactRequest
+    AddContactRequest, AddGroupMemberRequest, CreateGroupRequest, DateFilter, DeleteContactRequest, DeletedFilter, DeleteManyContactsRequest, ExportContactsRequest, PurgeAllContactsRequest, RenameTagRequest, RestoreContactRequest, FindContactRequest,
+    ListContactsRequest, ListGroupsRequest, RemoveGroupMemberRequest, RandomContactsRequest, SearchContactsRequest, SortBy, StatsRequest, SuggestTagsRequest, TimeBucket, sort_contacts, TouchContactRequest, UpcomingBirthdaysRequest, UpdateContactRequest
 };
-use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
-use crate::infrastructure::repositories::FileContactRepository;
-use crate::presentation::cli::{commands::*, formatters::ContactFormatter};
-use clap::Parser;
-use std::io::{self, Write};
+use crate::domain::entities::{Contact, EmailDedupPolicy};
+use crate::domain::errors::DomainError;
+use crate::domain::repositories::RepositoryError;
+use crate::domain::value_objects::{ContactId, Date, Email, PhoneNumber, PhoneValidationPolicy, Timestamp};
+use crate::infrastructure::config::ProfileConfig;
+use crate::infrastructure::export::{MarkdownExporter, VCardExporter};
+use crate::infrastructure::import::{CsvImporter, CsvRecord};
+use crate::infrastructure::persistence::FileStorage;
+use crate::infrastructure::repositories::{FileContactRepository, FileGroupRepository};
+use crate::presentation::cli::{
+    commands::*,
+    formatters::{ColumnSpec, ContactFormatter, Formatter},
+    line_editor::{split_args, LineEditor, ReadLine},
+    style::{terminal_width, Style},
+    template::ContactTemplate,
+};
+use crate::presentation::output::OutputSink;
+use clap_complete::Shell;
+use log::{Level, LevelFilter};
+use crate::presentation::progress::Progress;
+use clap::{CommandFactory, Parser};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use uuid::Uuid;
+
+/// Suffix of every yes/no confirmation prompt; anything but an answer starting with `y` declines
+pub const CONFIRM_SUFFIX: &str = "(y/N): ";
+
+/// Printed when the user declines a deletion
+pub const DELETION_CANCELLED: &str = "Deletion cancelled";
+
+/// Error raised instead of prompting when stdin can't answer
+pub const NON_INTERACTIVE_CONFIRMATION: &str =
+    "Cannot ask for confirmation because stdin is not a terminal; pass --yes to delete without confirming";
+
+/// A contact for `add --stdin-json`; values are validated like the `add` flags
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ContactJson {
+    first_name: String,
+    last_name: String,
+    #[serde(default)]
+    phone_numbers: Vec<String>,
+    #[serde(default)]
+    emails: Vec<String>,
+    notes: Option<String>,
+    birthday: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    photo: Option<PathBuf>,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+}
 
 /// Main CLI application
 pub struct PhonebookApp {
     contact_service: ContactService,
+    group_service: GroupService,
+    file_path: String,
+    /// Passphrase of the contacts file, also tried on other files it reads
+    #[cfg(feature = "encrypt")]
+    passphrase: Option<String>,
+    phone_policy: PhoneValidationPolicy,
+    email_policy: EmailDedupPolicy,
+    style: Style,
+    max_page_size: usize,
+    formatter: Box<dyn Formatter>,
 }
 
 impl PhonebookApp {
     /// Create a new phonebook app with file-based storage
     pub fn new(file_path: String) -> Self {
-        let repository = Arc::new(FileContactRepository::new(file_path));
-        let contact_service = ContactService::new(repository);
+        Self::open(file_path, false)
+    }
 
-        Self { contact_service }
+    /// Create a phonebook app, optionally skipping unreadable entries of a damaged file
+    pub fn open(file_path: String, recover: bool) -> Self {
+        let repository = FileContactRepository::new(file_path.clone()).with_recovery(recover);
+        let group_repository = FileGroupRepository::new(file_path.clone()).with_recovery(recover);
+        Self::with_repositories(file_path, repository, group_repository)
+    }
+
+    /// Create a phonebook app whose contacts file is encrypted with `passphrase`
+    /// Without a passphrase this is the same as `open`
+    #[cfg(feature = "encrypt")]
+    pub fn open_encrypted(file_path: String, recover: bool, passphrase: Option<String>) -> Self {
+        let repository = FileContactRepository::new(file_path.clone())
+            .with_recovery(recover)
+            .with_passphrase(passphrase.clone());
+        let group_repository = FileGroupRepository::new(file_path.clone())
+            .with_recovery(recover)
+            .with_passphrase(passphrase.clone());
+
+        let mut app = Self::with_repositories(file_path, repository, group_repository);
+        app.passphrase = passphrase;
+        app
+    }
+
+    fn with_repositories(
+        file_path: String,
+        repository: FileContactRepository,
+        group_repository: FileGroupRepository,
+    ) -> Self {
+        let repository = Arc::new(repository);
+        let contact_service = ContactService::new(repository.clone());
+        let group_service = GroupService::new(Arc::new(group_repository), repository);
+
+        Self {
+            contact_service,
+            group_service,
+            file_path,
+            #[cfg(feature = "encrypt")]
+            passphrase: None,
+            phone_policy: PhoneValidationPolicy::default(),
+            email_policy: EmailDedupPolicy::default(),
+            style: Style::plain(),
+            max_page_size: RECOMMENDED_MAX_PAGE_SIZE,
+            formatter: Box::new(ContactFormatter),
+        }
+    }
+
+    /// Use a custom phone validation policy for numbers entered on the command line
+    pub fn with_phone_policy(mut self, phone_policy: PhoneValidationPolicy) -> Self {
+        self.phone_policy = phone_policy;
+        self
+    }
+
+    /// Decide which repeated emails are dropped when adding or updating contacts
+    pub fn with_email_policy(mut self, email_policy: EmailDedupPolicy) -> Self {
+        self.email_policy = email_policy;
+        self
+    }
+
+    /// Use the given output style for human-readable output
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Allow search pages up to this size (larger than recommended sizes still warn)
+    pub fn with_max_page_size(mut self, max_page_size: usize) -> Self {
+        self.max_page_size = max_page_size;
+        self
+    }
+
+    /// Render contacts, lists and search results with a custom formatter
+    pub fn with_formatter(mut self, formatter: Box<dyn Formatter>) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    /// Send `log` records to stderr, at the level picked by `-q` and repeated `-v`
+    /// (warnings by default). Other crates only log warnings and errors.
+    /// A set `RUST_LOG` replaces both with its own directives.
+    fn init_logging(verbose: u8, quiet: bool) {
+        let level = match (quiet, verbose) {
+            (true, _) => LevelFilter::Error,
+            (false, 0) => LevelFilter::Warn,
+            (false, 1) => LevelFilter::Info,
+            (false, _) => LevelFilter::Debug,
+        };
+
+        let mut builder = env_logger::Builder::new();
+        if std::env::var_os("RUST_LOG").is_some() {
+            builder.parse_default_env();
+        } else {
+            builder
+                .filter_level(LevelFilter::Warn)
+                .filter_module(env!("CARGO_CRATE_NAME"), level);
+        }
+
+        builder
+            .format(|out, record| {
+                let prefix = match record.level() {
+                    Level::Error => "Error",
+                    Level::Warn => "Warning",
+                    Level::Info => "info",
+                    Level::Debug => "debug",
+                    Level::Trace => "trace",
+                };
+                if record.target().starts_with(env!("CARGO_CRATE_NAME")) {
+                    writeln!(out, "{}: {}", prefix, record.args())
+                } else {
+                    writeln!(out, "{} [{}]: {}", prefix, record.target(), record.args())
+                }
+            })
+            .init();
     }
 
     /// Run the CLI application
     pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         let cli = Cli::parse();
-        let app = PhonebookApp::new(cli.file);
+        Self::init_logging(cli.verbose, cli.quiet);
+        let phone_policy = PhoneValidationPolicy::new(
+            cli.phone_min_digits,
+            cli.phone_max_digits,
+            cli.allow_short_codes,
+        )?;
+        // Profiles are managed without opening a phonebook, so `profile add` works for a profile that does not exist yet
+        if let Some(Commands::Profile { action }) = cli.command {
+            return Self::handle_profile(action);
+        }
+
+        #[cfg(feature = "encrypt")]
+        let app = PhonebookApp::open_encrypted(cli.contacts_file()?, cli.recover, cli.passphrase.clone());
+        #[cfg(not(feature = "encrypt"))]
+        let app = PhonebookApp::open(cli.contacts_file()?, cli.recover);
+
+        let app = app
+            .with_phone_policy(phone_policy)
+            .with_email_policy(cli.email_dedup)
+            .with_style(
+                Style::new(cli.color.enabled())
+                    .with_name_format(cli.name_format)
+                    .with_width(cli.width.map(usize::from).or_else(terminal_width)),
+            )
+            .with_max_page_size(cli.max_page_size);
 
         match cli.command {
+            #[cfg(feature = "server")]
+            Some(Commands::Serve { addr }) => app.handle_serve(addr),
+            Some(command) => app.execute(command),
+            None => app.handle_interactive(),
+        }
+    }
+
+    /// Execute a single parsed command
+    fn execute(&self, command: Commands) -> Result<(), Box<dyn std::error::Error>> {
+        let app = self;
+
+        match command {
             Commands::Add {
                 first_name,
                 last_name,
                 phone,
                 email,
-                notes: _,
-                tag: _,
-            } => app.handle_add(first_name, last_name, phone, email),
+                notes,
+                tag,
+                birthday,
+                photo,
+                meta,
+                no_suggest,
+                no_disposable,
+                fix_email,
+                stdin_json,
+            } => {
+                if stdin_json {
+                    return app.handle_add_json(io::stdin().lock(), no_suggest, no_disposable, fix_email);
+                }
+                if !no_suggest {
+                    app.warn_similar_tags(&tag);
+                }
+                app.handle_add(
+                    first_name.unwrap_or_default(),
+                    last_name.unwrap_or_default(),
+                    phone,
+                    email,
+                    notes,
+                    tag,
+                    birthday,
+                    photo,
+                    meta,
+                    no_disposable,
+                    fix_email,
+                )
+            }
+
+            Commands::Find {
+                id,
+                by_phone,
+                by_email,
+                related,
+                format,
+            } => match (id, by_phone, by_email) {
+                (Some(id), _, _) => app.handle_find(id, related, format),
+                (None, Some(phone), _) => app.handle_find_by_phone(phone, format),
+                (None, None, Some(email)) => app.handle_find_by_email(email, format),
+                (None, None, None) => Ok(()),
+            },
 
-            Commands::Find { id } => app.handle_find(id),
+            Commands::History { id } => app.handle_history(id),
 
             Commands::List {
                 page,
                 page_size,
+                all,
                 sort_by,
                 reverse,
-            } => app.handle_list(page, page_size, sort_by, reverse),
+                columns,
+                group_by,
+                template,
+                include_deleted,
+                filter,
+                created_after,
+                created_before,
+                updated_since,
+                porcelain,
+                count_only,
+                output,
+                since_snapshot,
+                #[cfg(feature = "watch")]
+                watch,
+            } => {
+                let deleted = if include_deleted {
+                    DeletedFilter::Include
+                } else {
+                    DeletedFilter::Exclude
+                };
+                let dates = Self::parse_date_filter(created_after, created_before, updated_since)?;
+                if let Some(snapshot) = since_snapshot {
+                    return app.handle_since_snapshot(snapshot, deleted, filter, dates);
+                }
+                #[cfg(feature = "watch")]
+                if watch {
+                    return app.handle_watch(|| {
+                        app.handle_list(
+                            page, page_size, all, sort_by.clone(), reverse, columns.clone(), group_by, template.clone(),
+                            deleted, filter.clone(), dates, porcelain, count_only, None,
+                        )
+                    });
+                }
 
-            Commands::Search { query } => app.handle_search(query),
+                app.handle_list(
+                    page, page_size, all, sort_by, reverse, columns, group_by, template, deleted, filter, dates, porcelain,
+                    count_only, output,
+                )
+            }
+
+            Commands::Search {
+                query,
+                regex,
+                highlight,
+                limit,
+                offset,
+                cursor,
+                sort_by,
+                reverse,
+                no_relevance,
+                no_meta,
+                include_meta: _,
+                porcelain,
+                count_only,
+                output,
+            } => app.handle_search(
+                query, regex, highlight, limit, offset, cursor, sort_by, reverse, !no_relevance, !no_meta, porcelain,
+                count_only, output,
+            ),
 
             Commands::Update {
                 id,
                 first_name,
                 last_name,
+                clear_phones,
                 add_phone,
                 remove_phone,
+                clear_emails,
                 add_email,
                 remove_email,
-                notes: _,
-                add_tag: _,
-                remove_tag: _,
-            } => app.handle_update(
+                notes,
+                clear_tags,
+                add_tag,
+                remove_tag,
+                birthday,
+                photo,
+                meta,
+                remove_meta,
+                set,
+                expected_version,
+                dry_run,
+                no_suggest,
+            } => {
+                if !no_suggest {
+                    app.warn_similar_tags(&add_tag);
+                }
+                app.handle_update(
+                    id,
+                    first_name,
+                    last_name,
+                    clear_phones,
+                    add_phone,
+                    remove_phone,
+                    clear_emails,
+                    add_email,
+                    remove_email,
+                    notes,
+                    clear_tags,
+                    add_tag,
+                    remove_tag,
+                    birthday,
+                    photo,
+                    meta,
+                    remove_meta,
+                    set,
+                    expected_version,
+                    dry_run,
+                )
+            }
+
+            Commands::Delete {
                 id,
-                first_name,
-                last_name,
-                add_phone,
-                remove_phone,
-                add_email,
-                remove_email,
-            ),
+                yes,
+                dry_run,
+                purge,
+            } => app.handle_delete(id, yes, dry_run, purge),
+
+            Commands::Trash => app.handle_trash(),
+
+            Commands::RestoreContact { id } => app.handle_restore(id),
+
+            Commands::Touch { id } => app.handle_touch(id),
+
+            Commands::DeleteMany { ids, tag, yes } => app.handle_delete_many(ids, tag, yes),
+
+            Commands::PurgeAll { yes } => app.handle_purge_all(yes),
+
+            Commands::RenameTag { from, to } => app.handle_rename_tag(from, to),
+
+            Commands::Group { action } => app.handle_group(action),
+
+            Commands::Profile { action } => Self::handle_profile(action),
+
+            Commands::Init { path, force } => {
+                let path = path.map(|path| expand_home(&path)).unwrap_or_else(|| app.file_path.clone());
+                app.handle_init(path, force)
+            }
 
-            Commands::Delete { id, yes } => app.handle_delete(id, yes),
+            Commands::Stats { format, over_time } => match over_time {
+                Some(interval) => app.handle_stats_over_time(interval),
+                None => app.handle_stats(format),
+            },
 
-            Commands::Stats => app.handle_stats(),
+            Commands::Random { count, tag, seed } => app.handle_random(count, tag, seed),
+
+            Commands::Validate => app.handle_validate(),
+
+            Commands::Diff { other } => app.handle_diff(other),
+
+            Commands::Export {
+                format,
+                tag,
+                filter,
+                since,
+                anonymize,
+                output,
+                output_dir,
+                force,
+            } => app.handle_export(format, tag, filter, since, anonymize, output, output_dir, force),
+
+            Commands::Import { path, upsert } => app.handle_import(path, upsert),
+
+            Commands::Completions { shell, bin_name } => Self::handle_completions(shell, &bin_name),
+
+            Commands::Upcoming { days } => app.handle_upcoming(days),
+
+            Commands::Interactive => app.handle_interactive(),
+
+            #[cfg(feature = "server")]
+            Commands::Serve { .. } => Err("serve cannot be started from the interactive shell".into()),
         }
     }
 
+    fn handle_interactive(&self) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Phonebook interactive shell. Type 'help' for commands, 'history' for past commands, 'quit' to exit.");
+        let mut editor = LineEditor::new("phonebook> ")?;
+
+        while let ReadLine::Line(line) = editor.read_line()? {
+            match line.as_str() {
+                "quit" | "exit" => break,
+                "history" => {
+                    for (index, entry) in editor.history().iter().enumerate() {
+                        println!("{:>4}  {}", index + 1, entry);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let args = match split_args(&line) {
+                Ok(args) => args,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    continue;
+                }
+            };
+
+            match ShellLine::try_parse_from(args) {
+                Ok(ShellLine {
+                    command: Commands::Interactive,
+                }) => eprintln!("Error: already in the interactive shell"),
+                Ok(parsed) => {
+                    if let Err(e) = self.execute(parsed.command) {
+                        eprintln!("Error: {}", e);
+                    }
+                }
+                // Covers `help` and `--help` as well as genuine parse errors
+                Err(e) => {
+                    let _ = e.print();
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn handle_add(
         &self,
         first_name: String,
         last_name: String,
         phone_strings: Vec<String>,
         email_strings: Vec<String>,
+        notes: Option<String>,
+        tags: Vec<String>,
+        birthday_string: Option<String>,
+        photo: Option<PathBuf>,
+        meta_strings: Vec<String>,
+        no_disposable: bool,
+        fix_email: bool,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // Parse phone numbers
-        let mut phone_numbers = Vec::new();
-        for phone_str in phone_strings {
-            match PhoneNumber::try_from(phone_str.as_str()) {
-                Ok(phone) => phone_numbers.push(phone),
-                Err(e) => {
-                    eprintln!("Invalid phone number '{}': {}", phone_str, e);
-                    return Ok(());
+        // Parse every input first so all the bad ones are reported together
+        let mut errors = ValidationErrors::new();
+        let phone_numbers = Self::parse_each(phone_strings, "phone number", &mut errors, |phone| {
+            PhoneNumber::new_with_policy(phone.to_string(), &self.phone_policy)
+        });
+        let emails: Vec<Email> = Self::parse_each(email_strings, "email", &mut errors, Email::parse_labeled)
+            .into_iter()
+            .map(|email| match email.suggest_correction() {
+                Some(corrected) if fix_email => {
+                    log::warn!("Using '{}' instead of '{}'", corrected, email);
+                    corrected
                 }
-            }
-        }
-
-        // Parse emails
-        let mut emails = Vec::new();
-        for email_str in email_strings {
-            match Email::try_from(email_str.as_str()) {
-                Ok(email) => emails.push(email),
-                Err(e) => {
-                    eprintln!("Invalid email '{}': {}", email_str, e);
-                    return Ok(());
+                Some(corrected) => {
+                    log::warn!(
+                        "'{}' may be a typo of '{}' (pass --fix-email to use it)",
+                        email, corrected
+                    );
+                    email
                 }
+                None => email,
+            })
+            .collect();
+        for email in emails.iter().filter(|email| email.is_disposable()) {
+            if no_disposable {
+                errors.push(format!("Disposable email address not allowed: {}", email));
+            } else {
+                log::warn!("'{}' is a disposable email address", email);
             }
         }
+        let birthday = Self::parse_each(birthday_string, "birthday", &mut errors, Date::parse).pop();
+        let metadata = Self::parse_metadata(meta_strings).unwrap_or_else(|e| {
+            errors.push(e);
+            Vec::new()
+        });
+        errors.into_result()?;
 
         let request = AddContactRequest {
             first_name,
             last_name,
             phone_numbers,
             emails,
+            notes,
+            birthday,
+            tags,
+            photo,
+            metadata,
+            email_policy: self.email_policy,
+            ..Default::default()
         };
 
-        match self.contact_service.add_contact(request) {
-            Ok(response) => {
-                println!("✓ {}", response.message);
-                println!("Contact ID: {}", response.contact_id);
+        let response = self.contact_service.add_contact(request)?;
+        println!("✓ {}", response.message);
+        println!("Contact ID: {}", response.contact_id);
+
+        Ok(())
+    }
+
+    /// Add one contact read as a JSON object, validated the same way as `add` flags
+    fn handle_add_json<R: Read>(
+        &self,
+        reader: R,
+        no_suggest: bool,
+        no_disposable: bool,
+        fix_email: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let input: ContactJson = serde_json::from_reader(reader)
+            .map_err(|e| DomainError::Validation(format!("Invalid contact JSON: {}", e)))?;
+
+        if !no_suggest {
+            self.warn_similar_tags(&input.tags);
+        }
+        let meta = input.metadata.into_iter().map(|(key, value)| format!("{}={}", key, value)).collect();
+
+        self.handle_add(
+            input.first_name,
+            input.last_name,
+            input.phone_numbers,
+            input.emails,
+            input.notes,
+            input.tags,
+            input.birthday,
+            input.photo,
+            meta,
+            no_disposable,
+            fix_email,
+        )
+    }
+
+    fn handle_find(&self, id_str: String, related: bool, format: DisplayFormat) -> Result<(), Box<dyn std::error::Error>> {
+        let id = self.contact_service.resolve_contact_id(&id_str)?;
+
+        let show_related = related && matches!(format, DisplayFormat::Text);
+        let request = FindContactRequest { contact_id: id.clone() };
+
+        let response = self.contact_service.find_contact(request)?;
+        if response.found {
+            if let Some(contact) = response.contact {
+                self.print_contact(&contact, format)?;
             }
-            Err(e) => eprintln!("Error: {}", e),
+            if show_related {
+                self.print_related(&id)?;
+            }
+        } else {
+            println!("Contact not found");
         }
 
         Ok(())
     }
 
-    fn handle_find(&self, id_str: String) -> Result<(), Box<dyn std::error::Error>> {
-        let id = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => ContactId::from_uuid(uuid),
-            Err(_) => {
-                eprintln!("Invalid contact ID format");
-                return Ok(());
+    fn handle_find_by_phone(&self, phone_str: String, format: DisplayFormat) -> Result<(), Box<dyn std::error::Error>> {
+        let phone = PhoneNumber::new_with_policy(phone_str.clone(), &self.phone_policy)
+            .map_err(|e| DomainError::Validation(format!("Invalid phone number '{}': {}", phone_str, e)))?;
+
+        let contacts = self.contact_service.find_contacts_by_phone(&phone)?;
+        self.print_matches(&contacts, format)
+    }
+
+    fn handle_find_by_email(&self, email_str: String, format: DisplayFormat) -> Result<(), Box<dyn std::error::Error>> {
+        let email = Email::new(email_str.clone())
+            .map_err(|e| DomainError::Validation(format!("Invalid email '{}': {}", email_str, e)))?;
+
+        let contacts = self.contact_service.find_contacts_by_email(&email)?;
+        self.print_matches(&contacts, format)
+    }
+
+    /// Print a single contact in the requested format
+    fn print_contact(&self, contact: &Contact, format: DisplayFormat) -> Result<(), Box<dyn std::error::Error>> {
+        match format {
+            DisplayFormat::Text => {
+                println!("{}", self.formatter.format_contact(contact, &self.style));
             }
-        };
+            DisplayFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(contact)?);
+            }
+            DisplayFormat::VCard => {
+                print!("{}", ContactFormatter::format_contact_vcard(contact));
+            }
+        }
+        Ok(())
+    }
 
-        let request = FindContactRequest { contact_id: id };
+    /// Print the contacts sharing tags with a contact, below its details
+    fn print_related(&self, id: &ContactId) -> Result<(), Box<dyn std::error::Error>> {
+        let related = self.contact_service.find_related_contacts(id)?;
+        if related.is_empty() {
+            println!("No related contacts");
+            return Ok(());
+        }
+
+        println!("Related contacts (sharing tags):");
+        for entry in &related {
+            println!(
+                "  {}  [{}]",
+                ContactFormatter::format_contact_compact_styled(&entry.contact, &self.style.indented(2), None)
+                    .replace('\n', "\n  "),
+                entry.shared_tags.join(", ")
+            );
+        }
+        Ok(())
+    }
 
-        match self.contact_service.find_contact(request) {
-            Ok(response) => {
-                if response.found {
-                    if let Some(contact) = response.contact {
-                        println!("{}", ContactFormatter::format_contact(&contact));
+    /// Print every contact found by a phone or email lookup
+    fn print_matches(&self, contacts: &[Contact], format: DisplayFormat) -> Result<(), Box<dyn std::error::Error>> {
+        match format {
+            _ if contacts.is_empty() => println!("Contact not found"),
+            DisplayFormat::Json => println!("{}", serde_json::to_string_pretty(contacts)?),
+            DisplayFormat::VCard => print!("{}", VCardExporter::export(contacts)),
+            DisplayFormat::Text => {
+                for (index, contact) in contacts.iter().enumerate() {
+                    if index > 0 {
+                        println!();
                     }
-                } else {
-                    println!("Contact not found");
+                    self.print_contact(contact, DisplayFormat::Text)?;
                 }
             }
-            Err(e) => eprintln!("Error: {}", e),
+        }
+        Ok(())
+    }
+
+    fn handle_history(&self, id_str: String) -> Result<(), Box<dyn std::error::Error>> {
+        let id = self.contact_service.resolve_contact_id(&id_str)?;
+
+        let request = FindContactRequest { contact_id: id };
+
+        match self.contact_service.find_contact(request)?.contact {
+            Some(contact) => println!("{}", ContactFormatter::format_history(contact.history())),
+            None => println!("Contact not found"),
         }
 
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn handle_list(
         &self,
         page: usize,
         page_size: usize,
-        sort_by: SortField,
+        all: bool,
+        sort_by: Vec<SortField>,
         reverse: bool,
+        columns: Option<ColumnSpec>,
+        group_by: Option<GroupBy>,
+        template: Option<ContactTemplate>,
+        deleted: DeletedFilter,
+        filter: Option<String>,
+        dates: DateFilter,
+        porcelain: bool,
+        count_only: bool,
+        output: Option<String>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let sort_by = match sort_by {
-            SortField::FirstName => crate::application::use_cases::list_contacts::SortBy::FirstName,
-            SortField::LastName => crate::application::use_cases::list_contacts::SortBy::LastName,
-            SortField::FullName => crate::application::use_cases::list_contacts::SortBy::FullName,
-        };
+        let filter = filter.as_deref().map(FilterExpr::parse).transpose()?;
+        if !all {
+            self.warn_large_page(page_size);
+        }
 
         let request = ListContactsRequest {
             page,
             page_size,
-            sort_by,
+            max_page_size: self.max_page_size,
+            all,
+            sort_by: Self::sort_keys(sort_by),
             reverse,
+            deleted,
+            filter,
+            dates,
         };
 
-        match self.contact_service.list_contacts(request) {
-            Ok(response) => {
-                if response.contacts.is_empty() {
-                    println!("No contacts found");
-                    return Ok(());
-                }
-
-                println!("{}", ContactFormatter::format_list_header());
-                println!("{}", ContactFormatter::format_separator());
+        let response = self.contact_service.list_contacts(request)?;
+        let mut out = OutputSink::open(output.as_deref())?;
+        if count_only {
+            return Ok(Self::write_count(out, response.total_count)?);
+        }
+        if porcelain {
+            return Ok(Self::write_porcelain(out, &response.contacts)?);
+        }
+        if let Some(template) = template {
+            for contact in &response.contacts {
+                writeln!(out, "{}", template.render(contact))?;
+            }
+            return Ok(out.finish(response.contacts.len())?);
+        }
+        let style = self.style_for(&out);
 
-                for contact in &response.contacts {
-                    println!("{}", ContactFormatter::format_contact_compact(contact));
-                }
+        if response.contacts.is_empty() {
+            writeln!(out, "No contacts found")?;
+            return Ok(out.finish(0)?);
+        }
 
-                println!("{}", ContactFormatter::format_separator());
-                println!(
-                    "{}",
-                    ContactFormatter::format_pagination_info(
-                        response.page,
-                        response.page_size,
-                        response.total_count,
-                        response.has_more
-                    )
-                );
+        match (&columns, group_by) {
+            (Some(spec), _) => {
+                writeln!(out, "{}", ContactFormatter::format_table(&response.contacts, spec, &style))?;
+            }
+            (None, Some(GroupBy::Tag)) => {
+                writeln!(out, "{}", ContactFormatter::format_grouped(&response.contacts, &style))?;
+            }
+            (None, None) => {
+                writeln!(out, "{}", self.formatter.format_list(&response.contacts, &style))?;
             }
-            Err(e) => eprintln!("Error: {}", e),
         }
 
+        writeln!(out, "{}", ContactFormatter::format_separator_styled(&style))?;
+        let info = if all {
+            format!("Showing all {} contacts", response.total_count)
+        } else {
+            ContactFormatter::format_pagination_info(
+                response.current_page,
+                response.total_pages,
+                response.page_size,
+                response.total_count,
+            )
+        };
+        writeln!(out, "{}", info)?;
+        out.finish(response.contacts.len())?;
+
         Ok(())
     }
 
-    fn handle_search(&self, query: String) -> Result<(), Box<dyn std::error::Error>> {
-        let request = SearchContactsRequest { query };
+    #[allow(clippy::too_many_arguments)]
+    fn handle_search(
+        &self,
+        query: String,
+        regex: bool,
+        highlight: bool,
+        limit: usize,
+        offset: usize,
+        cursor: Option<String>,
+        sort_by: Vec<SortField>,
+        reverse: bool,
+        rank_by_relevance: bool,
+        include_metadata: bool,
+        porcelain: bool,
+        count_only: bool,
+        output: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.warn_large_page(limit);
+
+        let request = SearchContactsRequest {
+            query,
+            regex,
+            limit,
+            max_limit: self.max_page_size,
+            offset,
+            cursor,
+            sort_by: Self::sort_keys(sort_by),
+            reverse,
+            include_metadata,
+            rank_by_relevance,
+        };
+
+        let response = self.contact_service.search_contacts(request)?;
+        let mut out = OutputSink::open(output.as_deref())?;
+        if count_only {
+            return Ok(Self::write_count(out, response.count)?);
+        }
+        if porcelain {
+            return Ok(Self::write_porcelain(out, &response.contacts)?);
+        }
+        let style = self.style_for(&out);
+
+        // Regex patterns aren't literal text, so only highlight plain queries
+        let query = (!regex).then_some(response.query.as_str());
+
+        if !highlight {
+            writeln!(
+                out,
+                "{}",
+                self.formatter
+                    .format_search(&response.query, response.count, &response.contacts, &style, query)
+            )?;
+        } else {
+            // Match contexts are interleaved with the rows, so this layout is always built in
+            writeln!(
+                out,
+                "{}",
+                ContactFormatter::format_search_summary(&response.query, response.count)
+            )?;
+        }
 
-        match self.contact_service.search_contacts(request) {
-            Ok(response) => {
-                println!(
+        if highlight && !response.contacts.is_empty() {
+            writeln!(out, "{}", ContactFormatter::format_list_header_styled(&style))?;
+            writeln!(out, "{}", ContactFormatter::format_separator_styled(&style))?;
+
+            for contact in &response.contacts {
+                writeln!(
+                    out,
+                    "{}",
+                    ContactFormatter::format_contact_compact_styled(contact, &style, query)
+                )?;
+                write!(
+                    out,
                     "{}",
-                    ContactFormatter::format_search_summary(&response.query, response.count)
-                );
+                    ContactFormatter::format_match_contexts(contact, &response.query, &style)
+                )?;
+            }
+        }
 
-                if !response.contacts.is_empty() {
-                    println!("{}", ContactFormatter::format_list_header());
-                    println!("{}", ContactFormatter::format_separator());
+        if response.contacts.len() < response.count {
+            writeln!(out, "{}", ContactFormatter::format_separator_styled(&style))?;
+            writeln!(
+                out,
+                "{}",
+                ContactFormatter::format_search_window(
+                    response.offset,
+                    response.contacts.len(),
+                    response.count,
+                    response.counted_from_cursor
+                )
+            )?;
+            if let Some(next_cursor) = &response.next_cursor {
+                writeln!(out, "{}", ContactFormatter::format_next_cursor(next_cursor))?;
+            }
+        }
+        out.finish(response.contacts.len())?;
 
-                    for contact in &response.contacts {
-                        println!("{}", ContactFormatter::format_contact_compact(contact));
-                    }
+        Ok(())
+    }
+
+    /// Warn about pages larger than recommended but still within `--max-page-size`;
+    /// larger ones are rejected by validation instead
+    fn warn_large_page(&self, page_size: usize) {
+        if page_size > RECOMMENDED_MAX_PAGE_SIZE && page_size <= self.max_page_size {
+            log::warn!(
+                "showing more than {} results per page may be slow",
+                RECOMMENDED_MAX_PAGE_SIZE
+            );
+        }
+    }
+
+    /// Write contacts in the stable tab-separated format, nothing else
+    fn write_porcelain(mut out: OutputSink, contacts: &[Contact]) -> io::Result<()> {
+        for contact in contacts {
+            writeln!(out, "{}", ContactFormatter::format_porcelain(contact))?;
+        }
+        out.finish(contacts.len())
+    }
+
+    /// Parse each input, recording a `Invalid <kind> '<input>': <reason>` error for every
+    /// one that fails instead of stopping at the first
+    fn parse_each<T, E: std::fmt::Display>(
+        inputs: impl IntoIterator<Item = String>,
+        kind: &str,
+        errors: &mut ValidationErrors,
+        parse: impl Fn(&str) -> Result<T, E>,
+    ) -> Vec<T> {
+        inputs
+            .into_iter()
+            .filter_map(|input| match parse(&input) {
+                Ok(value) => Some(value),
+                Err(e) => {
+                    errors.push(format!("Invalid {} '{}': {}", kind, input, e));
+                    None
                 }
+            })
+            .collect()
+    }
+
+    /// Split `--meta key=value` arguments into key/value pairs
+    fn parse_metadata(entries: Vec<String>) -> Result<Vec<(String, String)>, String> {
+        entries
+            .into_iter()
+            .map(|entry| match entry.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => Ok((key.trim().to_string(), value.to_string())),
+                _ => Err(format!("Invalid metadata '{}': expected key=value", entry)),
+            })
+            .collect()
+    }
+
+    /// Parse the `list` date bounds, each a date or an RFC 3339 time
+    fn parse_date_filter(
+        created_after: Option<String>,
+        created_before: Option<String>,
+        updated_since: Option<String>,
+    ) -> Result<DateFilter, DomainError> {
+        let parse = |value: Option<String>| {
+            value
+                .map(|value| {
+                    Timestamp::parse_date_or_time(&value)
+                        .map_err(|_| DomainError::Validation(format!("Invalid date or time '{}'", value)))
+                })
+                .transpose()
+        };
+
+        Ok(DateFilter {
+            created_after: parse(created_after)?,
+            created_before: parse(created_before)?,
+            updated_since: parse(updated_since)?,
+        })
+    }
+
+    /// Write just the number of matches, nothing else
+    fn write_count(mut out: OutputSink, count: usize) -> io::Result<()> {
+        writeln!(out, "{}", count)?;
+        out.finish(1)
+    }
+
+    /// Warn about new tags that look like typos of existing ones
+    fn warn_similar_tags(&self, tags: &[String]) {
+        if tags.is_empty() {
+            return;
+        }
+
+        let request = SuggestTagsRequest { tags: tags.to_vec() };
+        if let Ok(response) = self.contact_service.suggest_tags(request) {
+            for (tag, existing) in response.suggestions {
+                log::warn!("tag '{}' looks like existing tag '{}'", tag, existing);
             }
-            Err(e) => eprintln!("Error: {}", e),
         }
+    }
 
-        Ok(())
+    /// Map CLI sort fields to use case sort keys
+    fn sort_keys(fields: Vec<SortField>) -> Vec<SortBy> {
+        fields
+            .into_iter()
+            .map(|field| match field {
+                SortField::FirstName => SortBy::FirstName,
+                SortField::LastName => SortBy::LastName,
+                SortField::FullName => SortBy::FullName,
+            })
+            .collect()
     }
 
+    /// Output style for a sink: files never get ANSI escapes
+    fn style_for(&self, sink: &OutputSink) -> Style {
+        if sink.is_file() {
+            Style::plain().with_name_format(self.style.name_format())
+        } else {
+            self.style
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn handle_update(
         &self,
         id_str: String,
         first_name: Option<String>,
         last_name: Option<String>,
+        clear_phone_numbers: bool,
         add_phone_strings: Vec<String>,
         remove_phone_strings: Vec<String>,
+        clear_emails: bool,
         add_email_strings: Vec<String>,
         remove_email_strings: Vec<String>,
+        notes: Option<String>,
+        clear_tags: bool,
+        add_tags: Vec<String>,
+        remove_tags: Vec<String>,
+        birthday_string: Option<String>,
+        photo_string: Option<String>,
+        meta_strings: Vec<String>,
+        remove_metadata: Vec<String>,
+        set_strings: Vec<String>,
+        expected_version: Option<u64>,
+        dry_run: bool,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let id = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => ContactId::from_uuid(uuid),
-            Err(_) => {
-                eprintln!("Invalid contact ID format");
-                return Ok(());
-            }
-        };
+        let id = self.contact_service.resolve_contact_id(&id_str)?;
 
-        // Parse phone numbers to add
-        let mut add_phone_numbers = Vec::new();
-        for phone_str in add_phone_strings {
-            match PhoneNumber::try_from(phone_str.as_str()) {
-                Ok(phone) => add_phone_numbers.push(phone),
-                Err(e) => {
-                    eprintln!("Invalid phone number '{}': {}", phone_str, e);
-                    return Ok(());
-                }
-            }
-        }
+        // Parse every input first so all the bad ones are reported together
+        let mut errors = ValidationErrors::new();
+        let parse_phone = |phone: &str| PhoneNumber::new_with_policy(phone.to_string(), &self.phone_policy);
+        let add_phone_numbers = Self::parse_each(add_phone_strings, "phone number", &mut errors, parse_phone);
+        let remove_phone_numbers = Self::parse_each(remove_phone_strings, "phone number", &mut errors, parse_phone);
+        let add_emails = Self::parse_each(add_email_strings, "email", &mut errors, Email::parse_labeled);
+        let remove_emails = Self::parse_each(remove_email_strings, "email", &mut errors, Email::parse_labeled);
 
-        // Parse phone numbers to remove
-        let mut remove_phone_numbers = Vec::new();
-        for phone_str in remove_phone_strings {
-            match PhoneNumber::try_from(phone_str.as_str()) {
-                Ok(phone) => remove_phone_numbers.push(phone),
-                Err(e) => {
-                    eprintln!("Invalid phone number '{}': {}", phone_str, e);
-                    return Ok(());
-                }
-            }
-        }
+        // Parse birthday (empty clears it)
+        let birthday = match birthday_string {
+            Some(birthday_str) if birthday_str.trim().is_empty() => Some(None),
+            Some(birthday_str) => Some(Self::parse_each(Some(birthday_str), "birthday", &mut errors, Date::parse).pop()),
+            None => None,
+        };
 
-        // Parse emails to add
-        let mut add_emails = Vec::new();
-        for email_str in add_email_strings {
-            match Email::try_from(email_str.as_str()) {
-                Ok(email) => add_emails.push(email),
-                Err(e) => {
-                    eprintln!("Invalid email '{}': {}", email_str, e);
-                    return Ok(());
-                }
+        // Parse photo path (empty clears it)
+        let photo = photo_string.map(|path| {
+            if path.trim().is_empty() {
+                None
+            } else {
+                Some(PathBuf::from(path))
             }
-        }
+        });
 
-        // Parse emails to remove
-        let mut remove_emails = Vec::new();
-        for email_str in remove_email_strings {
-            match Email::try_from(email_str.as_str()) {
-                Ok(email) => remove_emails.push(email),
-                Err(e) => {
-                    eprintln!("Invalid email '{}': {}", email_str, e);
-                    return Ok(());
-                }
-            }
-        }
+        let set_metadata = Self::parse_metadata(meta_strings).unwrap_or_else(|e| {
+            errors.push(e);
+            Vec::new()
+        });
+        let set_fields = Self::parse_each(set_strings, "field assignment", &mut errors, |entry| match entry.split_once('=') {
+            Some((field, value)) if !field.trim().is_empty() => Ok((field.trim().to_string(), value.to_string())),
+            _ => Err("expected field=value"),
+        });
+        errors.into_result()?;
 
         let request = UpdateContactRequest {
             contact_id: id,
             first_name,
             last_name,
-            notes: None,
+            notes,
+            birthday,
+            photo,
+            clear_phone_numbers,
             add_phone_numbers,
             remove_phone_numbers,
+            clear_emails,
             add_emails,
             remove_emails,
-            add_tags: Vec::new(),
-            remove_tags: Vec::new(),
+            clear_tags,
+            add_tags,
+            remove_tags,
+            set_metadata,
+            remove_metadata,
+            set_fields,
+            email_policy: self.email_policy,
+            expected_version,
+            dry_run,
+        };
+
+        let response = self.contact_service.update_contact(request)?;
+        if dry_run {
+            println!("{}", ContactFormatter::format_contact_styled(&response.contact, &self.style));
+        }
+        println!("✓ {}", response.message);
+
+        Ok(())
+    }
+
+    fn handle_delete(
+        &self,
+        id_str: String,
+        skip_confirmation: bool,
+        dry_run: bool,
+        purge: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // Only a purge can reach contacts already in the trash
+        let id = if purge {
+            self.contact_service.resolve_any_contact_id(&id_str)?
+        } else {
+            self.contact_service.resolve_contact_id(&id_str)?
         };
 
-        match self.contact_service.update_contact(request) {
-            Ok(response) => {
-                println!("✓ {}", response.message);
+        // Show contact details and ask for confirmation
+        if !skip_confirmation && !dry_run {
+            let preview = self.contact_service.delete_contact(DeleteContactRequest {
+                contact_id: id.clone(),
+                dry_run: true,
+                purge,
+            })?;
+
+            println!("Contact to delete:");
+            println!("{}", ContactFormatter::format_contact(&preview.contact));
+
+            if !Self::confirm("Are you sure you want to delete this contact?", io::stdin().is_terminal())? {
+                println!("{}", DELETION_CANCELLED);
+                return Ok(());
             }
-            Err(e) => eprintln!("Error: {}", e),
         }
 
+        let request = DeleteContactRequest {
+            contact_id: id,
+            dry_run,
+            purge,
+        };
+
+        let response = self.contact_service.delete_contact(request)?;
+        if dry_run {
+            println!("Contact to delete:");
+            println!("{}", ContactFormatter::format_contact_styled(&response.contact, &self.style));
+        }
+        println!("✓ {}", response.message);
+
         Ok(())
     }
 
-    fn handle_delete(&self, id_str: String, skip_confirmation: bool) -> Result<(), Box<dyn std::error::Error>> {
-        let id = match Uuid::parse_str(&id_str) {
-            Ok(uuid) => ContactId::from_uuid(uuid),
-            Err(_) => {
-                eprintln!("Invalid contact ID format");
+    /// Ask a yes/no question on stdin; answers starting with `y` confirm
+    /// Without an interactive stdin nothing is read and an error asks for `--yes` instead,
+    /// so scripts fail fast rather than hang or act on piped input
+    fn confirm(question: &str, interactive: bool) -> Result<bool, Box<dyn std::error::Error>> {
+        if !interactive {
+            return Err(NON_INTERACTIVE_CONFIRMATION.into());
+        }
+
+        print!("{} {}", question, CONFIRM_SUFFIX);
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        Ok(input.trim().to_lowercase().starts_with('y'))
+    }
+
+    fn handle_delete_many(
+        &self,
+        ids: Vec<String>,
+        tag: Option<String>,
+        skip_confirmation: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // Resolve the targets first so the user can confirm the count
+        if !skip_confirmation {
+            let preview_request = DeleteManyContactsRequest {
+                ids: ids.clone(),
+                tag: tag.clone(),
+                dry_run: true,
+            };
+
+            let preview = self.contact_service.delete_many_contacts(preview_request)?;
+            if preview.deleted.is_empty() {
+                println!("No matching contacts to delete");
+                for (input, reason) in &preview.failed {
+                    eprintln!("  ✗ {}: {}", input, reason);
+                }
+                return Ok(());
+            }
+
+            let question = format!("Are you sure you want to delete {} contact(s)?", preview.deleted.len());
+            if !Self::confirm(&question, io::stdin().is_terminal())? {
+                println!("{}", DELETION_CANCELLED);
                 return Ok(());
             }
+        }
+
+        let request = DeleteManyContactsRequest {
+            ids,
+            tag,
+            dry_run: false,
         };
 
-        // Show contact details and ask for confirmation
+        let response = self.contact_service.delete_many_contacts(request)?;
+        println!(
+            "{}",
+            ContactFormatter::format_bulk_delete_summary(response.deleted.len(), &response.failed)
+        );
+
+        Ok(())
+    }
+
+    fn handle_purge_all(&self, skip_confirmation: bool) -> Result<(), Box<dyn std::error::Error>> {
+        // A plain y/N is too easy to give by reflex, so the user types the count instead
         if !skip_confirmation {
-            let find_request = FindContactRequest { contact_id: id.clone() };
-            match self.contact_service.find_contact(find_request) {
-                Ok(response) => {
-                    if !response.found {
-                        eprintln!("Contact not found");
-                        return Ok(());
+            let preview = self.contact_service.purge_all_contacts(PurgeAllContactsRequest { dry_run: true })?;
+
+            if preview.removed == 0 {
+                println!("No contacts to delete");
+                return Ok(());
+            }
+
+            let question = format!(
+                "This permanently deletes all {} contact(s), including any in the trash.",
+                preview.removed
+            );
+            let stdin = io::stdin();
+            if !Self::confirm_count(&question, preview.removed, stdin.is_terminal(), stdin.lock())? {
+                println!("{}", DELETION_CANCELLED);
+                return Ok(());
+            }
+        }
+
+        let response = self.contact_service.purge_all_contacts(PurgeAllContactsRequest { dry_run: false })?;
+        println!("✓ Permanently deleted {} contact(s)", response.removed);
+
+        Ok(())
+    }
+
+    /// Ask the user to type `expected` to confirm, for operations that can't be undone
+    /// Like `confirm`, this fails instead of reading when stdin isn't interactive
+    fn confirm_count(
+        question: &str,
+        expected: usize,
+        interactive: bool,
+        mut reader: impl BufRead,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        if !interactive {
+            return Err(NON_INTERACTIVE_CONFIRMATION.into());
+        }
+
+        println!("{}", question);
+        print!("Type the number of contacts ({}) to confirm: ", expected);
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        reader.read_line(&mut input)?;
+        Ok(input.trim() == expected.to_string())
+    }
+
+    fn handle_rename_tag(&self, from: String, to: String) -> Result<(), Box<dyn std::error::Error>> {
+        let request = RenameTagRequest { from, to };
+
+        let response = self.contact_service.rename_tag(request)?;
+        println!(
+            "✓ Renamed tag '{}' to '{}' on {} contact(s)",
+            response.from, response.to, response.affected
+        );
+
+        Ok(())
+    }
+
+    fn handle_trash(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let request = ListContactsRequest {
+            all: true,
+            deleted: DeletedFilter::Only,
+            ..Default::default()
+        };
+
+        let response = self.contact_service.list_contacts(request)?;
+        if response.contacts.is_empty() {
+            println!("Trash is empty");
+            return Ok(());
+        }
+
+        println!("{}", ContactFormatter::format_list_header_styled(&self.style));
+        println!("{}", ContactFormatter::format_separator_styled(&self.style));
+        for contact in &response.contacts {
+            println!(
+                "{}",
+                ContactFormatter::format_contact_compact_styled(contact, &self.style, None)
+            );
+        }
+        println!("{}", ContactFormatter::format_separator_styled(&self.style));
+        println!("{} contact(s) in the trash", response.total_count);
+
+        Ok(())
+    }
+
+    fn handle_restore(&self, id_str: String) -> Result<(), Box<dyn std::error::Error>> {
+        let id = self.contact_service.resolve_deleted_contact_id(&id_str)?;
+        let response = self.contact_service.restore_contact(RestoreContactRequest { contact_id: id })?;
+        println!("✓ {}", response.message);
+        Ok(())
+    }
+
+    fn handle_touch(&self, id_str: String) -> Result<(), Box<dyn std::error::Error>> {
+        let id = self.contact_service.resolve_contact_id(&id_str)?;
+        let response = self.contact_service.touch_contact(TouchContactRequest { contact_id: id })?;
+        println!("✓ {}", response.message);
+        Ok(())
+    }
+
+    fn handle_stats(&self, format: StatsFormat) -> Result<(), Box<dyn std::error::Error>> {
+        let response = self.contact_service.stats(StatsRequest)?;
+        match format {
+            StatsFormat::Table => println!("{}", ContactFormatter::format_stats(&response.stats)),
+            StatsFormat::Csv => println!("{}", ContactFormatter::format_stats_csv(&response.stats)),
+            StatsFormat::Json => println!("{}", serde_json::to_string_pretty(&response.stats)?),
+        }
+
+        Ok(())
+    }
+
+    fn handle_stats_over_time(&self, interval: StatsInterval) -> Result<(), Box<dyn std::error::Error>> {
+        let bucket = match interval {
+            StatsInterval::Day => TimeBucket::Day,
+            StatsInterval::Week => TimeBucket::Week,
+            StatsInterval::Month => TimeBucket::Month,
+        };
+
+        let buckets = self.contact_service.stats_over_time(bucket)?;
+        println!("{}", ContactFormatter::format_histogram(&buckets));
+
+        Ok(())
+    }
+
+    /// Storage for a contacts file, using the phonebook's passphrase if it has one
+    fn storage(&self, path: String) -> FileStorage {
+        let storage = FileStorage::new(path);
+        #[cfg(feature = "encrypt")]
+        let storage = storage.with_passphrase(self.passphrase.clone());
+        storage
+    }
+
+    fn handle_group(&self, action: GroupAction) -> Result<(), Box<dyn std::error::Error>> {
+        let result = match action {
+            GroupAction::Create { name } => self
+                .group_service
+                .create_group(CreateGroupRequest { name })
+                .map(|response| println!("✓ {}: {}", response.message, response.group.name())),
+
+            GroupAction::AddMember { group, id } => self
+                .contact_service
+                .resolve_contact_id(&id)
+                .and_then(|contact_id| {
+                    self.group_service
+                        .add_group_member(AddGroupMemberRequest { group, contact_id })
+                })
+                .map(|response| println!("✓ {}: {}", response.message, response.group.name())),
+
+            GroupAction::RemoveMember { group, id } => self
+                .contact_service
+                .resolve_contact_id(&id)
+                .and_then(|contact_id| {
+                    self.group_service
+                        .remove_group_member(RemoveGroupMemberRequest { group, contact_id })
+                })
+                .map(|response| println!("✓ {}: {}", response.message, response.group.name())),
+
+            GroupAction::List { name } => self
+                .group_service
+                .list_groups(ListGroupsRequest { name })
+                .map(|response| {
+                    if response.groups.is_empty() {
+                        println!("No groups found");
                     }
-                    
-                    if let Some(contact) = response.contact {
-                        println!("Contact to delete:");
-                        println!("{}", ContactFormatter::format_contact(&contact));
-                        
-                        print!("Are you sure you want to delete this contact? (y/N): ");
-                        io::stdout().flush()?;
-                        
-                        let mut input = String::new();
-                        io::stdin().read_line(&mut input)?;
-                        
-                        if !input.trim().to_lowercase().starts_with('y') {
-                            println!("Deletion cancelled");
-                            return Ok(());
+
+                    for listing in &response.groups {
+                        println!(
+                            "{}",
+                            ContactFormatter::format_group_header(&listing.group, listing.members.len())
+                        );
+                        for contact in &listing.members {
+                            println!(
+                                "  {}",
+                                ContactFormatter::format_contact_compact_styled(contact, &self.style.indented(2), None)
+                                    .replace('\n', "\n  ")
+                            );
                         }
                     }
+                }),
+        };
+
+        Ok(result?)
+    }
+
+    /// Create an empty phonebook at `path`, then suggest what to do next
+    /// Hints only name `path` when it differs from the file commands use without `--file`
+    fn handle_init(&self, path: String, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let current_path = self.file_path.as_str();
+        if !force && Path::new(&path).exists() {
+            return Err(RepositoryError::StorageError(format!(
+                "{} already exists; pass --force to replace it with an empty phonebook",
+                path
+            ))
+            .into());
+        }
+
+        self.storage(path.clone()).create_empty()?;
+        println!("✓ Created an empty phonebook at {}", path);
+
+        let file_arg = if path == current_path {
+            String::new()
+        } else {
+            format!("--file {} ", path)
+        };
+        println!("Next steps:");
+        println!("  phonebook {}add --first-name Ada --last-name Lovelace --email ada@example.com", file_arg);
+        println!("  phonebook {}import contacts.csv", file_arg);
+        println!("  phonebook {}list", file_arg);
+
+        Ok(())
+    }
+
+    fn handle_profile(action: ProfileAction) -> Result<(), Box<dyn std::error::Error>> {
+        let config_path = ProfileConfig::default_path().ok_or_else(|| {
+            RepositoryError::StorageError("Cannot locate the profiles config: HOME is not set".to_string())
+        })?;
+        let mut config = ProfileConfig::load(&config_path)?;
+
+        match action {
+            ProfileAction::List => {
+                if config.profiles().is_empty() {
+                    println!("No profiles configured ({})", config_path.display());
                 }
-                Err(e) => {
-                    eprintln!("Error finding contact: {}", e);
-                    return Ok(());
+                for (name, path) in config.profiles() {
+                    println!("{:<16} {}", name, path);
+                }
+            }
+
+            ProfileAction::Add { name, path } => {
+                // Relative paths are pinned to the current directory so the profile works from anywhere
+                let path = if path.starts_with('~') {
+                    path
+                } else {
+                    std::path::absolute(&path)?.to_string_lossy().into_owned()
+                };
+                let replaced = config.add(&name, &path)?;
+                config.save(&config_path)?;
+                let verb = if replaced { "updated" } else { "added" };
+                println!("✓ Profile '{}' {}: {}", name, verb, path);
+            }
+
+            ProfileAction::Remove { name } => {
+                if config.remove(&name) {
+                    config.save(&config_path)?;
+                    println!("✓ Profile '{}' removed", name);
+                } else {
+                    return Err(format!("Profile '{}' not found", name).into());
                 }
             }
         }
 
-        let request = DeleteContactRequest { contact_id: id };
+        Ok(())
+    }
 
-        match self.contact_service.delete_contact(request) {
-            Ok(response) => {
-                println!("✓ {}", response.message);
+    fn handle_random(&self, count: usize, tag: Option<String>, seed: Option<u64>) -> Result<(), Box<dyn std::error::Error>> {
+        let request = RandomContactsRequest { count, tag, seed };
+
+        let response = self.contact_service.random_contacts(request)?;
+        if response.contacts.is_empty() {
+            println!("No contacts found");
+            return Ok(());
+        }
+
+        for (index, contact) in response.contacts.iter().enumerate() {
+            if index > 0 {
+                println!();
             }
-            Err(e) => eprintln!("Error: {}", e),
+            println!("{}", ContactFormatter::format_contact_styled(contact, &self.style));
         }
 
         Ok(())
     }
 
-    fn handle_stats(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let request = ListContactsRequest::default();
+    fn handle_validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let entries = self.storage(self.file_path.clone()).load_raw_entries()?;
+        let issues = IntegrityChecker::new(self.phone_policy.clone()).check(&entries);
+
+        if issues.is_empty() {
+            println!("✓ {} contact(s) checked, no problems found", entries.len());
+            return Ok(());
+        }
+
+        for issue in &issues {
+            println!("✗ {}", issue);
+        }
+
+        Err(format!("{} problem(s) found in {} contact(s)", issues.len(), entries.len()).into())
+    }
+
+    fn handle_diff(&self, other: String) -> Result<(), Box<dyn std::error::Error>> {
+        let other_path = expand_home(&other);
+        let mut ours: Vec<Contact> = self.storage(self.file_path.clone()).load_contacts()?.into_values().collect();
+        let mut theirs: Vec<Contact> = self.storage(other_path).load_contacts()?.into_values().collect();
+
+        // Sorted input gives a stable report order
+        for contacts in [&mut ours, &mut theirs] {
+            sort_contacts(contacts, &[SortBy::LastName, SortBy::FirstName], false);
+        }
+
+        let diff = diff_contacts(&ours, &theirs);
+
+        println!("{}", ContactFormatter::format_diff(&diff, &other));
+        Ok(())
+    }
+
+    fn handle_since_snapshot(
+        &self,
+        snapshot: String,
+        deleted: DeletedFilter,
+        filter: Option<String>,
+        dates: DateFilter,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let filter = filter.as_deref().map(FilterExpr::parse).transpose()?;
+        let snapshot_path = expand_home(&snapshot);
+        if !Path::new(&snapshot_path).exists() {
+            return Err(RepositoryError::StorageError(format!("Snapshot {} does not exist", snapshot)).into());
+        }
+        let snapshot_contacts = self
+            .storage(snapshot_path)
+            .load_snapshot()
+            .map_err(|e| RepositoryError::StorageError(format!("Failed to read snapshot {}: {}", snapshot, e)))?;
+        let mut current: Vec<Contact> = self.storage(self.file_path.clone())
+            .load_contacts()?
+            .into_values()
+            .filter(|contact| deleted == DeletedFilter::Include || !contact.is_deleted())
+            .filter(|contact| dates.matches(contact))
+            .filter(|contact| filter.as_ref().is_none_or(|filter| filter.matches(contact)))
+            .collect();
+        sort_contacts(&mut current, &[SortBy::LastName, SortBy::FirstName], false);
+
+        let changes = changes_since(&snapshot_contacts, &current);
+
+        println!("{}", ContactFormatter::format_snapshot_changes(&changes, &snapshot));
+        Ok(())
+    }
+
+    fn handle_upcoming(&self, days: u32) -> Result<(), Box<dyn std::error::Error>> {
+        let request = UpcomingBirthdaysRequest { days, today: None };
+
+        let response = self.contact_service.upcoming_birthdays(request)?;
+        if response.contacts.is_empty() {
+            println!("No birthdays in the next {} days", response.days);
+            return Ok(());
+        }
+
+        for upcoming in &response.contacts {
+            println!(
+                "{}",
+                ContactFormatter::format_upcoming_birthday(&upcoming.contact, upcoming.days_until)
+            );
+        }
 
-        match self.contact_service.list_contacts(request) {
-            Ok(response) => {
-                println!("{}", ContactFormatter::format_stats(response.total_count));
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn handle_export(
+        &self,
+        format: ExportFormat,
+        tag: Option<String>,
+        filter: Option<String>,
+        since: Option<String>,
+        anonymize: bool,
+        output: Option<String>,
+        output_dir: Option<String>,
+        force: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if output_dir.is_some() && !matches!(format, ExportFormat::VCard) {
+            return Err(DomainError::Validation("--output-dir is only supported with --format vcard".to_string()).into());
+        }
+
+        let filter = filter.as_deref().map(FilterExpr::parse).transpose()?;
+
+        let since = since.as_deref().map(Timestamp::parse).transpose()?;
+
+        let request = ExportContactsRequest { tag, filter, since };
+        if matches!(format, ExportFormat::JsonLines) {
+            return self.handle_export_jsonl(&request, anonymize, output);
+        }
+
+        let mut response = self.contact_service.export_contacts(request)?;
+        if anonymize {
+            response.contacts = response.contacts.iter().map(Contact::anonymize).collect();
+        }
+
+        if let Some(dir) = output_dir {
+            let dir = expand_home(&dir);
+            let written = VCardExporter::export_to_dir(&response.contacts, Path::new(&dir), force)?;
+            println!("✓ Wrote {} vCard file(s) to {}", written, dir);
+            return Ok(());
+        }
+
+        let mut writer = OutputSink::open(output.as_deref())?;
+
+        match format {
+            ExportFormat::Json => {
+                serde_json::to_writer_pretty(&mut writer, &response.contacts)?;
+                writeln!(writer)?;
+            }
+            ExportFormat::JsonLines => unreachable!("JSON Lines exports are streamed"),
+            ExportFormat::VCard => {
+                write!(writer, "{}", VCardExporter::export(&response.contacts))?;
+            }
+            ExportFormat::Markdown => {
+                write!(writer, "{}", MarkdownExporter::export(&response.contacts))?;
             }
-            Err(e) => eprintln!("Error: {}", e),
         }
+        writer.flush()?;
+
+        Self::report_export(&writer, since.is_some(), response.contacts.len(), response.total_count);
+        Ok(())
+    }
 
+    /// Export as JSON Lines, writing each contact straight from the repository
+    /// instead of copying the selection first
+    fn handle_export_jsonl(
+        &self,
+        request: &ExportContactsRequest,
+        anonymize: bool,
+        output: Option<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = OutputSink::open(output.as_deref())?;
+
+        let summary = self.contact_service.export_contacts_streaming(request, &mut |contacts| {
+            let contacts = contacts.iter().map(|contact| {
+                if anonymize {
+                    Cow::Owned(contact.anonymize())
+                } else {
+                    Cow::Borrowed(*contact)
+                }
+            });
+            let progress = Progress::start("Exporting", contacts.len());
+            let contacts = contacts.inspect(|_| progress.inc());
+            FileStorage::export_jsonl(contacts, &mut writer)?;
+            progress.finish();
+            Ok(())
+        });
+        let summary = summary?;
+
+        Self::report_export(&writer, request.since.is_some(), summary.exported, summary.total_count);
         Ok(())
     }
+
+    /// Confirm where an export went; an export to stdout is left clean
+    fn report_export(writer: &OutputSink, incremental: bool, exported: usize, total_count: usize) {
+        match writer.path() {
+            Some(path) if incremental => {
+                println!("✓ Exported {} of {} contact(s) to {}", exported, total_count, path)
+            }
+            Some(path) => println!("✓ Exported {} contact(s) to {}", exported, path),
+            None if incremental => eprintln!("Exported {} of {} contact(s)", exported, total_count),
+            None => {}
+        }
+    }
+
+    fn handle_completions(shell: Shell, bin_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        // Render into memory first: `generate` panics on write errors such as a closed pipe
+        let mut script = Vec::new();
+        clap_complete::generate(shell, &mut Cli::command(), bin_name, &mut script);
+        io::stdout().lock().write_all(&script)?;
+        Ok(())
+    }
+
+    fn handle_import(&self, path: String, upsert: bool) -> Result<(), Box<dyn std::error::Error>> {
+        let records = if path == "-" {
+            CsvImporter::import(io::stdin().lock())
+        } else {
+            let file = File::open(&path)
+                .map_err(|e| RepositoryError::IoError(format!("Failed to open {}: {}", path, e)))?;
+            CsvImporter::import(file)
+        };
+
+        let records = records?;
+
+        let mut imported = 0;
+        let mut replaced = 0;
+        let mut failed = 0;
+        let progress = Progress::start("Importing", records.len());
+        for record in records {
+            let line = record.line;
+            match self.import_record(record, upsert) {
+                Ok(false) => imported += 1,
+                Ok(true) => replaced += 1,
+                Err(e) => {
+                    progress.message(format_args!("Line {}: {}", line, e));
+                    failed += 1;
+                }
+            }
+            progress.inc();
+        }
+        progress.finish();
+
+        println!("✓ {} contact(s) imported", imported);
+        if replaced > 0 {
+            println!("✓ {} contact(s) replaced", replaced);
+        }
+        if failed > 0 {
+            println!("⚠ {} row(s) skipped", failed);
+        }
+
+        Ok(())
+    }
+
+    /// Parse the cells of one CSV row and add it as a contact
+    /// Returns whether an existing contact was replaced (only with `upsert`)
+    fn import_record(&self, record: CsvRecord, upsert: bool) -> Result<bool, String> {
+        let id = match record.id.as_str() {
+            "" => None,
+            id => Some(ContactId::parse(id).map_err(|e| e.to_string())?),
+        };
+
+        let (phone_numbers, invalid) = PhoneNumber::parse_many_with_policy(&record.phones, &self.phone_policy);
+        if let Some(fragment) = invalid.first() {
+            return Err(format!("Invalid phone number '{}'", fragment));
+        }
+
+        let emails = record
+            .emails
+            .split([',', ';', ' '])
+            .filter(|fragment| !fragment.trim().is_empty())
+            .map(|fragment| Email::parse_labeled(fragment.trim()).map_err(|e| format!("Invalid email '{}': {}", fragment, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let birthday = match record.birthday.as_str() {
+            "" => None,
+            birthday => Some(Date::parse(birthday).map_err(|e| format!("Invalid birthday '{}': {}", birthday, e))?),
+        };
+
+        let request = AddContactRequest {
+            first_name: record.first_name,
+            last_name: record.last_name,
+            phone_numbers,
+            emails,
+            notes: Some(record.notes),
+            birthday,
+            tags: record.tags.split([',', ';']).map(|tag| tag.trim().to_string()).collect(),
+            id,
+            upsert,
+            email_policy: self.email_policy,
+            ..Default::default()
+        };
+
+        self.contact_service
+            .add_contact(request)
+            .map(|response| response.replaced)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Clear the terminal and run `render` now and after every change to the contacts file
+    /// Runs until interrupted; Ctrl-C ends the process, which has nothing left to flush
+    #[cfg(feature = "watch")]
+    fn handle_watch<F>(&self, mut render: F) -> Result<(), Box<dyn std::error::Error>>
+    where
+        F: FnMut() -> Result<(), Box<dyn std::error::Error>>,
+    {
+        let clear = io::stdout().is_terminal();
+        crate::infrastructure::watch::FileWatcher::new(self.file_path.clone()).run(|| {
+            if clear {
+                print!("\x1b[2J\x1b[H");
+            }
+            render()?;
+            io::stdout().flush()?;
+            Ok(())
+        })
+    }
+
+    #[cfg(feature = "server")]
+    fn handle_serve(self, addr: String) -> Result<(), Box<dyn std::error::Error>> {
+        println!("Serving phonebook API on http://{}", addr);
+        crate::presentation::http::HttpServer::new(self.contact_service, self.phone_policy).serve(addr)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmation_is_refused_without_an_interactive_stdin() {
+        let error = PhonebookApp::confirm("Delete?", false).unwrap_err();
+        assert_eq!(error.to_string(), NON_INTERACTIVE_CONFIRMATION);
+    }
+
+    #[test]
+    fn purge_confirmation_needs_the_contact_count_typed() {
+        let confirm = |input: &str, interactive| PhonebookApp::confirm_count("Delete?", 3, interactive, input.as_bytes());
+
+        assert!(confirm("3\n", true).unwrap());
+        assert!(confirm(" 3 \n", true).unwrap());
+        assert!(!confirm("y\n", true).unwrap());
+        assert!(!confirm("yes\n", true).unwrap());
+        assert!(!confirm("", true).unwrap());
+        assert!(confirm("3\n", false).is_err(), "non-interactive input must not confirm");
+    }
+
+    fn stored_contacts(path: &str) -> Vec<Contact> {
+        use crate::domain::repositories::ContactRepositorySync;
+        FileContactRepository::new(path.to_string()).find_all().unwrap()
+    }
+
+    #[test]
+    fn json_contacts_are_added_from_a_reader() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+        let json = r#"{
+            "first_name": "Ann",
+            "last_name": "Tester",
+            "phone_numbers": ["555-000-0001"],
+            "emails": ["ann@example.com"],
+            "tags": ["work"],
+            "metadata": { "team": "blue" }
+        }"#;
+
+        PhonebookApp::new(path.clone()).handle_add_json(json.as_bytes(), true, false, false).unwrap();
+
+        let contacts = stored_contacts(&path);
+        assert_eq!(contacts.len(), 1);
+        assert_eq!(contacts[0].full_name(), "Ann Tester");
+        assert_eq!(contacts[0].phone_numbers()[0].value(), "5550000001");
+        assert_eq!(contacts[0].tags(), ["work"]);
+        assert_eq!(contacts[0].metadata().get("team").map(String::as_str), Some("blue"));
+    }
+
+    #[test]
+    fn malformed_or_invalid_json_contacts_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+        let app = PhonebookApp::new(path.clone());
+        let add = |json: &str| app.handle_add_json(json.as_bytes(), true, false, false).unwrap_err().to_string();
+
+        assert!(add(r#"{"first_name": "Ann""#).contains("Invalid contact JSON"));
+        assert!(add(r#"{"first_name": "Ann", "last_name": "Tester", "nickname": "A"}"#).contains("nickname"));
+        assert!(add(r#"{"first_name": "Ann", "last_name": "Tester", "phone_numbers": ["abc"]}"#).contains("'abc'"));
+        assert!(add(r#"{"first_name": "Ann", "last_name": "Tester", "emails": ["not-an-email"]}"#).contains("not-an-email"));
+        assert!(stored_contacts(&path).is_empty());
+    }
 }
@@ -1,40 +1,162 @@
+use crate::application::validation::RECOMMENDED_MAX_PAGE_SIZE;
+use crate::domain::entities::{EmailDedupPolicy, NameFormat};
+use crate::domain::repositories::RepositoryError;
+use crate::infrastructure::config::ProfileConfig;
+use crate::presentation::cli::formatters::ColumnSpec;
+use crate::presentation::cli::style::ColorChoice;
+use crate::presentation::cli::template::ContactTemplate;
 use clap::{Parser, Subcommand};
+use clap_complete::Shell;
 use std::env;
+use std::path::PathBuf;
 
-/// Get default contacts file path, supporting Docker environment
+/// Get default contacts file path
+/// `PHONEBOOK_FILE` wins over `DPBOOK_DATA_DIR` (Docker), which wins over `contacts.json`
 fn default_contacts_file() -> String {
-    if let Ok(data_dir) = env::var("DPBOOK_DATA_DIR") {
+    if let Ok(file) = env::var("PHONEBOOK_FILE") {
+        file
+    } else if let Ok(data_dir) = env::var("DPBOOK_DATA_DIR") {
         format!("{}/contacts.json", data_dir)
     } else {
         "contacts.json".to_string()
     }
 }
 
+impl Cli {
+    /// Contacts file to open: `--file` wins over `--profile`, which wins over the default
+    pub fn contacts_file(&self) -> Result<String, RepositoryError> {
+        if let Some(file) = &self.file {
+            return Ok(expand_home(file));
+        }
+
+        let Some(profile) = &self.profile else {
+            return Ok(expand_home(&default_contacts_file()));
+        };
+
+        let config_path = ProfileConfig::default_path().ok_or_else(|| {
+            RepositoryError::StorageError("Cannot locate the profiles config: HOME is not set".to_string())
+        })?;
+        match ProfileConfig::load(&config_path)?.get(profile) {
+            Some(path) => Ok(expand_home(path)),
+            None => Err(RepositoryError::StorageError(format!(
+                "Unknown profile '{}' (see `phonebook profile list`)",
+                profile
+            ))),
+        }
+    }
+}
+
+/// Expand a leading `~` to the user's home directory
+pub fn expand_home(path: &str) -> String {
+    let rest = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => rest,
+        _ => return path.to_string(),
+    };
+
+    match env::var("HOME") {
+        Ok(home) => format!("{}{}", home, rest),
+        Err(_) => path.to_string(),
+    }
+}
+
 /// Phonebook CLI Application
 #[derive(Parser)]
 #[command(name = "phonebook")]
 #[command(about = "A CLI phonebook application")]
 #[command(version = "1.0")]
 pub struct Cli {
+    /// Command to run (starts the interactive shell when omitted)
     #[command(subcommand)]
-    pub command: Commands,
+    pub command: Option<Commands>,
+
+    /// Path to the contacts file (defaults to the --profile file, then $PHONEBOOK_FILE, then contacts.json)
+    #[arg(short, long)]
+    pub file: Option<String>,
+
+    /// Use the contacts file of a named profile (see `phonebook profile list`)
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// Minimum digits accepted in a phone number (excluding country code)
+    #[arg(long, global = true, default_value = "10")]
+    pub phone_min_digits: usize,
+
+    /// Maximum digits accepted in a phone number (excluding country code)
+    #[arg(long, global = true, default_value = "15")]
+    pub phone_max_digits: usize,
+
+    /// Accept short service codes (3-6 digits) as phone numbers
+    #[arg(long, global = true)]
+    pub allow_short_codes: bool,
+
+    /// Which repeated emails on a contact are dropped, and which variants `--remove-email` matches (allow-all, exact, canonical)
+    #[arg(long, global = true, default_value = "exact")]
+    pub email_dedup: EmailDedupPolicy,
+
+    /// Colorize output (auto, always, never)
+    #[arg(long, global = true, default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Largest number of results a single list or search page may show
+    #[arg(long, global = true, default_value_t = RECOMMENDED_MAX_PAGE_SIZE)]
+    pub max_page_size: usize,
 
-    /// Path to the contacts file
-    #[arg(short, long, default_value_t = default_contacts_file())]
-    pub file: String,
+    /// Fit contact lists into this many columns (default: the terminal width when stdout is a terminal)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(20..))]
+    pub width: Option<u16>,
+
+    /// How to show contact names (first-last, last-first)
+    #[arg(long, global = true, default_value = "first-last")]
+    pub name_format: NameFormat,
+
+    /// Log diagnostics to stderr (-v for info, -vv for debug)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Suppress warnings; only errors are reported
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Load a damaged contacts file, skipping entries that can't be read
+    #[arg(long, global = true)]
+    pub recover: bool,
+
+    /// Encrypt the contacts file with this passphrase (a plain file is encrypted on its next save)
+    #[cfg(feature = "encrypt")]
+    #[arg(long, global = true, env = "PHONEBOOK_PASSPHRASE", hide_env_values = true)]
+    pub passphrase: Option<String>,
+}
+
+// A single line entered in the interactive shell
+#[derive(Parser)]
+#[command(name = "phonebook", no_binary_name = true)]
+#[command(about = "Phonebook interactive shell (also: history, !!, !N, quit)")]
+pub struct ShellLine {
+    #[command(subcommand)]
+    pub command: Commands,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Create an empty phonebook file
+    Init {
+        /// Where to create it (defaults to the --file, --profile or default contacts file)
+        path: Option<String>,
+
+        /// Replace an existing file with an empty phonebook
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Add a new contact
     Add {
         /// First name
-        #[arg(short, long)]
-        first_name: String,
+        #[arg(short, long, required_unless_present = "stdin_json")]
+        first_name: Option<String>,
 
         /// Last name
-        #[arg(short, long)]
-        last_name: String,
+        #[arg(short, long, required_unless_present = "stdin_json")]
+        last_name: Option<String>,
 
         /// Phone numbers (can be specified multiple times)
         #[arg(short, long)]
@@ -51,10 +173,61 @@ pub enum Commands {
         /// Tags (can be specified multiple times)
         #[arg(short, long)]
         tag: Vec<String>,
+
+        /// Birthday (YYYY-MM-DD)
+        #[arg(long)]
+        birthday: Option<String>,
+
+        /// Path to a photo of the contact
+        #[arg(long)]
+        photo: Option<PathBuf>,
+
+        /// Extra field as key=value (can be specified multiple times)
+        #[arg(long)]
+        meta: Vec<String>,
+
+        /// Don't warn about tags that look like typos of existing ones
+        #[arg(long)]
+        no_suggest: bool,
+
+        /// Reject disposable (throwaway) email addresses instead of warning about them
+        #[arg(long)]
+        no_disposable: bool,
+
+        /// Replace emails whose domain looks like a typo of a popular provider (e.g. gmial.com) instead of warning
+        #[arg(long)]
+        fix_email: bool,
+
+        /// Read the contact from stdin as one JSON object, e.g. {"first_name": "Ada", "last_name": "Lovelace", "emails": ["ada@example.com"]}
+        #[arg(long, conflicts_with_all = ["first_name", "last_name", "phone", "email", "notes", "tag", "birthday", "photo", "meta"])]
+        stdin_json: bool,
     },
 
-    /// Find a contact by ID
+    /// Find a contact by ID, phone number or email
     Find {
+        /// Contact ID
+        #[arg(required_unless_present_any = ["by_phone", "by_email"])]
+        id: Option<String>,
+
+        /// Find contacts with this phone number (any format)
+        #[arg(long, conflicts_with_all = ["id", "by_email"])]
+        by_phone: Option<String>,
+
+        /// Find contacts with this email address
+        #[arg(long, conflicts_with_all = ["id", "by_phone"])]
+        by_email: Option<String>,
+
+        /// Also list other contacts sharing the contact's tags (text output)
+        #[arg(long, requires = "id")]
+        related: bool,
+
+        /// Output format (text, json, vcard)
+        #[arg(long, default_value = "text")]
+        format: DisplayFormat,
+    },
+
+    /// Show the change history of a contact
+    History {
         /// Contact ID
         id: String,
     },
@@ -69,19 +242,137 @@ pub enum Commands {
         #[arg(long, default_value = "10")]
         page_size: usize,
 
-        /// Sort by field
-        #[arg(long, default_value = "last-name")]
-        sort_by: SortField,
+        /// Show every contact on one page
+        #[arg(long, conflicts_with_all = ["page", "page_size"])]
+        all: bool,
+
+        /// Comma-separated sort fields; later ones break ties (e.g. last-name,first-name)
+        #[arg(long, value_delimiter = ',', default_value = "last-name")]
+        sort_by: Vec<SortField>,
 
         /// Reverse sort order
         #[arg(long)]
         reverse: bool,
+
+        /// Comma-separated columns to show (id, name, phone, email, tags, notes)
+        #[arg(long)]
+        columns: Option<ColumnSpec>,
+
+        /// Group the listed contacts under headers (tag)
+        #[arg(long, conflicts_with_all = ["columns", "porcelain", "count_only"])]
+        group_by: Option<GroupBy>,
+
+        /// Print one line per contact from a template, e.g. "{last_name}, {first_name}: {phone}"
+        /// (placeholders: id, first_name, last_name, full_name, phone, email, tags, notes)
+        #[arg(long, visible_alias = "output-template", conflicts_with_all = ["columns", "group_by", "porcelain", "count_only"])]
+        template: Option<ContactTemplate>,
+
+        /// Include contacts in the trash
+        #[arg(long)]
+        include_deleted: bool,
+
+        /// Only list contacts matching an expression, e.g. "tag:work and email:*@acme.com"
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Only contacts created at or after this date or RFC 3339 time
+        #[arg(long)]
+        created_after: Option<String>,
+
+        /// Only contacts created before this date or RFC 3339 time
+        #[arg(long)]
+        created_before: Option<String>,
+
+        /// Only contacts changed at or after this date or RFC 3339 time
+        #[arg(long)]
+        updated_since: Option<String>,
+
+        /// Print one tab-separated line per contact (id, name, phone, email) with no decorations
+        #[arg(long)]
+        porcelain: bool,
+
+        /// Print only the number of matching contacts
+        #[arg(long, conflicts_with = "porcelain")]
+        count_only: bool,
+
+        /// Write the results to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Only list contacts added or modified since a snapshot (a contacts file or JSON export)
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = [
+                "page", "page_size", "all", "sort_by", "reverse", "columns", "group_by", "template", "porcelain",
+                "count_only", "output",
+            ]
+        )]
+        since_snapshot: Option<String>,
+
+        /// Re-print the list whenever the contacts file changes (Ctrl-C to stop)
+        #[cfg(feature = "watch")]
+        #[arg(long, conflicts_with_all = ["output", "since_snapshot"])]
+        watch: bool,
     },
 
     /// Search contacts
     Search {
         /// Search query
         query: String,
+
+        /// Treat the query as a regular expression
+        #[arg(long)]
+        regex: bool,
+
+        /// Show which fields matched, with the surrounding text
+        #[arg(long, conflicts_with_all = ["regex", "porcelain", "count_only"])]
+        highlight: bool,
+
+        /// Maximum number of results to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Number of results to skip
+        #[arg(long, default_value = "0")]
+        offset: usize,
+
+        /// Resume after the previous page, using the cursor printed below it
+        /// Pass the same query and sort options as that page
+        #[arg(long, conflicts_with = "offset")]
+        cursor: Option<String>,
+
+        /// Comma-separated sort fields; later ones break ties (e.g. last-name,first-name)
+        #[arg(long, value_delimiter = ',', default_value = "last-name")]
+        sort_by: Vec<SortField>,
+
+        /// Reverse sort order
+        #[arg(long)]
+        reverse: bool,
+
+        /// Order results by --sort-by alone instead of ranking name and email hits above tag and note hits
+        #[arg(long)]
+        no_relevance: bool,
+
+        /// Don't match metadata keys and values, e.g. when bulky data is stored there
+        #[arg(long, overrides_with = "include_meta")]
+        no_meta: bool,
+
+        /// Match metadata keys and values (the default; undoes an earlier --no-meta)
+        #[arg(long, overrides_with = "no_meta")]
+        include_meta: bool,
+
+        /// Print one tab-separated line per contact (id, name, phone, email) with no decorations
+        #[arg(long)]
+        porcelain: bool,
+
+        /// Print only the number of matching contacts
+        #[arg(long, conflicts_with = "porcelain")]
+        count_only: bool,
+
+        /// Write the results to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<String>,
     },
 
     /// Update a contact
@@ -97,6 +388,10 @@ pub enum Commands {
         #[arg(long)]
         last_name: Option<String>,
 
+        /// Remove all phone numbers before adding any --add-phone ones
+        #[arg(long, conflicts_with = "remove_phone")]
+        clear_phones: bool,
+
         /// Add phone numbers
         #[arg(long)]
         add_phone: Vec<String>,
@@ -105,6 +400,10 @@ pub enum Commands {
         #[arg(long)]
         remove_phone: Vec<String>,
 
+        /// Remove all email addresses before adding any --add-email ones
+        #[arg(long, conflicts_with = "remove_email")]
+        clear_emails: bool,
+
         /// Add email addresses
         #[arg(long)]
         add_email: Vec<String>,
@@ -117,6 +416,10 @@ pub enum Commands {
         #[arg(long)]
         notes: Option<String>,
 
+        /// Remove all tags before adding any --add-tag ones
+        #[arg(long, conflicts_with = "remove_tag")]
+        clear_tags: bool,
+
         /// Add tags
         #[arg(long)]
         add_tag: Vec<String>,
@@ -124,6 +427,38 @@ pub enum Commands {
         /// Remove tags
         #[arg(long)]
         remove_tag: Vec<String>,
+
+        /// Set birthday (YYYY-MM-DD, empty to clear)
+        #[arg(long)]
+        birthday: Option<String>,
+
+        /// Set photo path (empty to clear)
+        #[arg(long)]
+        photo: Option<String>,
+
+        /// Set an extra field as key=value, overwriting any existing value
+        #[arg(long)]
+        meta: Vec<String>,
+
+        /// Remove an extra field by key
+        #[arg(long)]
+        remove_meta: Vec<String>,
+
+        /// Set a field as field=value (first_name, last_name, notes, birthday, photo; can be specified multiple times)
+        #[arg(long = "set", value_name = "FIELD=VALUE")]
+        set: Vec<String>,
+
+        /// Only update if the contact is still at this version (see `find --format json`)
+        #[arg(long)]
+        expected_version: Option<u64>,
+
+        /// Show the resulting contact without saving it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Don't warn about tags that look like typos of existing ones
+        #[arg(long)]
+        no_suggest: bool,
     },
 
     /// Delete a contact
@@ -134,10 +469,234 @@ pub enum Commands {
         /// Skip confirmation prompt
         #[arg(short, long)]
         yes: bool,
+
+        /// Show what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Delete permanently instead of moving to the trash
+        #[arg(long)]
+        purge: bool,
+    },
+
+    /// List contacts in the trash
+    Trash,
+
+    /// Restore a contact from the trash
+    RestoreContact {
+        /// Contact ID
+        id: String,
+    },
+
+    /// Mark a contact as recently updated without changing it
+    Touch {
+        /// Contact ID
+        id: String,
+    },
+
+    /// Delete several contacts by ID and/or by tag
+    DeleteMany {
+        /// Contact IDs
+        ids: Vec<String>,
+
+        /// Delete every contact with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Permanently delete every contact, including those in the trash
+    PurgeAll {
+        /// Skip typing the contact count to confirm
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Rename a tag on every contact that has it
+    RenameTag {
+        /// Current tag name
+        from: String,
+
+        /// New tag name
+        to: String,
+    },
+
+    /// Manage named groups of contacts
+    Group {
+        #[command(subcommand)]
+        action: GroupAction,
+    },
+
+    /// Manage named phonebooks (profiles) selected with --profile
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
     },
 
     /// Show statistics
-    Stats,
+    Stats {
+        /// Output format (table, csv, json)
+        #[arg(long, default_value = "table")]
+        format: StatsFormat,
+
+        /// Chart how many contacts were created per day, week or month instead
+        #[arg(long, value_name = "INTERVAL", conflicts_with = "format")]
+        over_time: Option<StatsInterval>,
+    },
+
+    /// Show randomly chosen contacts
+    Random {
+        /// Number of contacts to pick
+        #[arg(long, default_value = "1")]
+        count: usize,
+
+        /// Only pick contacts with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Seed for a reproducible selection
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// Check the contacts file for invalid or corrupt entries without modifying it
+    Validate,
+
+    /// Compare this phonebook with another contacts file
+    Diff {
+        /// Path to the other contacts file
+        other: String,
+    },
+
+    /// Export all contacts
+    Export {
+        /// Export format (json, jsonl, vcard, markdown)
+        #[arg(long, default_value = "json")]
+        format: ExportFormat,
+
+        /// Only export contacts with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Only export contacts matching an expression, e.g. "tag:work and email:*@acme.com"
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Only export contacts changed at or after this RFC 3339 time, e.g. the last sync
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Scrub names, phone digits, email local parts, notes and metadata, e.g. for bug reports
+        #[arg(long)]
+        anonymize: bool,
+
+        /// Output file (defaults to stdout; `-` also means stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Write one .vcf file per contact into this directory instead (vcard only)
+        #[arg(long, conflicts_with = "output")]
+        output_dir: Option<String>,
+
+        /// Replace .vcf files already in --output-dir instead of refusing to export
+        #[arg(long, requires = "output_dir")]
+        force: bool,
+    },
+
+    /// Print a shell completion script (bash, zsh, fish, elvish, powershell)
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+
+        /// Command name the completions are registered for
+        #[arg(long, default_value = "dpbook")]
+        bin_name: String,
+    },
+
+    /// Import contacts from a CSV file with a header row
+    Import {
+        /// CSV file to read, or `-` for stdin
+        path: String,
+
+        /// Replace stored contacts whose ID matches a row's `id` column instead of skipping the row
+        #[arg(long)]
+        upsert: bool,
+    },
+
+    /// Serve the phonebook as a JSON HTTP API
+    #[cfg(feature = "server")]
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: String,
+    },
+
+    /// List contacts with a birthday in the next N days
+    Upcoming {
+        /// Number of days to look ahead
+        #[arg(long, default_value = "30")]
+        days: u32,
+    },
+
+    /// Start an interactive shell that keeps the phonebook loaded between commands
+    Interactive,
+}
+
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// List profiles and their contacts files
+    List,
+
+    /// Add a profile, or point an existing one at a new file
+    Add {
+        /// Profile name
+        name: String,
+
+        /// Contacts file for the profile
+        path: String,
+    },
+
+    /// Remove a profile (its contacts file is left alone)
+    Remove {
+        /// Profile name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GroupAction {
+    /// Create an empty group
+    Create {
+        /// Group name
+        name: String,
+    },
+
+    /// Add a contact to a group
+    AddMember {
+        /// Group name
+        group: String,
+
+        /// Contact ID
+        id: String,
+    },
+
+    /// Remove a contact from a group
+    RemoveMember {
+        /// Group name
+        group: String,
+
+        /// Contact ID
+        id: String,
+    },
+
+    /// List groups and their members
+    List {
+        /// Only show this group
+        name: Option<String>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -169,3 +728,150 @@ impl std::fmt::Display for SortField {
         }
     }
 }
+
+#[derive(Clone, Copy, Debug)]
+pub enum GroupBy {
+    Tag,
+}
+
+impl std::str::FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tag" | "tags" => Ok(GroupBy::Tag),
+            _ => Err(format!("Invalid group-by field: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GroupBy::Tag => write!(f, "tag"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum DisplayFormat {
+    Text,
+    Json,
+    VCard,
+}
+
+impl std::str::FromStr for DisplayFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(DisplayFormat::Text),
+            "json" => Ok(DisplayFormat::Json),
+            "vcard" | "vcf" => Ok(DisplayFormat::VCard),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for DisplayFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DisplayFormat::Text => write!(f, "text"),
+            DisplayFormat::Json => write!(f, "json"),
+            DisplayFormat::VCard => write!(f, "vcard"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl std::str::FromStr for StatsFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" | "text" => Ok(StatsFormat::Table),
+            "csv" => Ok(StatsFormat::Csv),
+            "json" => Ok(StatsFormat::Json),
+            _ => Err(format!("Invalid stats format: {} (expected table, csv or json)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for StatsFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatsFormat::Table => write!(f, "table"),
+            StatsFormat::Csv => write!(f, "csv"),
+            StatsFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsInterval {
+    Day,
+    Week,
+    Month,
+}
+
+impl std::str::FromStr for StatsInterval {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "day" | "daily" => Ok(StatsInterval::Day),
+            "week" | "weekly" => Ok(StatsInterval::Week),
+            "month" | "monthly" => Ok(StatsInterval::Month),
+            _ => Err(format!("Invalid interval: {} (expected day, week or month)", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for StatsInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatsInterval::Day => write!(f, "day"),
+            StatsInterval::Week => write!(f, "week"),
+            StatsInterval::Month => write!(f, "month"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum ExportFormat {
+    Json,
+    JsonLines,
+    VCard,
+    Markdown,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "jsonl" | "ndjson" => Ok(ExportFormat::JsonLines),
+            "vcard" | "vcf" => Ok(ExportFormat::VCard),
+            "markdown" | "md" => Ok(ExportFormat::Markdown),
+            _ => Err(format!("Invalid export format: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Json => write!(f, "json"),
+            ExportFormat::JsonLines => write!(f, "jsonl"),
+            ExportFormat::VCard => write!(f, "vcard"),
+            ExportFormat::Markdown => write!(f, "markdown"),
+        }
+    }
+}
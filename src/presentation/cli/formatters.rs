@@ -1,30 +1,267 @@
-use crate::domain::entities::Contact;
+use crate::application::diff::{ChangedContact, ContactsDiff, SnapshotChanges};
+use crate::application::use_cases::Stats;
+use crate::domain::entities::{Contact, Group, NameFormat};
+use crate::domain::value_objects::AuditEntry;
+use crate::infrastructure::export::VCardExporter;
+use crate::presentation::cli::style::Style;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// A column that can be shown in the contact list
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Id,
+    Name,
+    Phone,
+    Email,
+    Tags,
+    Notes,
+}
+
+impl Column {
+    /// Header label for the column
+    pub fn header(&self) -> &'static str {
+        match self {
+            Column::Id => "ID",
+            Column::Name => "Name",
+            Column::Phone => "Phone",
+            Column::Email => "Email",
+            Column::Tags => "Tags",
+            Column::Notes => "Notes",
+        }
+    }
+
+    /// Cell value for a contact, with names shown in `name_format` order
+    pub fn value(&self, contact: &Contact, name_format: NameFormat) -> String {
+        match self {
+            Column::Id => contact.id().to_string(),
+            Column::Name => ContactFormatter::name_or_placeholder(contact, name_format),
+            Column::Phone => contact
+                .phone_numbers()
+                .first()
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "No phone".to_string()),
+            Column::Email => contact
+                .emails()
+                .first()
+                .map(|e| e.to_string())
+                .unwrap_or_else(|| "No email".to_string()),
+            Column::Tags => contact.tags().join(", "),
+            Column::Notes => contact
+                .notes()
+                .map(|n| n.split_whitespace().collect::<Vec<_>>().join(" "))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "id" => Ok(Column::Id),
+            "name" => Ok(Column::Name),
+            "phone" => Ok(Column::Phone),
+            "email" => Ok(Column::Email),
+            "tags" | "tag" => Ok(Column::Tags),
+            "notes" | "note" => Ok(Column::Notes),
+            other => Err(format!("Invalid column: {}", other)),
+        }
+    }
+}
+
+/// Ordered selection of columns for list output, e.g. `id,name,tags,email`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSpec {
+    columns: Vec<Column>,
+}
+
+impl ColumnSpec {
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+}
+
+impl Default for ColumnSpec {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Id, Column::Name, Column::Phone, Column::Email],
+        }
+    }
+}
+
+impl FromStr for ColumnSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let columns = s
+            .split(',')
+            .filter(|part| !part.trim().is_empty())
+            .map(Column::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if columns.is_empty() {
+            return Err("At least one column is required".to_string());
+        }
+
+        Ok(Self { columns })
+    }
+}
+
+/// Shown in place of a contact's name when it has neither a first nor a last name
+pub const NO_NAME: &str = "(no name)";
+
+/// Characters of a contact ID shown when the full ID doesn't fit
+const ID_PREFIX_LEN: usize = 8;
+
+/// Narrowest width that still fits one contact per line
+const MIN_COLUMNS_WIDTH: usize = 60;
+
+/// How compact list rows are laid out for the available width
+enum RowLayout {
+    /// Fixed-width columns with nothing truncated, for output that isn't width-limited
+    Natural,
+    /// One line per contact, each column cut to its width
+    Columns { id: usize, name: usize, phone: usize, email: usize },
+    /// The name on one line, the shortened ID, phone and email indented below it
+    Stacked { width: usize },
+}
+
+impl RowLayout {
+    fn for_width(width: Option<usize>) -> Self {
+        // Full ID, name and phone columns, plus room for a typical email
+        const FULL_WIDTH: usize = 40 + 1 + 25 + 1 + 15 + 1 + 20;
+
+        match width {
+            None => RowLayout::Natural,
+            Some(width) if width >= FULL_WIDTH => RowLayout::Columns {
+                id: 40,
+                name: 25,
+                phone: 15,
+                email: width - (FULL_WIDTH - 20),
+            },
+            Some(width) if width >= MIN_COLUMNS_WIDTH => {
+                // Name and email share what's left after the ID prefix and phone
+                let rest = width - (ID_PREFIX_LEN + 1) - (15 + 1);
+                let name = (rest / 2).min(25);
+                RowLayout::Columns {
+                    id: ID_PREFIX_LEN,
+                    name,
+                    phone: 15,
+                    email: rest - name - 1,
+                }
+            }
+            Some(width) => RowLayout::Stacked { width },
+        }
+    }
+}
+
+/// Renders contacts for human-readable output
+/// `ContactFormatter` is the default; embedders can supply their own through
+/// `PhonebookApp::with_formatter`
+pub trait Formatter {
+    /// Full details of a single contact
+    fn format_contact(&self, contact: &Contact, style: &Style) -> String;
+
+    /// A page of contacts
+    fn format_list(&self, contacts: &[Contact], style: &Style) -> String;
+
+    /// Search results: `total` matches in all, of which `contacts` are shown;
+    /// `highlight` is literal text to emphasise in the results, if any
+    fn format_search(
+        &self,
+        query: &str,
+        total: usize,
+        contacts: &[Contact],
+        style: &Style,
+        highlight: Option<&str>,
+    ) -> String;
+}
 
 /// Formatter for displaying contacts in various formats
 pub struct ContactFormatter;
 
+impl ContactFormatter {
+    /// Column header, separator and one compact row per contact
+    fn format_rows(contacts: &[Contact], style: &Style, highlight: Option<&str>) -> String {
+        let mut lines = vec![Self::format_list_header_styled(style), Self::format_separator_styled(style)];
+        lines.extend(
+            contacts
+                .iter()
+                .map(|contact| Self::format_contact_compact_styled(contact, style, highlight)),
+        );
+        lines.join("\n")
+    }
+}
+
+impl Formatter for ContactFormatter {
+    fn format_contact(&self, contact: &Contact, style: &Style) -> String {
+        Self::format_contact_styled(contact, style)
+    }
+
+    fn format_list(&self, contacts: &[Contact], style: &Style) -> String {
+        Self::format_rows(contacts, style, None)
+    }
+
+    fn format_search(
+        &self,
+        query: &str,
+        total: usize,
+        contacts: &[Contact],
+        style: &Style,
+        highlight: Option<&str>,
+    ) -> String {
+        let mut output = Self::format_search_summary(query, total);
+        if !contacts.is_empty() {
+            output.push('\n');
+            output.push_str(&Self::format_rows(contacts, style, highlight));
+        }
+        output
+    }
+}
+
 impl ContactFormatter {
     /// Format a single contact for display
     pub fn format_contact(contact: &Contact) -> String {
+        Self::format_contact_styled(contact, &Style::plain())
+    }
+
+    /// Format a single contact for display, applying the given style
+    pub fn format_contact_styled(contact: &Contact, style: &Style) -> String {
         let mut output = String::new();
         
         output.push_str(&format!("ID: {}\n", contact.id()));
-        output.push_str(&format!("Name: {}\n", contact.full_name()));
+        output.push_str(&format!("Name: {}\n", style.bold(&Self::name_or_placeholder(contact, style.name_format()))));
         
         if !contact.phone_numbers().is_empty() {
             output.push_str("Phone Numbers:\n");
             for phone in contact.phone_numbers() {
-                output.push_str(&format!("  - {}\n", phone));
+                match phone.region() {
+                    Some(region) => output.push_str(&format!("  - {} [{}]\n", phone, region)),
+                    None => output.push_str(&format!("  - {}\n", phone)),
+                }
             }
         }
         
         if !contact.emails().is_empty() {
             output.push_str("Emails:\n");
             for email in contact.emails() {
-                output.push_str(&format!("  - {}\n", email));
+                match email.label() {
+                    Some(label) => output.push_str(&format!("  - {} ({})\n", email, label)),
+                    None => output.push_str(&format!("  - {}\n", email)),
+                }
             }
         }
         
+        if let Some(birthday) = contact.birthday() {
+            output.push_str(&format!("Birthday: {}\n", birthday));
+        }
+
+        if let Some(photo_path) = contact.photo_path() {
+            output.push_str(&format!("Photo: {}\n", photo_path.display()));
+        }
+
         if let Some(notes) = contact.notes() {
             output.push_str(&format!("Notes: {}\n", notes));
         }
@@ -32,12 +269,40 @@ impl ContactFormatter {
         if !contact.tags().is_empty() {
             output.push_str(&format!("Tags: {}\n", contact.tags().join(", ")));
         }
+
+        if !contact.metadata().is_empty() {
+            output.push_str("Metadata:\n");
+            let mut entries: Vec<_> = contact.metadata().iter().collect();
+            entries.sort();
+            for (key, value) in entries {
+                output.push_str(&format!("  {}: {}\n", key, value));
+            }
+        }
+
+        if let Some(deleted_at) = contact.deleted_at() {
+            output.push_str(&format!("Deleted: {}\n", deleted_at));
+        }
         
         output
     }
 
+    /// Format the fields a search query hit, one indented `field: snippet` line each
+    pub fn format_match_contexts(contact: &Contact, query: &str, style: &Style) -> String {
+        contact
+            .match_contexts(query)
+            .into_iter()
+            .map(|(field, snippet)| format!("    {}: {}\n", field, style.mark(&snippet, query)))
+            .collect()
+    }
+
     /// Format a contact for list display (compact format)
     pub fn format_contact_compact(contact: &Contact) -> String {
+        Self::format_contact_compact_styled(contact, &Style::plain(), None)
+    }
+
+    /// Format a contact for list display, applying the given style and
+    /// highlighting occurrences of `highlight` when provided
+    pub fn format_contact_compact_styled(contact: &Contact, style: &Style, highlight: Option<&str>) -> String {
         let phone = contact.phone_numbers()
             .first()
             .map(|p| p.to_string())
@@ -48,26 +313,168 @@ impl ContactFormatter {
             .map(|e| e.to_string())
             .unwrap_or_else(|| "No email".to_string());
 
-        format!(
-            "{:<40} {:<25} {:<15} {}",
-            &contact.id().to_string(),
-            contact.full_name(),
-            phone,
-            email
-        )
+        let id = contact.id().to_string();
+        let name = Self::name_or_placeholder(contact, style.name_format());
+        let paint = |text: &str| match highlight {
+            Some(query) => style.highlight(text, query),
+            None => text.to_string(),
+        };
+
+        match RowLayout::for_width(style.width()) {
+            RowLayout::Natural => format!(
+                "{} {} {} {}",
+                Self::pad(&paint(&id), &id, 40),
+                Self::pad(&style.bold(&paint(&name)), &name, 25),
+                Self::pad(&paint(&phone), &phone, 15),
+                paint(&email)
+            ),
+            RowLayout::Columns {
+                id: id_width,
+                name: name_width,
+                phone: phone_width,
+                email: email_width,
+            } => {
+                let id = Self::shorten_id(&id, id_width);
+                let name = Self::truncate(&name, name_width);
+                let phone = Self::truncate(&phone, phone_width);
+                let email = Self::truncate(&email, email_width);
+                format!(
+                    "{} {} {} {}",
+                    Self::pad(&paint(&id), &id, id_width),
+                    Self::pad(&style.bold(&paint(&name)), &name, name_width),
+                    Self::pad(&paint(&phone), &phone, phone_width),
+                    paint(&email)
+                )
+            }
+            RowLayout::Stacked { width } => {
+                let name = Self::truncate(&name, width);
+                let details = Self::truncate(
+                    &format!("{}  {}  {}", Self::shorten_id(&id, ID_PREFIX_LEN), phone, email),
+                    width.saturating_sub(2),
+                );
+                format!("{}\n  {}", style.bold(&paint(&name)), paint(&details))
+            }
+        }
+    }
+
+    /// The contact's name in `name_format` order, or `NO_NAME` when both parts are blank
+    fn name_or_placeholder(contact: &Contact, name_format: NameFormat) -> String {
+        let name = contact.display_name(name_format);
+        if name.is_empty() {
+            NO_NAME.to_string()
+        } else {
+            name
+        }
+    }
+
+    /// Left-align styled text to `width`, measuring the unstyled text so
+    /// escape codes don't disturb the layout
+    fn pad(styled: &str, raw: &str, width: usize) -> String {
+        let len = raw.chars().count();
+        if len >= width {
+            styled.to_string()
+        } else {
+            format!("{}{}", styled, " ".repeat(width - len))
+        }
+    }
+
+    /// Cut text to at most `width` characters, marking the cut with an ellipsis
+    fn truncate(text: &str, width: usize) -> String {
+        if text.chars().count() <= width {
+            return text.to_string();
+        }
+        let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+        cut.push('…');
+        cut
+    }
+
+    /// A contact ID, shortened to a prefix when its column is too narrow for all of it;
+    /// commands taking an ID accept any unambiguous prefix
+    fn shorten_id(id: &str, width: usize) -> String {
+        id.chars().take(width).collect()
     }
 
     /// Format the header for contact list
     pub fn format_list_header() -> String {
-        format!(
-            "{:<40} {:<25} {:<15} {}",
-            "ID", "Name", "Phone", "Email"
-        )
+        Self::format_list_header_styled(&Style::plain())
+    }
+
+    /// Format the header for contact list, laid out for the style's width
+    pub fn format_list_header_styled(style: &Style) -> String {
+        match RowLayout::for_width(style.width()) {
+            RowLayout::Natural => format!(
+                "{:<40} {:<25} {:<15} {}",
+                "ID", "Name", "Phone", "Email"
+            ),
+            RowLayout::Columns { id, name, phone, .. } => format!(
+                "{:<id$} {:<name$} {:<phone$} {}",
+                "ID",
+                "Name",
+                "Phone",
+                "Email",
+                id = id,
+                name = name,
+                phone = phone
+            ),
+            RowLayout::Stacked { .. } => "Name\n  ID  Phone  Email".to_string(),
+        }
+    }
+
+    /// Format contacts as a table with the chosen columns
+    /// Column widths are sized to fit the widest value so nothing is truncated
+    pub fn format_table(contacts: &[Contact], spec: &ColumnSpec, style: &Style) -> String {
+        let rows: Vec<Vec<String>> = contacts
+            .iter()
+            .map(|contact| spec.columns().iter().map(|c| c.value(contact, style.name_format())).collect())
+            .collect();
+
+        let widths: Vec<usize> = spec
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                rows.iter()
+                    .map(|row| row[i].chars().count())
+                    .chain(std::iter::once(column.header().len()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let render_row = |cells: Vec<&str>| {
+            let last = cells.len() - 1;
+            cells
+                .iter()
+                .enumerate()
+                .map(|(i, cell)| {
+                    if i == last {
+                        cell.to_string()
+                    } else {
+                        format!("{:<width$}", cell, width = widths[i])
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+
+        let mut lines = vec![render_row(spec.columns().iter().map(|c| c.header()).collect())];
+        let total_width = widths.iter().sum::<usize>() + 2 * (widths.len() - 1);
+        lines.push("-".repeat(total_width));
+        for row in &rows {
+            lines.push(render_row(row.iter().map(String::as_str).collect()));
+        }
+
+        lines.join("\n")
     }
 
     /// Format a separator line
     pub fn format_separator() -> String {
-        "-".repeat(180)
+        Self::format_separator_styled(&Style::plain())
+    }
+
+    /// Format a separator line as wide as the style allows
+    pub fn format_separator_styled(style: &Style) -> String {
+        "-".repeat(style.width().unwrap_or(180))
     }
 
     /// Format search results summary
@@ -75,22 +482,497 @@ impl ContactFormatter {
         format!("Found {} contact(s) matching '{}'\n", count, query)
     }
 
+    /// Format which slice of the search results is shown
+    /// `after_cursor` marks a total that only counts the matches after the cursor
+    pub fn format_search_window(offset: usize, shown: usize, total: usize, after_cursor: bool) -> String {
+        let matches = if after_cursor { "matches after the cursor" } else { "matches" };
+        if shown == 0 {
+            return format!("Showing 0 of {} {}", total, matches);
+        }
+
+        format!("Showing {} - {} of {} {}", offset + 1, offset + shown, total, matches)
+    }
+
+    /// Tell the user how to fetch the next page of search results
+    pub fn format_next_cursor(cursor: &str) -> String {
+        format!("Next page: --cursor {}", cursor)
+    }
+
     /// Format list pagination info
-    pub fn format_pagination_info(page: usize, page_size: usize, total: usize, has_more: bool) -> String {
-        let start = page * page_size + 1;
-        let end = std::cmp::min((page + 1) * page_size, total);
+    pub fn format_pagination_info(
+        current_page: usize,
+        total_pages: usize,
+        page_size: usize,
+        total: usize,
+    ) -> String {
+        let start = (current_page - 1) * page_size + 1;
+        let end = std::cmp::min(current_page * page_size, total);
         
         let mut info = format!("Showing {} - {} of {} contacts", start, end, total);
         
-        if has_more {
-            info.push_str(&format!(" (Page {})", page + 1));
+        if total_pages > 1 {
+            info.push_str(&format!(" (Page {} of {})", current_page, total_pages));
         }
         
         info
     }
 
-    /// Format statistics
-    pub fn format_stats(total_contacts: usize) -> String {
-        format!("Total contacts: {}", total_contacts)
+    /// Format the outcome of a bulk delete
+    pub fn format_bulk_delete_summary(deleted: usize, failed: &[(String, String)]) -> String {
+        let mut output = format!("✓ Deleted {} contact(s)", deleted);
+
+        if !failed.is_empty() {
+            output.push_str(&format!("\n✗ Failed to delete {} contact(s):", failed.len()));
+            for (input, reason) in failed {
+                output.push_str(&format!("\n  - {}: {}", input, reason));
+            }
+        }
+
+        output
+    }
+
+    /// Format statistics as an aligned table, with per-tag counts last
+    pub fn format_stats(stats: &Stats) -> String {
+        let mut lines = vec![
+            format!("Total contacts:   {}", stats.total_contacts),
+            format!("In trash:         {}", stats.deleted_contacts),
+            format!("Phone numbers:    {}", stats.phone_numbers),
+            format!("Emails:           {}", stats.emails),
+            format!("Without phone:    {}", stats.without_phone),
+            format!("Without email:    {}", stats.without_email),
+            format!("With birthday:    {}", stats.with_birthday),
+            format!("Untagged:         {}", stats.untagged),
+        ];
+
+        if !stats.tags.is_empty() {
+            lines.push("Tags:".to_string());
+            let width = stats.tags.keys().map(|tag| tag.chars().count()).max().unwrap_or(0);
+            for (tag, count) in &stats.tags {
+                lines.push(format!("  {:<width$}  {}", tag, count, width = width));
+            }
+        }
+
+        lines.join("\n")
+    }
+
+    /// Format `(label, count)` buckets as a bar chart of `#`, scaled so the longest bar fits
+    pub fn format_histogram(buckets: &[(String, usize)]) -> String {
+        const MAX_BAR: usize = 50;
+
+        let Some(max) = buckets.iter().map(|(_, count)| *count).max() else {
+            return "no data".to_string();
+        };
+        let label_width = buckets.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        let count_width = max.to_string().len();
+
+        buckets
+            .iter()
+            .map(|(label, count)| {
+                // Round up so every non-empty bucket shows at least one mark
+                let bar = (count * MAX_BAR).div_ceil(max.max(MAX_BAR));
+                format!(
+                    "{:<label_width$}  {:>count_width$}  {}",
+                    label,
+                    count,
+                    "#".repeat(bar),
+                    label_width = label_width,
+                    count_width = count_width
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Format statistics as `metric,value` CSV rows; tag counts use `tag:<name>` metrics
+    pub fn format_stats_csv(stats: &Stats) -> String {
+        let mut rows = vec![
+            "metric,value".to_string(),
+            format!("total_contacts,{}", stats.total_contacts),
+            format!("deleted_contacts,{}", stats.deleted_contacts),
+            format!("phone_numbers,{}", stats.phone_numbers),
+            format!("emails,{}", stats.emails),
+            format!("without_phone,{}", stats.without_phone),
+            format!("without_email,{}", stats.without_email),
+            format!("with_birthday,{}", stats.with_birthday),
+            format!("untagged,{}", stats.untagged),
+        ];
+
+        for (tag, count) in &stats.tags {
+            rows.push(format!("{},{}", Self::csv_field(&format!("tag:{}", tag)), count));
+        }
+
+        rows.join("\n")
+    }
+
+    /// Quote a CSV field if it contains a delimiter, quote or line break
+    fn csv_field(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// Format an upcoming birthday reminder
+    pub fn format_upcoming_birthday(contact: &Contact, days_until: u32) -> String {
+        let when = match days_until {
+            0 => "today".to_string(),
+            1 => "tomorrow".to_string(),
+            n => format!("in {} days", n),
+        };
+
+        match contact.birthday() {
+            Some(birthday) => format!("{:<25} {}  ({})", contact.full_name(), birthday, when),
+            None => format!("{:<25} ({})", contact.full_name(), when),
+        }
+    }
+
+    /// Format a contact's change history, one line per changed field
+    pub fn format_history(entries: &[AuditEntry]) -> String {
+        if entries.is_empty() {
+            return "No changes recorded".to_string();
+        }
+
+        entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}  {}: {} -> {}",
+                    entry.timestamp(),
+                    entry.field(),
+                    entry.old_value().map_or_else(|| "(none)".to_string(), |v| format!("'{}'", v)),
+                    entry.new_value().map_or_else(|| "(none)".to_string(), |v| format!("'{}'", v))
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Format a single contact as a vCard 3.0 card (CRLF line endings)
+    pub fn format_contact_vcard(contact: &Contact) -> String {
+        VCardExporter::export_contact(contact)
+    }
+
+    /// Format a contact as `id<TAB>full_name<TAB>first_phone<TAB>first_email`
+    /// This layout is a stable interface for scripts: missing values are empty
+    /// and tabs or newlines inside values are replaced by spaces
+    pub fn format_porcelain(contact: &Contact) -> String {
+        let clean = |value: &str| value.replace(['\t', '\n', '\r'], " ");
+
+        format!(
+            "{}\t{}\t{}\t{}",
+            contact.id(),
+            clean(&contact.full_name()),
+            contact.phone_numbers().first().map_or("", |p| p.value()),
+            contact.emails().first().map_or("", |e| e.value())
+        )
+    }
+
+    /// Format a group heading with its member count
+    pub fn format_group_header(group: &Group, members: usize) -> String {
+        format!("{} ({} member(s))", group.name(), members)
+    }
+
+    /// Format contacts bucketed by tag, tags in alphabetical order, with an
+    /// "(untagged)" bucket last; a contact appears under each of its tags
+    pub fn format_grouped(contacts: &[Contact], style: &Style) -> String {
+        let mut groups: BTreeMap<(String, &str), Vec<&Contact>> = BTreeMap::new();
+        let mut untagged = Vec::new();
+
+        for contact in contacts {
+            if contact.tags().is_empty() {
+                untagged.push(contact);
+            }
+            for tag in contact.tags() {
+                groups.entry((tag.to_lowercase(), tag.as_str())).or_default().push(contact);
+            }
+        }
+
+        let buckets = groups
+            .into_iter()
+            .map(|((_, tag), members)| (tag, members))
+            .chain((!untagged.is_empty()).then_some(("(untagged)", untagged)));
+
+        let mut output = String::new();
+        for (index, (name, members)) in buckets.enumerate() {
+            if index > 0 {
+                output.push('\n');
+            }
+            output.push_str(&format!("{} ({} contact(s))\n", style.bold(name), members.len()));
+            for contact in members {
+                output.push_str(&format!(
+                    "  {}\n",
+                    Self::format_contact_compact_styled(contact, &style.indented(2), None).replace('\n', "\n  ")
+                ));
+            }
+        }
+
+        output.trim_end().to_string()
+    }
+
+    /// Format the differences between two phonebooks: a summary line, then
+    /// `-` for contacts only in this file, `+` for only in the other, `~` for changed
+    pub fn format_diff(diff: &ContactsDiff, other: &str) -> String {
+        let mut output = format!(
+            "{} only here, {} only in {}, {} changed, {} identical",
+            diff.only_in_a.len(),
+            diff.only_in_b.len(),
+            other,
+            diff.changed.len(),
+            diff.unchanged
+        );
+
+        for contact in &diff.only_in_a {
+            output.push_str(&format!("\n- {} ({})", contact.full_name(), contact.id()));
+        }
+
+        for contact in &diff.only_in_b {
+            output.push_str(&format!("\n+ {} ({})", contact.full_name(), contact.id()));
+        }
+
+        for changed in &diff.changed {
+            Self::push_changed(&mut output, changed);
+        }
+
+        output
+    }
+
+    /// Summarize contacts added or modified since a snapshot, then list them
+    pub fn format_snapshot_changes(changes: &SnapshotChanges, snapshot: &str) -> String {
+        let mut output = format!(
+            "{} added, {} modified, {} unchanged since {}",
+            changes.added.len(),
+            changes.modified.len(),
+            changes.unchanged,
+            snapshot
+        );
+
+        for contact in &changes.added {
+            output.push_str(&format!("\n+ {} ({})", contact.full_name(), contact.id()));
+        }
+
+        for changed in &changes.modified {
+            Self::push_changed(&mut output, changed);
+        }
+
+        output
+    }
+
+    /// Append a changed contact and its field changes as `~ name (id)` plus indented lines
+    fn push_changed(output: &mut String, changed: &ChangedContact) {
+        output.push_str(&format!("\n~ {} ({})", changed.a.full_name(), changed.a.id()));
+        for change in &changed.changes {
+            output.push_str(&format!(
+                "\n    {}: {} -> {}",
+                change.field,
+                change.old.as_deref().map_or_else(|| "(none)".to_string(), |v| format!("'{}'", v)),
+                change.new.as_deref().map_or_else(|| "(none)".to_string(), |v| format!("'{}'", v))
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{ContactId, Email, PhoneNumber};
+
+    fn contact(first_name: &str, last_name: &str) -> Contact {
+        Contact::new(
+            first_name.to_string(),
+            last_name.to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![],
+        )
+    }
+
+    fn last_first() -> Style {
+        Style::plain().with_name_format(NameFormat::LastFirst)
+    }
+
+    #[test]
+    fn every_layout_uses_the_name_format() {
+        let ada = contact("Ada", "Lovelace");
+        let spec: ColumnSpec = "name,phone".parse().unwrap();
+
+        assert!(ContactFormatter::format_contact_styled(&ada, &last_first()).contains("Name: Lovelace, Ada\n"));
+        assert!(ContactFormatter::format_contact_compact_styled(&ada, &last_first(), None).contains("Lovelace, Ada"));
+        assert!(ContactFormatter::format_table(std::slice::from_ref(&ada), &spec, &last_first()).contains("Lovelace, Ada"));
+        assert!(ContactFormatter::format_table(std::slice::from_ref(&ada), &spec, &Style::plain()).contains("Ada Lovelace"));
+    }
+
+    #[test]
+    fn one_word_names_are_shown_alone() {
+        let cher = contact("Cher", "");
+        let spec: ColumnSpec = "name".parse().unwrap();
+
+        for style in [Style::plain(), last_first()] {
+            assert!(ContactFormatter::format_contact_styled(&cher, &style).contains("Name: Cher\n"));
+            assert!(ContactFormatter::format_contact_compact_styled(&cher, &style, None).contains("Cher "));
+            assert_eq!(ContactFormatter::format_table(std::slice::from_ref(&cher), &spec, &style).lines().last(), Some("Cher"));
+        }
+    }
+
+    #[test]
+    fn nameless_contacts_get_a_placeholder_in_every_layout() {
+        let nameless = contact(" ", "");
+        let spec: ColumnSpec = "name,phone".parse().unwrap();
+
+        assert!(ContactFormatter::format_contact(&nameless).contains(&format!("Name: {}\n", NO_NAME)));
+        assert!(ContactFormatter::format_contact_compact(&nameless).contains(NO_NAME));
+        let table = ContactFormatter::format_table(std::slice::from_ref(&nameless), &spec, &Style::plain());
+        assert!(table.lines().last().unwrap().starts_with(NO_NAME), "{}", table);
+    }
+
+    #[test]
+    fn short_ids_are_not_padded_or_cut_past_their_end() {
+        assert_eq!(ContactFormatter::shorten_id("abc", ID_PREFIX_LEN), "abc");
+        assert_eq!(ContactFormatter::shorten_id("", ID_PREFIX_LEN), "");
+        assert_eq!(ContactFormatter::shorten_id("0123456789abcdef", ID_PREFIX_LEN), "01234567");
+        assert_eq!(ContactFormatter::shorten_id("ünïcödé-id", 4), "ünïc");
+
+        let narrow = Style::plain().with_width(Some(40));
+        let row = ContactFormatter::format_contact_compact_styled(&contact("", ""), &narrow, None);
+        assert!(row.contains(NO_NAME), "{}", row);
+    }
+
+    #[test]
+    fn compact_rows_and_headers_fit_an_explicit_width() {
+        let mut ada = contact("Augusta Ada", "King-Noel, Countess of Lovelace");
+        ada.add_email(Email::new("augusta.ada.king-noel@analytical-engine.example.com".to_string()).unwrap());
+
+        for width in [20, 45, 59, 60, 80, 103, 140] {
+            let style = Style::plain().with_width(Some(width));
+            let row = ContactFormatter::format_contact_compact_styled(&ada, &style, None);
+            let header = ContactFormatter::format_list_header_styled(&style);
+            for line in row.lines().chain(header.lines()) {
+                assert!(line.chars().count() <= width, "{:?} is wider than {}", line, width);
+            }
+            assert_eq!(row.lines().count(), if width < MIN_COLUMNS_WIDTH { 2 } else { 1 }, "{}", row);
+        }
+
+        let natural = ContactFormatter::format_contact_compact(&ada);
+        assert!(natural.contains(&ada.id().to_string()) && natural.ends_with("analytical-engine.example.com"));
+    }
+
+    #[test]
+    fn porcelain_layout_is_four_tab_separated_fields() {
+        let id = "abcd1234-0000-4000-8000-000000000001";
+        let mut ada = Contact::with_id(
+            ContactId::parse(id).unwrap(),
+            "Ada".to_string(),
+            "Love\tlace".to_string(),
+            vec![
+                PhoneNumber::new("5550000001".to_string()).unwrap(),
+                PhoneNumber::new("5550000002".to_string()).unwrap(),
+            ],
+            vec![Email::new("ada@example.com".to_string()).unwrap()],
+        );
+
+        assert_eq!(
+            ContactFormatter::format_porcelain(&ada),
+            format!("{}\tAda Love lace\t5550000001\tada@example.com", id)
+        );
+
+        ada.remove_email(&Email::new("ada@example.com".to_string()).unwrap());
+        assert_eq!(ContactFormatter::format_porcelain(&ada), format!("{}\tAda Love lace\t5550000001\t", id));
+    }
+
+    #[test]
+    fn grouped_output_lists_two_tag_contacts_under_both_headers() {
+        let mut ann = contact("Ann", "Tester");
+        ann.add_tag("work".to_string());
+        ann.add_tag("Family".to_string());
+        let mut bob = contact("Bob", "Tester");
+        bob.add_tag("work".to_string());
+        let cal = contact("Cal", "Tester");
+
+        let output = ContactFormatter::format_grouped(&[ann, bob, cal], &Style::plain());
+        let groups: Vec<(&str, Vec<&str>)> = output
+            .split("\n\n")
+            .map(|group| {
+                let mut lines = group.lines();
+                let header = lines.next().unwrap();
+                let names = lines.map(|line| line.split("  ").filter(|cell| !cell.is_empty()).nth(1).unwrap().trim()).collect();
+                (header, names)
+            })
+            .collect();
+
+        assert_eq!(
+            groups,
+            [
+                ("Family (1 contact(s))", vec!["Ann Tester"]),
+                ("work (2 contact(s))", vec!["Ann Tester", "Bob Tester"]),
+                ("(untagged) (1 contact(s))", vec!["Cal Tester"]),
+            ],
+            "{}",
+            output
+        );
+    }
+
+    fn sample_stats() -> Stats {
+        Stats {
+            total_contacts: 3,
+            deleted_contacts: 1,
+            phone_numbers: 4,
+            emails: 2,
+            without_phone: 0,
+            without_email: 1,
+            with_birthday: 2,
+            untagged: 1,
+            tags: [("friends, old".to_string(), 1), ("work".to_string(), 2)].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn stats_serialize_to_json_fields() {
+        let json = serde_json::to_value(sample_stats()).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "total_contacts": 3,
+                "deleted_contacts": 1,
+                "phone_numbers": 4,
+                "emails": 2,
+                "without_phone": 0,
+                "without_email": 1,
+                "with_birthday": 2,
+                "untagged": 1,
+                "tags": { "friends, old": 1, "work": 2 },
+            })
+        );
+    }
+
+    #[test]
+    fn stats_csv_has_one_row_per_metric_and_tag() {
+        assert_eq!(
+            ContactFormatter::format_stats_csv(&sample_stats()),
+            [
+                "metric,value",
+                "total_contacts,3",
+                "deleted_contacts,1",
+                "phone_numbers,4",
+                "emails,2",
+                "without_phone,0",
+                "without_email,1",
+                "with_birthday,2",
+                "untagged,1",
+                "\"tag:friends, old\",1",
+                "tag:work,2",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn histogram_draws_one_bar_per_bucket_or_says_no_data() {
+        assert_eq!(ContactFormatter::format_histogram(&[]), "no data");
+
+        let buckets = vec![("2024-01".to_string(), 3), ("2024-02".to_string(), 12)];
+        assert_eq!(
+            ContactFormatter::format_histogram(&buckets),
+            "2024-01   3  ###\n2024-02  12  ############"
+        );
     }
 }
@@ -0,0 +1,185 @@
+use rustyline::error::ReadlineError;
+use rustyline::history::{History, SearchDirection};
+use rustyline::DefaultEditor;
+use std::io;
+
+/// What the user entered at the prompt
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReadLine {
+    /// A line to execute, with history references already expanded
+    Line(String),
+    /// End of input (Ctrl-D)
+    Eof,
+}
+
+/// Line editor for the interactive shell, backed by rustyline
+/// Adds cursor movement, arrow-key history and Ctrl-R search at a terminal, and
+/// expands `!!` (previous line) and `!N` (history entry N) before running a line
+pub struct LineEditor {
+    prompt: String,
+    editor: DefaultEditor,
+}
+
+impl LineEditor {
+    pub fn new(prompt: impl Into<String>) -> io::Result<Self> {
+        Ok(Self {
+            prompt: prompt.into(),
+            editor: DefaultEditor::new().map_err(io::Error::other)?,
+        })
+    }
+
+    /// Previously entered lines, oldest first
+    pub fn history(&self) -> Vec<String> {
+        (0..self.editor.history().len()).filter_map(|index| self.entry(index)).collect()
+    }
+
+    /// History entry at a zero-based index
+    fn entry(&self, index: usize) -> Option<String> {
+        let history = self.editor.history();
+        history
+            .get(index, SearchDirection::Forward)
+            .ok()
+            .flatten()
+            .map(|result| result.entry.into_owned())
+    }
+
+    /// Print the prompt and read the next non-empty line
+    /// Ctrl-C abandons the current line and prompts again
+    pub fn read_line(&mut self) -> io::Result<ReadLine> {
+        loop {
+            let input = match self.editor.readline(&self.prompt) {
+                Ok(input) => input,
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => {
+                    println!();
+                    return Ok(ReadLine::Eof);
+                }
+                Err(ReadlineError::Io(e)) => return Err(e),
+                Err(e) => return Err(io::Error::other(e)),
+            };
+
+            let line = input.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let line = match self.expand(line) {
+                Ok(line) => line,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    continue;
+                }
+            };
+
+            self.editor.add_history_entry(line.as_str()).map_err(io::Error::other)?;
+            return Ok(ReadLine::Line(line));
+        }
+    }
+
+    /// Expand a `!!` or `!N` history reference
+    fn expand(&self, line: &str) -> Result<String, String> {
+        let Some(reference) = line.strip_prefix('!') else {
+            return Ok(line.to_string());
+        };
+
+        let number = if reference == "!" {
+            self.editor.history().len()
+        } else {
+            reference
+                .parse()
+                .map_err(|_| format!("Invalid history reference: {}", line))?
+        };
+
+        match number.checked_sub(1).and_then(|index| self.entry(index)) {
+            Some(entry) => {
+                println!("{}", entry);
+                Ok(entry)
+            }
+            None => Err(format!("No such history entry: {}", line)),
+        }
+    }
+}
+
+/// Split a line into arguments, honouring single and double quotes and backslash escapes
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => current.push(c),
+            (_, '\\') => {
+                let escaped = chars.next().ok_or("Trailing backslash")?;
+                current.push(escaped);
+                in_arg = true;
+            }
+            (Some(_), c) => current.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if quote.is_some() {
+        return Err("Unterminated quote".to_string());
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor_with(entries: &[&str]) -> LineEditor {
+        let mut editor = LineEditor::new("> ").unwrap();
+        for entry in entries {
+            editor.editor.add_history_entry(*entry).unwrap();
+        }
+        editor
+    }
+
+    #[test]
+    fn split_args_honours_quotes_and_escapes() {
+        assert_eq!(
+            split_args(r#"add -f "Ada Mary" -n 'C:\notes' a\ b"#).unwrap(),
+            vec!["add", "-f", "Ada Mary", "-n", "C:\\notes", "a b"]
+        );
+        assert_eq!(split_args(r#"find """#).unwrap(), vec!["find", ""]);
+        assert!(split_args("find \"Ada").is_err());
+        assert!(split_args("find Ada\\").is_err());
+    }
+
+    #[test]
+    fn history_references_expand_to_earlier_lines() {
+        let editor = editor_with(&["list", "find Ada"]);
+
+        assert_eq!(editor.history(), vec!["list", "find Ada"]);
+        assert_eq!(editor.expand("!!").unwrap(), "find Ada");
+        assert_eq!(editor.expand("!1").unwrap(), "list");
+        assert_eq!(editor.expand("stats").unwrap(), "stats");
+        assert!(editor.expand("!0").is_err());
+        assert!(editor.expand("!3").is_err());
+        assert!(editor.expand("!x").is_err());
+        assert!(editor_with(&[]).expand("!!").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use crate::domain::entities::Contact;
+use crate::infrastructure::export::markdown_exporter::{escape, table};
+use crate::presentation::cli::formatters::Formatter;
+use crate::presentation::cli::style::Style;
+
+/// Formatter rendering contacts as Markdown, for pasting into notes or issues
+/// Lists and search results become GitHub-flavoured tables; terminal colors are never used
+pub struct MarkdownFormatter;
+
+impl MarkdownFormatter {
+    fn table(contacts: &[Contact], style: &Style) -> String {
+        let rows = contacts.iter().map(|contact| {
+            let phone = contact.phone_numbers().first().map(|p| p.to_string()).unwrap_or_default();
+            let email = contact.emails().first().map(|e| e.to_string()).unwrap_or_default();
+            vec![
+                contact.id().to_string(),
+                escape(&contact.display_name(style.name_format())),
+                escape(&phone),
+                escape(&email),
+            ]
+        });
+        table(&["ID", "Name", "Phone", "Email"], rows)
+    }
+}
+
+impl Formatter for MarkdownFormatter {
+    fn format_contact(&self, contact: &Contact, style: &Style) -> String {
+        let mut output = format!("## {}\n\n", escape(&contact.display_name(style.name_format())));
+        output.push_str(&format!("- **ID:** `{}`\n", contact.id()));
+
+        for phone in contact.phone_numbers() {
+            output.push_str(&format!("- **Phone:** {}\n", escape(&phone.to_string())));
+        }
+
+        for email in contact.emails() {
+            match email.label() {
+                Some(label) => output.push_str(&format!("- **Email:** {} ({})\n", escape(&email.to_string()), escape(label))),
+                None => output.push_str(&format!("- **Email:** {}\n", escape(&email.to_string()))),
+            }
+        }
+
+        if let Some(birthday) = contact.birthday() {
+            output.push_str(&format!("- **Birthday:** {}\n", birthday));
+        }
+
+        if !contact.tags().is_empty() {
+            output.push_str(&format!("- **Tags:** {}\n", escape(&contact.tags().join(", "))));
+        }
+
+        if let Some(notes) = contact.notes() {
+            output.push_str(&format!("\n{}\n", escape(notes)));
+        }
+
+        output
+    }
+
+    fn format_list(&self, contacts: &[Contact], style: &Style) -> String {
+        Self::table(contacts, style)
+    }
+
+    fn format_search(
+        &self,
+        query: &str,
+        total: usize,
+        contacts: &[Contact],
+        style: &Style,
+        _highlight: Option<&str>,
+    ) -> String {
+        let mut output = format!("**{} contact(s) matching `{}`**\n", total, query.replace('`', "'"));
+        if !contacts.is_empty() {
+            output.push('\n');
+            output.push_str(&Self::table(contacts, style));
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Email, PhoneNumber};
+    use crate::presentation::cli::formatters::ContactFormatter;
+
+    fn contacts() -> Vec<Contact> {
+        let mut ada = Contact::new(
+            "Ada".to_string(),
+            "Love|lace".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![Email::new("ada@example.com".to_string()).unwrap()],
+        );
+        ada.add_tag("math".to_string());
+        let bob = Contact::new(
+            "Bob".to_string(),
+            "Tester".to_string(),
+            vec![PhoneNumber::new("5550000002".to_string()).unwrap()],
+            vec![],
+        );
+        vec![ada, bob]
+    }
+
+    #[test]
+    fn both_formatters_render_the_same_contacts() {
+        let contacts = contacts();
+        let formatters: [&dyn Formatter; 2] = [&ContactFormatter, &MarkdownFormatter];
+
+        for formatter in formatters {
+            let list = formatter.format_list(&contacts, &Style::plain());
+            let search = formatter.format_search("a", 5, &contacts, &Style::plain(), None);
+            for contact in &contacts {
+                assert!(list.contains(&contact.id().to_string()[..8]), "{}", list);
+                assert!(search.contains(&contact.id().to_string()[..8]), "{}", search);
+            }
+            assert!(list.contains("Bob Tester"), "{}", list);
+            assert!(search.contains("5 contact(s) matching"), "{}", search);
+            assert!(formatter.format_contact(&contacts[0], &Style::plain()).contains("ada@example.com"));
+        }
+    }
+
+    #[test]
+    fn markdown_list_is_a_table_with_escaped_cells() {
+        let contacts = contacts();
+
+        let table = MarkdownFormatter.format_list(&contacts, &Style::plain());
+
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[..2], ["| ID | Name | Phone | Email |", "|----|------|-------|-------|"]);
+        assert_eq!(lines.len(), 4);
+        assert!(lines[2].contains("| Ada Love\\|lace |"), "{}", lines[2]);
+        assert!(lines[3].ends_with("| Bob Tester | (555) 000-0002 |  |"), "{}", lines[3]);
+    }
+
+    #[test]
+    fn markdown_contact_lists_every_field() {
+        let contact = &contacts()[0];
+
+        let output = MarkdownFormatter.format_contact(contact, &Style::plain());
+
+        assert!(output.starts_with("## Ada Love\\|lace\n\n"), "{}", output);
+        assert!(output.contains(&format!("- **ID:** `{}`\n", contact.id())));
+        assert!(output.contains("- **Email:** ada@example.com\n"));
+        assert!(output.contains("- **Tags:** math\n"));
+    }
+}
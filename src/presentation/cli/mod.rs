@@ -1,7 +1,14 @@
 pub mod commands;
 pub mod app;
 pub mod formatters;
+pub mod line_editor;
+pub mod markdown_formatter;
+pub mod style;
+pub mod template;
 
 pub use commands::*;
 pub use app::*;
 pub use formatters::*;
+pub use markdown_formatter::*;
+pub use style::*;
+pub use template::*;
@@ -0,0 +1,181 @@
+use crate::domain::entities::NameFormat;
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use terminal_size::{terminal_size, Width};
+
+/// Width assumed for a terminal whose size can't be determined
+pub const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+/// Width of the terminal stdout is attached to, or `None` when stdout isn't a terminal
+/// Reads `COLUMNS` first, then asks the terminal, falling back to `DEFAULT_TERMINAL_WIDTH`
+pub fn terminal_width() -> Option<usize> {
+    if !io::stdout().is_terminal() {
+        return None;
+    }
+
+    let from_env = std::env::var("COLUMNS").ok().and_then(|value| value.trim().parse().ok());
+    let from_terminal = || terminal_size().map(|(Width(width), _)| usize::from(width));
+
+    Some(
+        from_env
+            .filter(|width| *width > 0)
+            .or_else(from_terminal)
+            .filter(|width| *width > 0)
+            .unwrap_or(DEFAULT_TERMINAL_WIDTH),
+    )
+}
+
+/// When to emit ANSI colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    /// Resolve the choice against the current stdout
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorChoice::Auto => io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!("Invalid color choice: {}", s)),
+        }
+    }
+}
+
+impl std::fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorChoice::Auto => write!(f, "auto"),
+            ColorChoice::Always => write!(f, "always"),
+            ColorChoice::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// Minimal ANSI escape-code helper, plus how names are displayed and how wide lists may be
+/// A disabled style returns its input unchanged
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Style {
+    enabled: bool,
+    name_format: NameFormat,
+    width: Option<usize>,
+}
+
+impl Style {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            name_format: NameFormat::default(),
+            width: None,
+        }
+    }
+
+    /// A style that never emits escape codes
+    pub fn plain() -> Self {
+        Self::new(false)
+    }
+
+    /// Show contact names in the given order
+    pub fn with_name_format(mut self, name_format: NameFormat) -> Self {
+        self.name_format = name_format;
+        self
+    }
+
+    /// Fit contact lists into this many columns; `None` keeps the full fixed-width layout
+    pub fn with_width(mut self, width: Option<usize>) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The same style for text indented by `by` columns
+    pub fn indented(mut self, by: usize) -> Self {
+        self.width = self.width.map(|width| width.saturating_sub(by));
+        self
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn name_format(&self) -> NameFormat {
+        self.name_format
+    }
+
+    pub fn width(&self) -> Option<usize> {
+        self.width
+    }
+
+    /// Render text in bold
+    pub fn bold(&self, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[1m{}\x1b[22m", text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Highlight every case-insensitive occurrence of `query` in `text`
+    pub fn highlight(&self, text: &str, query: &str) -> String {
+        if !self.enabled {
+            return text.to_string();
+        }
+        Self::wrap_matches(text, query, "\x1b[7m", "\x1b[27m")
+    }
+
+    /// Like `highlight`, but falls back to `[brackets]` around matches when
+    /// color is off, so they stay visible in plain output
+    pub fn mark(&self, text: &str, query: &str) -> String {
+        if self.enabled {
+            self.highlight(text, query)
+        } else {
+            Self::wrap_matches(text, query, "[", "]")
+        }
+    }
+
+    /// Wrap case-insensitive occurrences of `query` in `open`/`close`
+    fn wrap_matches(text: &str, query: &str, open: &str, close: &str) -> String {
+        if query.is_empty() {
+            return text.to_string();
+        }
+
+        let haystack = text.to_lowercase();
+        let needle = query.to_lowercase();
+
+        // Lowercasing can change byte offsets for some scripts; only highlight
+        // when the offsets still line up with the original text
+        if haystack.len() != text.len() {
+            return text.to_string();
+        }
+
+        let mut output = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, _) in haystack.match_indices(&needle) {
+            if start < last {
+                continue;
+            }
+            let end = start + needle.len();
+            output.push_str(&text[last..start]);
+            output.push_str(open);
+            output.push_str(&text[start..end]);
+            output.push_str(close);
+            last = end;
+        }
+        output.push_str(&text[last..]);
+        output
+    }
+}
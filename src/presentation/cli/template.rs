@@ -0,0 +1,182 @@
+use crate::domain::entities::Contact;
+use std::str::FromStr;
+
+/// Per-contact output template, e.g. `{last_name}, {first_name}: {phone}`
+/// Placeholders are `id`, `first_name`, `last_name`, `full_name`, `phone`, `email`,
+/// `tags` and `notes`; write `{{` and `}}` for literal braces. Missing values
+/// (no phone, no notes, ...) render as empty text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContactTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Field(TemplateField),
+}
+
+/// Contact value a placeholder stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateField {
+    Id,
+    FirstName,
+    LastName,
+    FullName,
+    Phone,
+    Email,
+    Tags,
+    Notes,
+}
+
+impl TemplateField {
+    const NAMES: &'static str = "id, first_name, last_name, full_name, phone, email, tags, notes";
+
+    /// Value of the field for a contact; only the first phone and email are used
+    pub fn value(&self, contact: &Contact) -> String {
+        match self {
+            TemplateField::Id => contact.id().to_string(),
+            TemplateField::FirstName => contact.first_name().to_string(),
+            TemplateField::LastName => contact.last_name().to_string(),
+            TemplateField::FullName => contact.full_name(),
+            TemplateField::Phone => contact.phone_numbers().first().map(|p| p.to_string()).unwrap_or_default(),
+            TemplateField::Email => contact.emails().first().map(|e| e.to_string()).unwrap_or_default(),
+            TemplateField::Tags => contact.tags().join(", "),
+            TemplateField::Notes => contact
+                .notes()
+                .map(|n| n.split_whitespace().collect::<Vec<_>>().join(" "))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl FromStr for TemplateField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "id" => Ok(TemplateField::Id),
+            "first_name" => Ok(TemplateField::FirstName),
+            "last_name" => Ok(TemplateField::LastName),
+            "full_name" => Ok(TemplateField::FullName),
+            "phone" => Ok(TemplateField::Phone),
+            "email" => Ok(TemplateField::Email),
+            "tags" => Ok(TemplateField::Tags),
+            "notes" => Ok(TemplateField::Notes),
+            other => Err(format!(
+                "Unknown template placeholder '{{{}}}' (expected one of {})",
+                other,
+                Self::NAMES
+            )),
+        }
+    }
+}
+
+impl ContactTemplate {
+    /// Fill in the template for one contact
+    pub fn render(&self, contact: &Contact) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Field(field) => field.value(contact),
+            })
+            .collect()
+    }
+}
+
+impl FromStr for ContactTemplate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("Unclosed '{{' in template: {}", s)),
+                        }
+                    }
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Field(name.parse()?));
+                }
+                '}' => return Err(format!("Unmatched '}}' in template (write '}}}}' for a literal brace): {}", s)),
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::value_objects::{Email, PhoneNumber};
+
+    fn render(template: &str, contact: &Contact) -> String {
+        template.parse::<ContactTemplate>().unwrap().render(contact)
+    }
+
+    #[test]
+    fn placeholders_are_substituted() {
+        let mut ada = Contact::new(
+            "Ada".to_string(),
+            "Lovelace".to_string(),
+            vec![PhoneNumber::new("5550000001".to_string()).unwrap()],
+            vec![Email::new("ada@example.com".to_string()).unwrap()],
+        );
+        ada.add_tag("math".to_string());
+        ada.add_tag("poetry".to_string());
+        ada.set_notes(Some("Wrote the\nfirst program".to_string()));
+
+        assert_eq!(render("{last_name}, {first_name}: {phone}", &ada), "Lovelace, Ada: (555) 000-0001");
+        assert_eq!(render("{full_name} <{email}> [{tags}]", &ada), "Ada Lovelace <ada@example.com> [math, poetry]");
+        assert_eq!(render("{id}", &ada), ada.id().to_string());
+        assert_eq!(render("{notes}", &ada), "Wrote the first program");
+        assert_eq!(render("{{{first_name}}}", &ada), "{Ada}");
+    }
+
+    #[test]
+    fn missing_values_render_empty() {
+        let emailed = Contact::new(
+            "Bob".to_string(),
+            "Tester".to_string(),
+            vec![],
+            vec![Email::new("bob@example.com".to_string()).unwrap()],
+        );
+
+        assert_eq!(render("{first_name}|{phone}|{tags}|{notes}|{email}", &emailed), "Bob||||bob@example.com");
+    }
+
+    #[test]
+    fn unknown_placeholders_and_stray_braces_are_errors() {
+        let error = "{first_name} {nickname}".parse::<ContactTemplate>().unwrap_err();
+        assert!(error.contains("'{nickname}'") && error.contains("first_name"), "{}", error);
+
+        assert!("{first_name".parse::<ContactTemplate>().unwrap_err().contains("Unclosed"));
+        assert!("first_name}".parse::<ContactTemplate>().unwrap_err().contains("Unmatched"));
+    }
+}
@@ -0,0 +1,364 @@
+use crate::application::services::ContactService;
+use crate::application::use_cases::{
+    AddContactRequest, DeleteContactRequest, FindContactRequest, ListContactsRequest,
+    SearchContactsRequest, UpdateContactRequest,
+};
+use crate::domain::errors::ApplicationError;
+use crate::domain::value_objects::{ContactId, Date, Email, PhoneNumber, PhoneValidationPolicy};
+use axum::body::Bytes;
+use axum::extract::rejection::BytesRejection;
+use axum::extract::{DefaultBodyLimit, FromRef, Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Deserializer};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io;
+use std::net::{TcpListener, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+use tower_http::timeout::TimeoutLayer;
+
+/// Largest request body accepted; anything bigger is answered with 413
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Requests that take longer than this, including reading the body, are answered with 408
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// HTTP server exposing the contact use cases as a JSON API
+/// Requests are served concurrently; the blocking use cases run on tokio's blocking pool
+pub struct HttpServer {
+    contact_service: Arc<ContactService>,
+    phone_policy: Arc<PhoneValidationPolicy>,
+}
+
+/// State shared by the handlers; each takes only the part it needs
+#[derive(Clone)]
+struct ApiState {
+    contact_service: Arc<ContactService>,
+    phone_policy: Arc<PhoneValidationPolicy>,
+}
+
+impl FromRef<ApiState> for Arc<ContactService> {
+    fn from_ref(state: &ApiState) -> Self {
+        state.contact_service.clone()
+    }
+}
+
+impl FromRef<ApiState> for Arc<PhoneValidationPolicy> {
+    fn from_ref(state: &ApiState) -> Self {
+        state.phone_policy.clone()
+    }
+}
+
+/// Query string parameters
+type Params = Query<HashMap<String, String>>;
+
+/// Raw request body; a rejection (e.g. over `MAX_BODY_BYTES`) becomes a JSON error
+type Body = Result<Bytes, BytesRejection>;
+
+/// Error response with a JSON `{"error": ...}` body
+struct HttpError {
+    status: StatusCode,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: StatusCode, message: impl ToString) -> Self {
+        Self { status, message: message.to_string() }
+    }
+
+    fn bad_request(message: impl ToString) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+}
+
+impl IntoResponse for HttpError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+impl From<ApplicationError> for HttpError {
+    fn from(error: ApplicationError) -> Self {
+        let status = if error.is_not_found() {
+            StatusCode::NOT_FOUND
+        } else if error.is_validation() {
+            StatusCode::BAD_REQUEST
+        } else if error.is_conflict() {
+            StatusCode::CONFLICT
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        };
+        HttpError::new(status, error)
+    }
+}
+
+type HttpResult = Result<Response, HttpError>;
+
+/// Request body for `POST /contacts`
+#[derive(Debug, Deserialize)]
+struct NewContactBody {
+    first_name: String,
+    last_name: String,
+    #[serde(default)]
+    phone_numbers: Vec<String>,
+    #[serde(default)]
+    emails: Vec<String>,
+    notes: Option<String>,
+    birthday: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Request body for `PUT /contacts/:id`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct UpdateContactBody {
+    first_name: Option<String>,
+    last_name: Option<String>,
+    notes: Option<String>,
+    /// A date sets the birthday and `null` clears it; leaving it out keeps it
+    #[serde(deserialize_with = "present")]
+    birthday: Option<Option<String>>,
+    add_phone_numbers: Vec<String>,
+    remove_phone_numbers: Vec<String>,
+    add_emails: Vec<String>,
+    remove_emails: Vec<String>,
+    add_tags: Vec<String>,
+    remove_tags: Vec<String>,
+    /// Reject the update with 409 unless the contact is still at this version
+    expected_version: Option<u64>,
+}
+
+/// Tell a field set to `null` (`Some(None)`) apart from a missing one (`None`)
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::deserialize(deserializer).map(Some)
+}
+
+impl HttpServer {
+    /// Phone numbers in request bodies are validated against `phone_policy`, as the CLI does
+    pub fn new(contact_service: ContactService, phone_policy: PhoneValidationPolicy) -> Self {
+        Self {
+            contact_service: Arc::new(contact_service),
+            phone_policy: Arc::new(phone_policy),
+        }
+    }
+
+    /// Bind to `addr` and serve requests until the process is stopped
+    pub fn serve(self, addr: impl ToSocketAddrs) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        self.serve_listener(listener)
+    }
+
+    /// Serve requests from an already-bound listener
+    pub fn serve_listener(self, listener: TcpListener) -> io::Result<()> {
+        listener.set_nonblocking(true)?;
+        let runtime = tokio::runtime::Builder::new_multi_thread().enable_all().build()?;
+
+        runtime.block_on(async move {
+            let listener = tokio::net::TcpListener::from_std(listener)?;
+            axum::serve(listener, self.router()).await
+        })
+    }
+
+    /// Routes of the API, with the body size limit and request timeout applied
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/contacts", get(list_contacts).post(create_contact))
+            .route(
+                "/contacts/{id}",
+                get(get_contact).put(update_contact).delete(delete_contact),
+            )
+            .route("/search", get(search_contacts))
+            .fallback(|| async { HttpError::new(StatusCode::NOT_FOUND, "Not found") })
+            .method_not_allowed_fallback(|| async {
+                HttpError::new(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed")
+            })
+            .layer(DefaultBodyLimit::max(MAX_BODY_BYTES))
+            .layer(TimeoutLayer::with_status_code(StatusCode::REQUEST_TIMEOUT, REQUEST_TIMEOUT))
+            .with_state(ApiState {
+                contact_service: self.contact_service,
+                phone_policy: self.phone_policy,
+            })
+    }
+}
+
+/// Run a use case on the blocking pool so file I/O doesn't stall other requests
+async fn run<T, F>(service: Arc<ContactService>, call: F) -> Result<T, HttpError>
+where
+    T: Send + 'static,
+    F: FnOnce(&ContactService) -> Result<T, ApplicationError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || call(&service))
+        .await
+        .map_err(|e| HttpError::new(StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .map_err(HttpError::from)
+}
+
+async fn list_contacts(State(service): State<Arc<ContactService>>, Query(params): Params) -> HttpResult {
+    let defaults = ListContactsRequest::default();
+    let list_request = ListContactsRequest {
+        page: query_usize(&params, "page", defaults.page)?,
+        page_size: query_usize(&params, "page_size", defaults.page_size)?,
+        ..defaults
+    };
+
+    let response = run(service, move |service| service.list_contacts(list_request)).await?;
+    Ok(Json(json!({
+        "contacts": response.contacts,
+        "total_count": response.total_count,
+        "page": response.page,
+        "page_size": response.page_size,
+        "has_more": response.has_more,
+        "total_pages": response.total_pages,
+        "current_page": response.current_page,
+    }))
+    .into_response())
+}
+
+async fn get_contact(State(service): State<Arc<ContactService>>, Path(id): Path<String>) -> HttpResult {
+    let contact_id = parse_id(&id)?;
+
+    let response = run(service, move |service| service.find_contact(FindContactRequest { contact_id })).await?;
+    match response.contact {
+        Some(contact) => Ok(Json(json!(contact)).into_response()),
+        None => Err(HttpError::new(
+            StatusCode::NOT_FOUND,
+            format!("Contact not found with ID: {}", id),
+        )),
+    }
+}
+
+async fn create_contact(
+    State(service): State<Arc<ContactService>>,
+    State(phone_policy): State<Arc<PhoneValidationPolicy>>,
+    body: Body,
+) -> HttpResult {
+    let body: NewContactBody = parse_body(body)?;
+
+    let add_request = AddContactRequest {
+        first_name: body.first_name,
+        last_name: body.last_name,
+        phone_numbers: parse_phones(&body.phone_numbers, &phone_policy)?,
+        emails: parse_emails(&body.emails)?,
+        notes: body.notes,
+        birthday: body.birthday.as_deref().map(parse_date).transpose()?,
+        tags: body.tags,
+        ..Default::default()
+    };
+
+    let response = run(service, move |service| service.add_contact(add_request)).await?;
+    Ok((StatusCode::CREATED, Json(json!({ "id": response.contact_id }))).into_response())
+}
+
+async fn update_contact(
+    State(service): State<Arc<ContactService>>,
+    State(phone_policy): State<Arc<PhoneValidationPolicy>>,
+    Path(id): Path<String>,
+    body: Body,
+) -> HttpResult {
+    let contact_id = parse_id(&id)?;
+    let body: UpdateContactBody = parse_body(body)?;
+
+    let update_request = UpdateContactRequest {
+        contact_id,
+        first_name: body.first_name,
+        last_name: body.last_name,
+        notes: body.notes,
+        birthday: body
+            .birthday
+            .map(|birthday| birthday.as_deref().map(parse_date).transpose())
+            .transpose()?,
+        add_phone_numbers: parse_phones(&body.add_phone_numbers, &phone_policy)?,
+        remove_phone_numbers: parse_phones(&body.remove_phone_numbers, &phone_policy)?,
+        add_emails: parse_emails(&body.add_emails)?,
+        remove_emails: parse_emails(&body.remove_emails)?,
+        add_tags: body.add_tags,
+        remove_tags: body.remove_tags,
+        expected_version: body.expected_version,
+        ..Default::default()
+    };
+
+    let response = run(service, move |service| service.update_contact(update_request)).await?;
+    Ok(Json(json!(response.contact)).into_response())
+}
+
+async fn delete_contact(State(service): State<Arc<ContactService>>, Path(id): Path<String>) -> HttpResult {
+    let delete_request = DeleteContactRequest {
+        contact_id: parse_id(&id)?,
+        ..Default::default()
+    };
+
+    run(service, move |service| service.delete_contact(delete_request)).await?;
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn search_contacts(State(service): State<Arc<ContactService>>, Query(params): Params) -> HttpResult {
+    let defaults = SearchContactsRequest::default();
+    let search_request = SearchContactsRequest {
+        query: params.get("q").cloned().unwrap_or_default(),
+        limit: query_usize(&params, "limit", defaults.limit)?,
+        offset: query_usize(&params, "offset", defaults.offset)?,
+        cursor: params.get("cursor").cloned(),
+        ..defaults
+    };
+
+    let response = run(service, move |service| service.search_contacts(search_request)).await?;
+    Ok(Json(json!({
+        "query": response.query,
+        "count": response.count,
+        "contacts": response.contacts,
+        "next_cursor": response.next_cursor,
+    }))
+    .into_response())
+}
+
+/// Parse a JSON request body, whatever its Content-Type
+fn parse_body<T: for<'de> Deserialize<'de>>(body: Body) -> Result<T, HttpError> {
+    let body = body.map_err(|rejection| HttpError::new(rejection.status(), rejection.body_text()))?;
+    serde_json::from_slice(&body).map_err(|e| HttpError::bad_request(format!("Invalid JSON body: {}", e)))
+}
+
+fn parse_id(id: &str) -> Result<ContactId, HttpError> {
+    ContactId::parse(id).map_err(HttpError::bad_request)
+}
+
+fn parse_date(value: &str) -> Result<Date, HttpError> {
+    Date::parse(value).map_err(HttpError::bad_request)
+}
+
+fn parse_phones(values: &[String], policy: &PhoneValidationPolicy) -> Result<Vec<PhoneNumber>, HttpError> {
+    values
+        .iter()
+        .map(|value| {
+            PhoneNumber::new_with_policy(value.clone(), policy)
+                .map_err(|e| HttpError::bad_request(format!("Invalid phone number '{}': {}", value, e)))
+        })
+        .collect()
+}
+
+fn parse_emails(values: &[String]) -> Result<Vec<Email>, HttpError> {
+    values
+        .iter()
+        .map(|value| {
+            Email::parse_labeled(value)
+                .map_err(|e| HttpError::bad_request(format!("Invalid email '{}': {}", value, e)))
+        })
+        .collect()
+}
+
+fn query_usize(params: &HashMap<String, String>, key: &str, default: usize) -> Result<usize, HttpError> {
+    match params.get(key) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| HttpError::bad_request(format!("Invalid value for '{}': {}", key, value))),
+        None => Ok(default),
+    }
+}
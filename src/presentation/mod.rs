@@ -1,3 +1,8 @@
 pub mod cli;
+#[cfg(feature = "server")]
+pub mod http;
+pub mod output;
+pub mod progress;
 
 pub use cli::*;
+pub use output::OutputSink;
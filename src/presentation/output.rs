@@ -0,0 +1,68 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Stdout, Write};
+use std::path::Path;
+
+/// Destination for command output: stdout, or a file given with `--output`
+pub enum OutputSink {
+    Stdout(BufWriter<Stdout>),
+    File { path: String, writer: BufWriter<File> },
+}
+
+impl OutputSink {
+    /// Open a sink writing to `path`, or to stdout when no path (or `-`) is given
+    /// Parent directories of the file are created if needed
+    pub fn open(path: Option<&str>) -> io::Result<Self> {
+        match path.filter(|path| *path != "-") {
+            Some(path) => {
+                if let Some(parent) = Path::new(path).parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                Ok(OutputSink::File {
+                    path: path.to_string(),
+                    writer: BufWriter::new(File::create(path)?),
+                })
+            }
+            None => Ok(OutputSink::Stdout(BufWriter::new(io::stdout()))),
+        }
+    }
+
+    /// Path of the output file, if writing to one
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            OutputSink::Stdout(_) => None,
+            OutputSink::File { path, .. } => Some(path),
+        }
+    }
+
+    /// Whether output goes to a file rather than the terminal
+    pub fn is_file(&self) -> bool {
+        matches!(self, OutputSink::File { .. })
+    }
+
+    /// Flush the sink and, when writing to a file, report how many records were written
+    pub fn finish(mut self, records: usize) -> io::Result<()> {
+        self.flush()?;
+
+        if let Some(path) = self.path() {
+            println!("✓ Wrote {} record(s) to {}", records, path);
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for OutputSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            OutputSink::Stdout(writer) => writer.write(buf),
+            OutputSink::File { writer, .. } => writer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            OutputSink::Stdout(writer) => writer.flush(),
+            OutputSink::File { writer, .. } => writer.flush(),
+        }
+    }
+}
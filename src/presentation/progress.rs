@@ -0,0 +1,102 @@
+#[cfg(feature = "progress")]
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::fmt;
+
+/// Fewest items worth drawing a progress bar for
+pub const PROGRESS_THRESHOLD: usize = 100;
+
+/// A progress bar on stderr for long imports and exports
+/// Draws nothing unless stderr is a terminal and there are at least
+/// `PROGRESS_THRESHOLD` items, so piped and scripted runs stay quiet
+#[cfg(feature = "progress")]
+pub struct Progress {
+    label: &'static str,
+    bar: ProgressBar,
+}
+
+#[cfg(feature = "progress")]
+impl Progress {
+    /// Start tracking `total` items, drawing the empty bar if it will be shown
+    pub fn start(label: &'static str, total: usize) -> Self {
+        let target = if total >= PROGRESS_THRESHOLD {
+            // Hidden by indicatif itself when stderr is not a terminal
+            ProgressDrawTarget::stderr()
+        } else {
+            ProgressDrawTarget::hidden()
+        };
+
+        let bar = ProgressBar::with_draw_target(Some(total as u64), target).with_message(label);
+        bar.set_style(
+            ProgressStyle::with_template("{msg} [{bar:30}] {pos}/{len} ({percent}%) {eta}")
+                .expect("progress template is valid")
+                .progress_chars("#>-"),
+        );
+        bar.tick();
+        Self { label, bar }
+    }
+
+    /// Count one more item as done
+    pub fn inc(&self) {
+        self.bar.inc(1);
+    }
+
+    /// Print a line to stderr above the bar, e.g. a skipped record
+    pub fn message(&self, message: fmt::Arguments) {
+        self.bar.suspend(|| eprintln!("{}", message));
+    }
+
+    /// Replace the bar with a summary line
+    pub fn finish(self) {
+        if self.bar.is_hidden() {
+            return;
+        }
+
+        self.bar.finish_and_clear();
+        eprintln!(
+            "{}: {} item(s) in {:.1}s",
+            self.label,
+            self.bar.position(),
+            self.bar.elapsed().as_secs_f64()
+        );
+    }
+}
+
+/// Stand-in for builds without the `progress` feature: draws nothing,
+/// but still prints messages so skipped records are reported
+#[cfg(not(feature = "progress"))]
+pub struct Progress;
+
+#[cfg(not(feature = "progress"))]
+impl Progress {
+    pub fn start(_label: &'static str, _total: usize) -> Self {
+        Self
+    }
+
+    pub fn inc(&self) {}
+
+    pub fn message(&self, message: fmt::Arguments) {
+        eprintln!("{}", message);
+    }
+
+    pub fn finish(self) {}
+}
+
+#[cfg(all(test, feature = "progress"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_runs_are_not_drawn() {
+        let progress = Progress::start("Importing", PROGRESS_THRESHOLD - 1);
+        assert!(progress.bar.is_hidden());
+    }
+
+    #[test]
+    fn counts_items_as_they_finish() {
+        let progress = Progress::start("Exporting", PROGRESS_THRESHOLD);
+        (0..3).for_each(|_| progress.inc());
+        assert_eq!(progress.bar.position(), 3);
+        assert_eq!(progress.bar.length(), Some(PROGRESS_THRESHOLD as u64));
+        progress.finish();
+    }
+}
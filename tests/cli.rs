@@ -0,0 +1,659 @@
+use std::path::{Path, PathBuf};
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// A phonebook file in a fresh temporary directory, driven through the `dpbook` binary
+struct Phonebook {
+    _dir: tempfile::TempDir,
+    path: PathBuf,
+}
+
+impl Phonebook {
+    fn new() -> Self {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("contacts.json");
+        Self { _dir: dir, path }
+    }
+
+    fn command(&self, args: &[&str]) -> Command {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_dpbook"));
+        command
+            .arg("--file")
+            .arg(&self.path)
+            .args(args)
+            .env_remove("PHONEBOOK_FILE")
+            .env_remove("PHONEBOOK_PASSPHRASE")
+            .env_remove("RUST_LOG")
+            .env("NO_COLOR", "1");
+        command
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        self.command(args).output().unwrap()
+    }
+
+    /// Run a command with `input` on its stdin
+    fn run_with_input(&self, args: &[&str], input: &str) -> Output {
+        let mut child = self
+            .command(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // Commands that refuse to read stdin may exit before it is written
+        if let Err(e) = child.stdin.take().unwrap().write_all(input.as_bytes()) {
+            assert_eq!(e.kind(), std::io::ErrorKind::BrokenPipe, "{}", e);
+        }
+        child.wait_with_output().unwrap()
+    }
+
+    /// Run a command that must succeed and return its stdout
+    fn ok(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        assert!(
+            output.status.success(),
+            "{:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    /// Raw contents of the phonebook file
+    fn contents(&self) -> Vec<u8> {
+        std::fs::read(&self.path).unwrap()
+    }
+
+    /// Add a contact and return its ID
+    fn add(&self, first_name: &str, phone: &str) -> String {
+        let stdout = self.ok(&["add", "-f", first_name, "-l", "Tester", "-p", phone]);
+        stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("Contact ID: "))
+            .unwrap_or_else(|| panic!("no contact ID in: {}", stdout))
+            .trim()
+            .to_string()
+    }
+}
+
+/// `dpbook` run from `dir` without `--file`, with its profiles config kept in `dir`
+/// and no contacts-file environment variables, so the default file is used
+fn bare_command(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_dpbook"));
+    command
+        .current_dir(dir)
+        .args(args)
+        .env("XDG_CONFIG_HOME", dir.join("config"))
+        .env_remove("PHONEBOOK_FILE")
+        .env_remove("DPBOOK_DATA_DIR")
+        .env_remove("PHONEBOOK_PASSPHRASE")
+        .env_remove("RUST_LOG")
+        .env("NO_COLOR", "1");
+    command
+}
+
+/// Add a contact with a bare command and assert it succeeded
+fn bare_add(command: &mut Command, first_name: &str) {
+    let output = command
+        .args(["add", "-f", first_name, "-l", "Tester", "-p", "5550000001"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+fn file_mentions(path: &Path, name: &str) -> bool {
+    std::fs::read_to_string(path).is_ok_and(|contents| contents.contains(name))
+}
+
+#[test]
+fn trashed_contact_is_restored_by_id_prefix() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    let prefix = &id[..8];
+
+    phonebook.ok(&["delete", prefix, "--yes"]);
+    assert!(!phonebook.ok(&["list"]).contains("Ann"));
+    assert!(phonebook.ok(&["trash"]).contains("Ann"));
+
+    phonebook.ok(&["restore-contact", prefix]);
+    assert!(phonebook.ok(&["list"]).contains("Ann"));
+    assert!(!phonebook.ok(&["trash"]).contains("Ann"));
+}
+
+#[test]
+fn trashed_contact_is_purged_by_id_prefix() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    let prefix = &id[..8];
+
+    phonebook.ok(&["delete", prefix, "--yes"]);
+    phonebook.ok(&["delete", prefix, "--yes", "--purge"]);
+
+    assert!(!phonebook.ok(&["trash"]).contains("Ann"));
+    assert!(!phonebook.run(&["restore-contact", &id]).status.success());
+}
+
+#[test]
+fn trashed_contact_is_hidden_from_find_and_update() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    phonebook.ok(&["delete", &id, "--yes"]);
+
+    assert!(!phonebook.ok(&["find", &id]).contains("Ann"));
+    assert!(!phonebook.run(&["update", &id, "--first-name", "Changed"]).status.success());
+    assert!(phonebook.ok(&["trash"]).contains("Ann"));
+}
+
+#[test]
+fn failed_delete_or_restore_exits_non_zero() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+
+    let restore = phonebook.run(&["restore-contact", &id]);
+    assert!(!restore.status.success(), "restoring a live contact should fail");
+    assert!(!restore.stderr.is_empty());
+
+    assert!(!phonebook.run(&["restore-contact", "ffffffff"]).status.success());
+    assert!(!phonebook.run(&["delete", "ffffffff", "--yes"]).status.success());
+}
+
+#[test]
+fn add_with_an_invalid_phone_exits_non_zero() {
+    let phonebook = Phonebook::new();
+
+    let output = phonebook.run(&["add", "-f", "Ann", "-l", "Tester", "-p", "abc", "-p", "5550000001", "-p", "12"]);
+    assert!(!output.status.success(), "a bad phone number must fail the command");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'abc'") && stderr.contains("'12'"), "every bad number should be reported: {}", stderr);
+    assert!(phonebook.ok(&["list"]).contains("No contacts found"));
+}
+
+#[test]
+fn failed_lookups_exit_non_zero() {
+    let phonebook = Phonebook::new();
+    phonebook.add("Ann", "5550000001");
+
+    assert!(!phonebook.run(&["find", "--by-phone", "abc"]).status.success());
+    assert!(!phonebook.run(&["find", "--by-email", "not-an-email"]).status.success());
+    assert!(!phonebook.run(&["history", "not-an-id"]).status.success());
+    assert!(!phonebook.run(&["list", "--created-after", "yesterday"]).status.success());
+    assert!(!phonebook.run(&["list", "--since-snapshot", "missing.json"]).status.success());
+}
+
+#[test]
+fn delete_without_yes_refuses_piped_input() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+
+    let output = phonebook.run_with_input(&["delete", &id], "y\n");
+    assert!(!output.status.success(), "piped input must not confirm a deletion");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --yes"));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("(y/N)"), "no prompt should be shown");
+    assert!(phonebook.ok(&["find", &id]).contains("Ann Tester"));
+
+    phonebook.ok(&["delete", &id, "--yes"]);
+    assert!(phonebook.ok(&["trash"]).contains("Ann"));
+}
+
+#[test]
+fn file_flag_beats_profile_which_beats_the_default_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let dir = dir.path();
+    assert!(bare_command(dir, &["profile", "add", "work", "work.json"]).output().unwrap().status.success());
+
+    bare_add(&mut bare_command(dir, &["--file", "flag.json", "--profile", "work"]), "Flag");
+    bare_add(&mut bare_command(dir, &["--profile", "work"]), "Profile");
+    bare_add(&mut bare_command(dir, &[]), "Default");
+
+    assert!(file_mentions(&dir.join("flag.json"), "Flag"));
+    assert!(file_mentions(&dir.join("work.json"), "Profile"));
+    assert!(!file_mentions(&dir.join("work.json"), "Flag"));
+    assert!(file_mentions(&dir.join("contacts.json"), "Default"));
+    assert!(!file_mentions(&dir.join("contacts.json"), "Profile"));
+
+    assert!(!bare_command(dir, &["--profile", "missing", "list"]).output().unwrap().status.success());
+}
+
+#[test]
+fn phonebook_file_beats_data_dir_which_beats_contacts_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let dir = dir.path();
+    std::fs::create_dir(dir.join("data")).unwrap();
+
+    bare_add(bare_command(dir, &[]).env("PHONEBOOK_FILE", "env.json").env("DPBOOK_DATA_DIR", "data"), "Env");
+    bare_add(bare_command(dir, &[]).env("DPBOOK_DATA_DIR", "data"), "Data");
+    bare_add(&mut bare_command(dir, &[]), "Plain");
+
+    assert!(file_mentions(&dir.join("env.json"), "Env"));
+    assert!(file_mentions(&dir.join("data").join("contacts.json"), "Data"));
+    assert!(!file_mentions(&dir.join("data").join("contacts.json"), "Env"));
+    assert!(file_mentions(&dir.join("contacts.json"), "Plain"));
+    assert!(!file_mentions(&dir.join("contacts.json"), "Data"));
+}
+
+#[test]
+fn completion_scripts_cover_subcommands() {
+    let phonebook = Phonebook::new();
+    for shell in ["bash", "zsh", "fish", "elvish", "powershell"] {
+        let script = phonebook.ok(&["completions", shell]);
+        assert!(script.contains("restore-contact"), "{} script lacks subcommands", shell);
+    }
+    assert!(phonebook.ok(&["completions", "bash", "--bin-name", "pb"]).contains("_pb()"));
+}
+
+#[test]
+fn dry_run_update_and_delete_leave_the_file_unchanged() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    let before = phonebook.contents();
+
+    let preview = phonebook.ok(&["update", &id, "--first-name", "Anna", "--dry-run"]);
+    assert!(preview.contains("Anna"), "dry run should show the updated contact: {}", preview);
+    assert!(phonebook.ok(&["delete", &id, "--dry-run"]).contains("Ann"));
+    phonebook.ok(&["delete", &id, "--dry-run", "--purge", "--yes"]);
+
+    assert_eq!(phonebook.contents(), before);
+    assert!(phonebook.ok(&["find", &id]).contains("Ann Tester"));
+}
+
+#[test]
+fn history_lists_every_update() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    phonebook.ok(&["update", &id, "--first-name", "Anna"]);
+    phonebook.ok(&["update", &id, "--last-name", "Smith"]);
+
+    let history = phonebook.ok(&["history", &id[..8]]);
+    let lines: Vec<&str> = history.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", history);
+    assert!(lines[0].ends_with("first_name: 'Ann' -> 'Anna'"));
+    assert!(lines[1].ends_with("last_name: 'Tester' -> 'Smith'"));
+}
+
+#[test]
+fn invalid_changes_are_not_saved_on_any_write_path() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    phonebook.ok(&["update", &id, "--add-tag", "work"]);
+    let before = phonebook.contents();
+    let long_tag = "x".repeat(60);
+
+    let rename = phonebook.run(&["rename-tag", "work", &long_tag]);
+    assert!(!rename.status.success());
+    assert!(String::from_utf8_lossy(&rename.stderr).contains("cannot exceed"));
+
+    let update = phonebook.run(&["update", &id, "--add-tag", &long_tag]);
+    assert!(!update.status.success());
+    let update = phonebook.run(&["update", &id, "--remove-phone", "5550000001"]);
+    assert!(!update.status.success());
+
+    assert!(!phonebook.run(&["add", "-f", "Bob", "-l", "Tester", "-t", &long_tag, "-p", "5550000002"]).status.success());
+    assert_eq!(phonebook.contents(), before);
+}
+
+#[test]
+fn list_filter_selects_matching_contacts() {
+    let phonebook = Phonebook::new();
+    let ann = phonebook.add("Ann", "5550000001");
+    phonebook.add("Bob", "5550000002");
+    phonebook.ok(&["update", &ann, "--add-tag", "work"]);
+
+    let listed = phonebook.ok(&["list", "--filter", "tag:work or name:nobody"]);
+    assert!(listed.contains("Ann") && !listed.contains("Bob"), "{}", listed);
+
+    let invalid = phonebook.run(&["list", "--filter", "tag:work and"]);
+    assert!(!invalid.status.success());
+    assert!(String::from_utf8_lossy(&invalid.stderr).contains("Filter ends unexpectedly"));
+}
+
+#[test]
+fn list_since_snapshot_counts_added_modified_and_unchanged_contacts() {
+    let phonebook = Phonebook::new();
+    let ann = phonebook.add("Ann", "5550000001");
+    phonebook.add("Bob", "5550000002");
+    phonebook.add("Cal", "5550000003");
+    let snapshot = phonebook.path.with_file_name("snapshot.json");
+    let snapshot = snapshot.to_str().unwrap();
+    phonebook.ok(&["export", "--format", "json", "--output", snapshot]);
+
+    phonebook.ok(&["update", &ann, "--last-name", "Smith"]);
+    phonebook.add("Dee", "5550000004");
+
+    let changes = phonebook.ok(&["list", "--since-snapshot", snapshot]);
+    assert!(changes.starts_with("1 added, 1 modified, 2 unchanged since"), "{}", changes);
+    assert!(changes.contains("+ Dee Tester"), "{}", changes);
+    assert!(changes.contains("last_name: 'Tester' -> 'Smith'"), "{}", changes);
+}
+
+#[test]
+fn list_since_snapshot_applies_the_filters_and_rejects_paging() {
+    let phonebook = Phonebook::new();
+    let ann = phonebook.add("Ann", "5550000001");
+    phonebook.add("Bob", "5550000002");
+    let snapshot = phonebook.path.with_file_name("snapshot.json");
+    let snapshot = snapshot.to_str().unwrap();
+    phonebook.ok(&["export", "--format", "json", "--output", snapshot]);
+
+    phonebook.ok(&["update", &ann, "--add-tag", "work"]);
+    let dee = phonebook.add("Dee", "5550000004");
+    phonebook.ok(&["update", &dee, "--add-tag", "home"]);
+
+    let changes = phonebook.ok(&["list", "--since-snapshot", snapshot, "--filter", "tag:work"]);
+    assert!(changes.starts_with("0 added, 1 modified, 0 unchanged since"), "{}", changes);
+    assert!(!changes.contains("Dee"), "{}", changes);
+
+    let future = phonebook.ok(&["list", "--since-snapshot", snapshot, "--created-after", "2999-01-01"]);
+    assert!(future.starts_with("0 added, 0 modified, 0 unchanged since"), "{}", future);
+
+    for option in [["--page", "3"], ["--sort-by", "first-name"]] {
+        let mut args = vec!["list", "--since-snapshot", snapshot];
+        args.extend(option);
+        assert!(!phonebook.run(&args).status.success(), "{:?}", option);
+    }
+}
+
+#[test]
+fn list_page_size_is_capped_by_max_page_size() {
+    let phonebook = Phonebook::new();
+    phonebook.add("Ann", "5550000001");
+
+    let at_cap = phonebook.run(&["list", "--page-size", "100"]);
+    assert!(at_cap.status.success());
+    assert!(at_cap.stderr.is_empty(), "{}", String::from_utf8_lossy(&at_cap.stderr));
+    assert!(!phonebook.run(&["list", "--page-size", "101"]).status.success());
+
+    let raised = phonebook.run(&["--max-page-size", "150", "list", "--page-size", "150"]);
+    assert!(raised.status.success());
+    assert!(String::from_utf8_lossy(&raised.stderr).contains("may be slow"));
+    assert!(!phonebook.run(&["--max-page-size", "150", "list", "--page-size", "151"]).status.success());
+}
+
+#[test]
+fn search_pages_continue_from_the_printed_cursor() {
+    let phonebook = Phonebook::new();
+    for (name, phone) in [("Ann", "5550000001"), ("Anna", "5550000002"), ("Annie", "5550000003")] {
+        phonebook.add(name, phone);
+    }
+
+    let first = phonebook.ok(&["search", "ann", "--limit", "2", "--sort-by", "first-name"]);
+    assert!(first.contains("Showing 1 - 2 of 3 matches"), "{}", first);
+    let cursor = first
+        .lines()
+        .find_map(|line| line.strip_prefix("Next page: --cursor "))
+        .expect("the next cursor is printed");
+
+    let second = phonebook.ok(&["search", "ann", "--limit", "2", "--sort-by", "first-name", "--cursor", cursor]);
+    assert!(second.contains("Annie") && !second.contains("Anna "), "{}", second);
+    assert!(second.contains("Showing 3 - 3 of 3 matches"), "{}", second);
+    assert!(!second.contains("Next page"));
+
+    let unranked = ["search", "ann", "--limit", "1", "--sort-by", "first-name", "--no-relevance"];
+    let first = phonebook.ok(&unranked);
+    let cursor = first.lines().find_map(|line| line.strip_prefix("Next page: --cursor ")).unwrap();
+    let second = phonebook.ok(&[&unranked[..], &["--cursor", cursor]].concat());
+    assert!(second.contains("Anna") && second.contains("Showing 1 - 1 of 2 matches after the cursor"), "{}", second);
+
+    assert!(!phonebook.run(&["search", "ann", "--cursor", "not-a-cursor"]).status.success());
+}
+
+#[test]
+fn import_reads_csv_from_stdin() {
+    let phonebook = Phonebook::new();
+
+    let empty = phonebook.run_with_input(&["import", "-"], "");
+    assert!(empty.status.success());
+    assert!(String::from_utf8_lossy(&empty.stdout).contains("0 contact(s) imported"));
+
+    let csv = "first_name,last_name,phones,tags\nAda,Lovelace,5551234567,math\nNo,Phone,,\n";
+    let output = phonebook.run_with_input(&["import", "-"], csv);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 contact(s) imported"), "{}", stdout);
+    assert!(stdout.contains("1 row(s) skipped"), "{}", stdout);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Line 3"));
+
+    assert!(phonebook.ok(&["list"]).contains("Ada Lovelace"));
+}
+
+#[test]
+fn import_follows_the_phone_policy() {
+    let phonebook = Phonebook::new();
+    let csv = "first_name,last_name,phones\nEmergency,Line,911\n";
+
+    let strict = phonebook.run_with_input(&["import", "-"], csv);
+    assert!(String::from_utf8_lossy(&strict.stderr).contains("Invalid phone number '911'"));
+
+    let output = phonebook.run_with_input(&["--allow-short-codes", "import", "-"], csv);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1 contact(s) imported"), "{}", stdout);
+    assert!(phonebook.ok(&["--allow-short-codes", "list"]).contains("Emergency Line"));
+}
+
+#[test]
+fn import_upsert_replaces_contacts_with_a_matching_id() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    phonebook.ok(&["update", &id, "--first-name", "Anna"]);
+    let before: serde_json::Value = serde_json::from_slice(&phonebook.contents()).unwrap();
+
+    let csv = format!("id,first_name,last_name,phones,tags\n{},Ada,Lovelace,5550000009;5550000009,math\n", id);
+    let output = phonebook.run_with_input(&["import", "-", "--upsert"], &csv);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("0 contact(s) imported"), "{}", stdout);
+    assert!(stdout.contains("1 contact(s) replaced"), "{}", stdout);
+
+    let data: serde_json::Value = serde_json::from_slice(&phonebook.contents()).unwrap();
+    let (stored, original) = (&data["contacts"][&id], &before["contacts"][&id]);
+    assert_eq!(stored["first_name"], "Ada");
+    assert_eq!(stored["last_name"], "Lovelace");
+    assert_eq!(stored["phone_numbers"], serde_json::json!([{ "value": "5550000009" }]));
+    assert_eq!(stored["tags"], serde_json::json!(["math"]));
+    assert_eq!(stored["created_at"], original["created_at"]);
+    assert_eq!(stored["history"], original["history"]);
+    assert_eq!(stored["version"], 2);
+}
+
+#[test]
+fn recover_flag_opens_a_file_with_a_broken_entry() {
+    let phonebook = Phonebook::new();
+    phonebook.add("Ann", "5550000001");
+    let mut document: serde_json::Value = serde_json::from_slice(&phonebook.contents()).unwrap();
+    document["contacts"]["00000000-0000-4000-8000-000000000009"] = serde_json::json!({ "first_name": 42 });
+    std::fs::write(&phonebook.path, document.to_string()).unwrap();
+
+    let strict = phonebook.run(&["list"]);
+    assert!(!strict.status.success());
+    assert!(String::from_utf8_lossy(&strict.stderr).contains("Serialization error"));
+
+    let recovered = phonebook.run(&["--recover", "list"]);
+    assert!(String::from_utf8_lossy(&recovered.stdout).contains("Ann Tester"));
+    assert!(String::from_utf8_lossy(&recovered.stderr).contains("Skipping contact '00000000-0000-4000-8000-000000000009'"));
+}
+
+#[test]
+fn validate_reports_a_broken_entry_alongside_other_problems() {
+    let phonebook = Phonebook::new();
+    let id = phonebook.add("Ann", "5550000001");
+    let mut document: serde_json::Value = serde_json::from_slice(&phonebook.contents()).unwrap();
+    document["contacts"]["00000000-0000-4000-8000-000000000009"] = serde_json::json!({ "first_name": 42 });
+    document["contacts"][&id]["emails"] = serde_json::json!([{ "value": "not-an-email" }]);
+    std::fs::write(&phonebook.path, document.to_string()).unwrap();
+
+    let output = phonebook.run(&["validate"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("00000000-0000-4000-8000-000000000009: entry could not be read"), "{}", stdout);
+    assert!(stdout.contains(&format!("{}: email 'not-an-email' has an invalid format", id)), "{}", stdout);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("2 problem(s) found in 2 contact(s)"));
+}
+
+#[test]
+fn purge_all_needs_confirmation_or_yes() {
+    let phonebook = Phonebook::new();
+    phonebook.add("Ann", "5550000001");
+    let id = phonebook.add("Bob", "5550000002");
+    phonebook.ok(&["delete", &id, "--yes"]);
+
+    let unconfirmed = phonebook.run_with_input(&["purge-all"], "2\n");
+    assert!(!unconfirmed.status.success(), "piped input must not confirm a purge");
+    assert!(phonebook.ok(&["list"]).contains("Ann"));
+
+    let purged = phonebook.ok(&["purge-all", "--yes"]);
+    assert!(purged.contains("Permanently deleted 2 contact(s)"), "{}", purged);
+    assert!(!phonebook.ok(&["list"]).contains("Ann"));
+    assert!(!phonebook.ok(&["trash"]).contains("Bob"));
+}
+
+#[test]
+fn count_only_prints_a_bare_number_after_narrowing() {
+    let phonebook = Phonebook::new();
+    phonebook.ok(&["add", "-f", "Ann", "-l", "Tester", "-p", "5550000001", "-t", "work"]);
+    phonebook.ok(&["add", "-f", "Anna", "-l", "Tester", "-p", "5550000002", "-t", "work"]);
+    phonebook.add("Annie", "5550000003");
+    phonebook.add("Bob", "5550000004");
+
+    assert_eq!(phonebook.ok(&["list", "--count-only"]), "4\n");
+    assert_eq!(phonebook.ok(&["list", "--count-only", "--page-size", "1"]), "4\n");
+    assert_eq!(phonebook.ok(&["list", "--count-only", "--filter", "tag:work"]), "2\n");
+    assert_eq!(phonebook.ok(&["search", "ann", "--count-only"]), "3\n");
+    assert_eq!(phonebook.ok(&["search", "ann", "--count-only", "--limit", "1"]), "3\n");
+    assert_eq!(phonebook.ok(&["search", "zed", "--count-only"]), "0\n");
+}
+
+#[test]
+fn list_rejects_an_invalid_date_bound() {
+    let phonebook = Phonebook::new();
+    phonebook.add("Ann", "5550000001");
+
+    let output = phonebook.run(&["list", "--created-after", "2024-13-01"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid date or time '2024-13-01'"));
+    assert!(phonebook.ok(&["list", "--created-after", "2000-01-01"]).contains("Ann"));
+}
+
+#[test]
+fn metadata_can_be_set_overwritten_and_removed() {
+    let phonebook = Phonebook::new();
+    let stdout = phonebook.ok(&[
+        "add", "-f", "Ann", "-l", "Tester", "-p", "5550000001", "--meta", "team=blue", "--meta", "desk=4B",
+    ]);
+    let id = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Contact ID: "))
+        .unwrap()
+        .trim()
+        .to_string();
+    assert!(phonebook.ok(&["find", &id]).contains("Metadata:\n  desk: 4B\n  team: blue\n"));
+
+    phonebook.ok(&["update", &id, "--meta", "team=red"]);
+    assert!(phonebook.ok(&["find", &id]).contains("Metadata:\n  desk: 4B\n  team: red\n"));
+
+    phonebook.ok(&["update", &id, "--remove-meta", "desk"]);
+    let found = phonebook.ok(&["find", &id]);
+    assert!(found.contains("Metadata:\n  team: red\n") && !found.contains("desk"), "{}", found);
+
+    let output = phonebook.run(&["update", &id, "--meta", "no-equals-sign"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no-equals-sign"));
+}
+
+#[test]
+fn debug_logging_reports_saves_only_when_asked() {
+    let phonebook = Phonebook::new();
+
+    let quiet = phonebook.run(&["add", "-f", "Ann", "-l", "Tester", "-p", "5550000001"]);
+    assert!(quiet.status.success());
+    assert!(quiet.stderr.is_empty(), "{}", String::from_utf8_lossy(&quiet.stderr));
+
+    let verbose = phonebook.run(&["-vv", "add", "-f", "Bob", "-l", "Tester", "-p", "5550000002"]);
+    assert!(verbose.status.success());
+    let stderr = String::from_utf8_lossy(&verbose.stderr);
+    assert!(
+        stderr.lines().any(|line| line.starts_with("debug: Saved 2 contact(s)") && line.contains("contacts.json")),
+        "{}",
+        stderr
+    );
+    assert!(!String::from_utf8_lossy(&verbose.stdout).contains("debug:"), "logs belong on stderr");
+}
+
+#[test]
+fn export_writes_only_the_tagged_or_filtered_contacts_in_every_format() {
+    let phonebook = Phonebook::new();
+    phonebook.ok(&["add", "-f", "Ann", "-l", "Worker", "-p", "5550000001", "-t", "work"]);
+    phonebook.ok(&["add", "-f", "Bob", "-l", "Homebody", "-p", "5550000002", "-t", "home"]);
+    phonebook.ok(&["add", "-f", "Cal", "-l", "Worker", "-p", "5550000003", "-t", "work", "-t", "home"]);
+
+    for format in ["json", "jsonl", "vcard", "markdown"] {
+        for narrowing in [["--tag", "work"], ["--filter", "tag:work"]] {
+            let mut args = vec!["export", "--format", format];
+            args.extend(narrowing);
+            let exported = phonebook.ok(&args);
+
+            assert!(exported.contains("Ann") && exported.contains("Cal"), "{:?}: {}", args, exported);
+            assert!(!exported.contains("Bob"), "{:?}: {}", args, exported);
+        }
+    }
+}
+
+#[test]
+fn disposable_emails_warn_or_are_rejected_with_no_disposable() {
+    let phonebook = Phonebook::new();
+
+    let warned = phonebook.run(&["add", "-f", "Ann", "-l", "Tester", "-e", "ann@10minutemail.com"]);
+    assert!(warned.status.success());
+    assert!(String::from_utf8_lossy(&warned.stderr).contains("'ann@10minutemail.com' is a disposable email address"));
+
+    let rejected = phonebook.run(&["add", "-f", "Bob", "-l", "Tester", "-e", "bob@10minutemail.com", "--no-disposable"]);
+    assert!(!rejected.status.success());
+    assert!(!phonebook.ok(&["list"]).contains("Bob"));
+}
+
+#[test]
+fn list_all_shows_every_contact_on_one_page() {
+    let phonebook = Phonebook::new();
+    for i in 0..12 {
+        phonebook.add(&format!("Person{:02}", i), &format!("55500000{:02}", i));
+    }
+
+    let listed = phonebook.ok(&["list", "--all"]);
+
+    assert!((0..12).all(|i| listed.contains(&format!("Person{:02}", i))), "{}", listed);
+    assert!(listed.contains("Showing all 12 contacts"), "{}", listed);
+    assert!(!phonebook.run(&["list", "--all", "--page-size", "5"]).status.success());
+}
+
+#[test]
+fn init_creates_an_empty_phonebook_and_keeps_existing_files_without_force() {
+    let phonebook = Phonebook::new();
+
+    let created = phonebook.ok(&["init"]);
+    assert!(created.contains("Created an empty phonebook") && created.contains("Next steps:"), "{}", created);
+    let data: serde_json::Value = serde_json::from_slice(&phonebook.contents()).unwrap();
+    assert_eq!(data["contacts"], serde_json::json!({}));
+    assert!(phonebook.ok(&["list"]).contains("No contacts found"));
+
+    phonebook.add("Ann", "5550000001");
+    let again = phonebook.run(&["init"]);
+    assert!(!again.status.success());
+    assert!(String::from_utf8_lossy(&again.stderr).contains("--force"));
+    assert!(phonebook.ok(&["list"]).contains("Ann"));
+
+    phonebook.ok(&["init", "--force"]);
+    assert!(phonebook.ok(&["list"]).contains("No contacts found"));
+}
+
+#[test]
+fn piped_imports_and_exports_draw_no_progress_bar() {
+    let phonebook = Phonebook::new();
+    let rows: String = (0..150).map(|i| format!("Ann{},Tester,555{:07}\n", i, i)).collect();
+
+    let imported = phonebook.run_with_input(&["import", "-"], &format!("first_name,last_name,phones\n{}", rows));
+    assert!(imported.status.success(), "{}", String::from_utf8_lossy(&imported.stderr));
+    assert!(String::from_utf8_lossy(&imported.stdout).contains("150 contact(s) imported"));
+    assert!(imported.stderr.is_empty(), "{}", String::from_utf8_lossy(&imported.stderr));
+
+    let exported = phonebook.command(&["export", "--format", "jsonl"]).output().unwrap();
+    assert!(exported.status.success());
+    assert_eq!(String::from_utf8_lossy(&exported.stdout).lines().count(), 150);
+    assert!(exported.stderr.is_empty(), "{}", String::from_utf8_lossy(&exported.stderr));
+}
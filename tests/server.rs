@@ -0,0 +1,157 @@
+#![cfg(feature = "server")]
+
+use dpbook::application::services::ContactService;
+use dpbook::domain::value_objects::PhoneValidationPolicy;
+use dpbook::infrastructure::FileContactRepository;
+use dpbook::presentation::http::server::MAX_BODY_BYTES;
+use dpbook::presentation::http::HttpServer;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+/// Start a server on a free local port, backed by a fresh contacts file in `dir`
+fn start_server(dir: &tempfile::TempDir) -> SocketAddr {
+    start_server_with_policy(dir, PhoneValidationPolicy::default())
+}
+
+fn start_server_with_policy(dir: &tempfile::TempDir, phone_policy: PhoneValidationPolicy) -> SocketAddr {
+    let path = dir.path().join("contacts.json").to_string_lossy().into_owned();
+    let service = ContactService::new(Arc::new(FileContactRepository::new(path)));
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    thread::spawn(move || HttpServer::new(service, phone_policy).serve_listener(listener));
+    addr
+}
+
+/// Send one request and return the status code and JSON body (`Null` when empty)
+fn request(addr: SocketAddr, method: &str, path: &str, body: Option<&[u8]>) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let body = body.unwrap_or_default();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        method,
+        path,
+        body.len()
+    )
+    .unwrap();
+    stream.write_all(body).unwrap();
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+    let response = String::from_utf8(response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let body = if body.is_empty() { Value::Null } else { serde_json::from_str(body).unwrap() };
+    (status, body)
+}
+
+fn send_json(addr: SocketAddr, method: &str, path: &str, body: Value) -> (u16, Value) {
+    request(addr, method, path, Some(body.to_string().as_bytes()))
+}
+
+#[test]
+fn contact_round_trips_through_the_api() {
+    let dir = tempfile::tempdir().unwrap();
+    let addr = start_server(&dir);
+
+    let (status, created) = send_json(
+        addr,
+        "POST",
+        "/contacts",
+        json!({
+            "first_name": "Ada",
+            "last_name": "Lovelace",
+            "phone_numbers": ["5551234567"],
+            "emails": ["ada@example.com"],
+            "tags": ["math"],
+        }),
+    );
+    assert_eq!(status, 201, "{}", created);
+    let id = created["id"].as_str().unwrap().to_string();
+
+    let (status, contact) = request(addr, "GET", &format!("/contacts/{}", id), None);
+    assert_eq!(status, 200);
+    assert_eq!(contact["first_name"], "Ada");
+    assert_eq!(contact["last_name"], "Lovelace");
+
+    let (status, updated) = send_json(
+        addr,
+        "PUT",
+        &format!("/contacts/{}", id),
+        json!({ "last_name": "King", "birthday": "1815-12-10" }),
+    );
+    assert_eq!(status, 200, "{}", updated);
+    assert_eq!(updated["last_name"], "King");
+
+    let (_, contact) = request(addr, "GET", &format!("/contacts/{}", id), None);
+    assert_eq!(contact["last_name"], "King");
+    assert!(contact["birthday"].to_string().contains("1815"), "birthday was not saved: {}", contact);
+
+    let (status, cleared) = send_json(addr, "PUT", &format!("/contacts/{}", id), json!({ "birthday": null }));
+    assert_eq!(status, 200);
+    assert!(cleared["birthday"].is_null(), "birthday was not cleared: {}", cleared);
+
+    let (status, search) = request(addr, "GET", "/search?q=ada", None);
+    assert_eq!(status, 200);
+    assert_eq!(search["contacts"][0]["id"], contact["id"]);
+
+    let (status, _) = request(addr, "DELETE", &format!("/contacts/{}", id), None);
+    assert_eq!(status, 204);
+    let (status, list) = request(addr, "GET", "/contacts", None);
+    assert_eq!(status, 200);
+    assert_eq!(list["total_count"], 0);
+    let (status, _) = request(addr, "GET", &format!("/contacts/{}", id), None);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn bad_requests_get_json_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let addr = start_server(&dir);
+
+    let (status, body) = request(addr, "POST", "/contacts", Some(b"{not json"));
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("Invalid JSON body"));
+
+    let (status, body) = request(addr, "GET", "/contacts?page=abc", None);
+    assert_eq!(status, 400);
+    assert!(body["error"].is_string());
+
+    let (status, _) = request(addr, "PATCH", "/contacts", None);
+    assert_eq!(status, 405);
+
+    let (status, _) = request(addr, "GET", "/nowhere", None);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn phones_are_validated_against_the_server_policy() {
+    let short_code = json!({ "first_name": "Emergency", "last_name": "Line", "phone_numbers": ["911"] });
+
+    let strict_dir = tempfile::tempdir().unwrap();
+    let (status, body) = send_json(start_server(&strict_dir), "POST", "/contacts", short_code.clone());
+    assert_eq!(status, 400, "{}", body);
+
+    let dir = tempfile::tempdir().unwrap();
+    let addr = start_server_with_policy(&dir, PhoneValidationPolicy::new(10, 15, true).unwrap());
+    let (status, created) = send_json(addr, "POST", "/contacts", short_code);
+    assert_eq!(status, 201, "{}", created);
+    let id = created["id"].as_str().unwrap();
+    let (status, updated) = send_json(addr, "PUT", &format!("/contacts/{}", id), json!({ "add_phone_numbers": ["112"] }));
+    assert_eq!(status, 200, "{}", updated);
+    assert_eq!(updated["phone_numbers"][1]["value"], "112");
+}
+
+#[test]
+fn oversized_body_is_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let addr = start_server(&dir);
+
+    let body = vec![b' '; MAX_BODY_BYTES + 1];
+    let (status, _) = request(addr, "POST", "/contacts", Some(&body));
+    assert_eq!(status, 413);
+}